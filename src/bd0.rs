@@ -25,6 +25,8 @@ use crate::libc::*;
 use crate::nmath::*;
 use crate::pgamma::log1pmx;
 use crate::rmath::*;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 use libm::frexp;
 use libm::ldexp;
 
@@ -74,7 +76,7 @@ pub fn bd0(x: f64, np: f64) -> f64 {
             }
         }
     }
-    println!("bd0: T.series failed to converge in 1000 iterations");
+    ml_warning(MlWarningKind::NoConv, "bd0");
     x * log(x / np) + np - x
 }
 
@@ -686,10 +688,10 @@ pub fn ebd0(x: f64, m: f64) -> (f64, f64) {
     if fg == 1.0 {
         return (yh, yl);
     }
-    for j in 0..4 {
-        add1(x * BD0_SCALE[i as usize][j] as f64, &mut yh, &mut yl);
+    for (&scale_i, &scale_0) in BD0_SCALE[i as usize].iter().zip(BD0_SCALE[0].iter()) {
+        add1(x * scale_i as f64, &mut yh, &mut yl);
         // `e` at end prevents overflow in  ebd0(1e307, 1e300).
-        add1(-x * (BD0_SCALE[0][j] * e as f32) as f64, &mut yh, &mut yl);
+        add1(-x * scale_0 as f64 * e as f64, &mut yh, &mut yl);
         if !r_finite(yh) {
             yh = ML_POSINF;
             yl = 0.0;
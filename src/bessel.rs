@@ -0,0 +1,87 @@
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 2001-2014  R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+// Constants and documentation that apply to several of the
+// bessel_[ijky].rs files.
+//
+// NSIG   = Decimal significance desired.  Should be set to
+//          INT(LOG10(2)*it+1).  Setting NSIG lower will result
+//          in decreased accuracy while setting NSIG higher will
+//          increase CPU time without increasing accuracy.  The
+//          truncation error is limited to a relative error of
+//          T=.5*10^(-NSIG).
+// ENTEN  = 10 ^ K, where K is the largest int such that
+//          ENTEN is machine-representable in working precision
+// ENSIG  = 10 ^ NSIG
+// RTNSIG = 10 ^ (-K) for the smallest int K such that K >= NSIG/4
+// ENMTEN = Smallest ABS(X) such that X/4 does not underflow
+//
+// For I :
+//
+// EXPARG = Largest working precision argument that the library
+//          EXP routine can handle and upper limit on the
+//          magnitude of X when IZE=1; approximately LOG(beta ^ maxexp)
+//
+// For I and J :
+//
+// xlrg_IJ = Upper limit on the magnitude of X (when IZE=2 for I()).
+//          Bear in mind that if floor(abs(x)) =: N, then at least N
+//          iterations of the backward recursion will be executed.
+//          The value of 10 ^ 4 was used till Feb.2009, when it was
+//          increased to 10 ^ 5 (= 1e5).
+//
+// For Y :
+//
+// xlrg_Y = Upper bound on X; approximately 1/DEL, because the sine and
+//          cosine functions have lost about half of their precision at
+//          that point.
+// THRESH = Lower bound for use of the asymptotic form;
+//          approximately AINT(-LOG10(EPS/2.0))+1.0
+//
+// For K :
+//
+// xmax_k = Upper limit on the magnitude of X when ize = 1;
+//          i.e. maximal x for UNscaled answer.
+//
+//          Solution to equation:
+//             W(X) * (1 -1/8 X + 9/128 X^2) = beta ^ minexp
+//          where  W(X) = EXP(-X)*SQRT(PI/2X)
+//
+// The values below are those for IEEE double precision.
+
+pub const NSIG_BESS: i32 = 16;
+pub const ENSIG_BESS: f64 = 1e16;
+pub const RTNSIG_BESS: f64 = 1e-4;
+pub const ENMTEN_BESS: f64 = 8.9e-308;
+pub const ENTEN_BESS: f64 = 1e308;
+
+pub const EXPARG_BESS: f64 = 709.;
+pub const XLRG_BESS_IJ: f64 = 1e5;
+pub const XLRG_BESS_Y: f64 = 1e8;
+pub const THRESH_BESS_Y: f64 = 16.;
+
+/// Maximal x for UNscaled answer.
+pub const XMAX_BESS_K: f64 = 705.342;
+
+/// sqrt(DBL_MIN) = 1.491668e-154
+pub const SQXMIN_BESS_K: f64 = 1.49e-154;
+
+/// x < eps_sinc  <==>  sin(x)/x == 1 (particularly "==>");
+/// Linux (around 2001-02) gives 2.14946906753213e-08
+/// Solaris 2.5.1 gives 2.14911933289084e-08
+pub const M_EPS_SINC: f64 = 2.149e-8;
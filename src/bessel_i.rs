@@ -0,0 +1,486 @@
+use crate::bessel::ENMTEN_BESS;
+use crate::bessel::ENSIG_BESS;
+use crate::bessel::ENTEN_BESS;
+use crate::bessel::EXPARG_BESS;
+use crate::bessel::NSIG_BESS;
+use crate::bessel::RTNSIG_BESS;
+use crate::bessel::XLRG_BESS_IJ;
+use crate::bessel_k::bessel_k_ex;
+use crate::fmax2::fmax2;
+use crate::gamma_cody::gamma_cody;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_POSINF;
+use crate::rmath::M_PI;
+use crate::sinpi;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+use libm::ldexp;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998-2014 Ross Ihaka and the R Core team.
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+// From http://www.netlib.org/specfun/ribesl  Fortran translated by f2c,...
+//   ------------------------------=#----  Martin Maechler, ETH Zurich
+
+/// The modified Bessel function of the first kind, `I_alpha(x)`.
+///
+/// If `expo` is 2, the exponentially scaled `exp(-x) I_alpha(x)` is
+/// returned instead.
+/// Negative orders `alpha` use the reflection formula
+/// `I_{-a}(x) = I_a(x) + 2/pi sin(pi a) K_a(x)`
+/// (Abramowitz & Stegun 9.6.2 & 9.6.6).
+///
+/// This allocates a work buffer of `1 + floor(|alpha|)` values;
+/// see `bessel_i_ex` to provide one instead.
+///
+/// An `MlWarningKind::Range` warning is raised for negative `x`, for
+/// `|alpha| > 1e7` where NaN is returned, and for other arguments out of
+/// range.
+/// An `MlWarningKind::Precision` warning is raised when precision was
+/// lost in the result; see `take_warnings`.
+pub fn bessel_i(x: f64, alpha: f64, expo: f64) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    // No buffer is used when alpha is too large for the algorithm.
+    let nb = if alpha.abs() > 1e7 {
+        0
+    } else {
+        1 + alpha.abs().floor() as usize
+    };
+    let mut bi = vec![0.0; nb];
+    bessel_i_ex(x, alpha, expo, &mut bi)
+}
+
+/// Modified version of `bessel_i` that accepts a work buffer instead of
+/// allocating one.
+///
+/// # Panics
+///
+/// If `bi` is shorter than `1 + floor(|alpha|)`.
+pub fn bessel_i_ex(x: f64, alpha: f64, expo: f64, bi: &mut [f64]) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    if x < 0. {
+        ml_warning(MlWarningKind::Range, "bessel_i");
+        return ML_NAN;
+    }
+    if alpha.abs() > 1e7 {
+        // besselI(x, nu): nu too large for bessel_i() algorithm
+        ml_warning(MlWarningKind::Range, "bessel_i");
+        return ML_NAN;
+    }
+    let ize = expo as i32;
+    let na = alpha.floor();
+    if alpha < 0. {
+        // Using Abramowitz & Stegun  9.6.2 & 9.6.6
+        // this may not be quite optimal (CPU and accuracy wise)
+        return bessel_i_ex(x, -alpha, expo, bi)
+            + if alpha == na {
+                0. // sin(pi * alpha) = 0
+            } else {
+                bessel_k_ex(x, -alpha, expo, bi)
+                    * (if ize == 1 { 2. } else { 2. * (-2. * x).exp() })
+                    / M_PI
+                    * sinpi(-alpha)
+            };
+    }
+    let nb = 1 + na as i32; // nb-1 <= alpha < nb
+    let alpha = alpha - (nb - 1) as f64;
+    let ncalc = i_bessel(x, alpha, nb, ize, bi);
+    if ncalc != nb {
+        // error input
+        if ncalc < 0 {
+            // bessel_i(x): ncalc != nb; alpha. Arg. out of range?
+            ml_warning(MlWarningKind::Range, "bessel_i");
+        } else {
+            // bessel_i(x,nu): precision lost in result
+            ml_warning(MlWarningKind::Precision, "bessel_i");
+        }
+    }
+    bi[nb as usize - 1]
+}
+
+/// This routine calculates Bessel functions I_(N+ALPHA) (X)
+/// for non-negative argument X, and non-negative order N+ALPHA,
+/// with or without exponential scaling.
+///
+/// X     - Non-negative argument for which
+///         I's or exponentially scaled I's (I*EXP(-X))
+///         are to be calculated.  If I's are to be calculated,
+///         X must be less than exparg_BESS (IZE=1) or xlrg_BESS_IJ (IZE=2),
+///         (see bessel.rs).
+/// ALPHA - Fractional part of order for which
+///         I's or exponentially scaled I's (I*EXP(-X)) are
+///         to be calculated.  0 <= ALPHA < 1.0.
+/// NB    - Number of functions to be calculated, NB > 0.
+///         The first function calculated is of order ALPHA, and the
+///         last is of order (NB - 1 + ALPHA).
+/// IZE   - Type.  IZE = 1 if unscaled I's are to be calculated,
+///                    = 2 if exponentially scaled I's are to be calculated.
+/// BI    - Output vector of length NB.  If the routine
+///         terminates normally (NCALC=NB), the vector BI contains the
+///         functions I(ALPHA,X) through I(NB-1+ALPHA,X), or the
+///         corresponding exponentially scaled functions.
+///
+/// Returns NCALC, indicating possible errors.
+/// Before using the vector BI, the user should check that
+/// NCALC=NB, i.e., all orders have been calculated to
+/// the desired accuracy.
+///
+/// In case of an error, NCALC != NB, and not all I's are
+/// calculated to the desired accuracy.
+///
+/// NCALC < 0:  An argument is out of range. For example,
+///    NB <= 0, IZE is not 1 or 2, or IZE=1 and ABS(X) >= EXPARG_BESS.
+///    In this case, the BI-vector is not calculated, and NCALC is
+///    set to MIN0(NB,0)-1 so that NCALC != NB.
+///
+/// NB > NCALC > 0: Not all requested function values could
+///    be calculated accurately.  This usually occurs because NB is
+///    much larger than ABS(X).  In this case, BI[N] is calculated
+///    to the desired accuracy for N <= NCALC, but precision
+///    is lost for NCALC < N <= NB.  If BI[N] does not vanish
+///    for N > NCALC (because it is too small to be represented),
+///    and BI[N]/BI[NCALC] = 10**(-K), then only the first NSIG-K
+///    significant figures of BI[N] can be trusted.
+///
+/// This program is based on a program written by David J.
+/// Sookne (2) that computes values of the Bessel functions J or
+/// I of float argument and long order.  Modifications include
+/// the restriction of the computation to the I Bessel function
+/// of non-negative float argument, the extension of the computation
+/// to arbitrary positive order, the inclusion of optional
+/// exponential scaling, and the elimination of most underflow.
+/// An earlier version was published in (3).
+///
+/// References:
+/// - "A Note on Backward Recurrence Algorithms," Olver,
+///   F. W. J., and Sookne, D. J., Math. Comp. 26, 1972,
+///   pp 941-947.
+/// - "Bessel Functions of Real Argument and Integer Order,"
+///   Sookne, D. J., NBS Jour. of Res. B. 77B, 1973, pp
+///   125-132.
+/// - "ALGORITHM 597, Sequence of Modified Bessel Functions
+///   of the First Kind," Cody, W. J., Trans. Math. Soft.,
+///   1983, pp. 242-245.
+///
+/// Latest modification: May 30, 1989
+///
+/// Modified by: W. J. Cody and L. Stoltz
+///              Applied Mathematics Division
+///              Argonne National Laboratory
+///              Argonne, IL  60439
+fn i_bessel(x: f64, alpha: f64, nb: i32, ize: i32, bi: &mut [f64]) -> i32 {
+    // Mathematical constants
+    const CONST: f64 = 1.585;
+
+    let nu = alpha;
+    let twonu = nu + nu;
+
+    // Check for X, NB, OR IZE out of range.
+    if !(nb > 0 && x >= 0. && (0. <= nu && nu < 1.) && (1 <= ize && ize <= 2)) {
+        // argument out of range
+        return nb.min(0) - 1;
+    }
+
+    let mut ncalc = nb;
+    if ize == 1 && x > EXPARG_BESS {
+        for k in 1..=nb {
+            bi[(k - 1) as usize] = ML_POSINF; // the limit *is* = Inf
+        }
+        return ncalc;
+    }
+    if ize == 2 && x > XLRG_BESS_IJ {
+        for k in 1..=nb {
+            bi[(k - 1) as usize] = 0.; // The limit exp(-x) * I_nu(x) --> 0 :
+        }
+        return ncalc;
+    }
+    let intx = x as i32; // fine, since x <= xlrg_BESS_IJ <<< LONG_MAX
+
+    if x >= RTNSIG_BESS {
+        // "non-small" x ( >= 1e-4 )
+
+        // Initialize the forward sweep, the P-sequence of Olver
+        let nbmx = nb - intx;
+        let mut n = intx + 1;
+        let mut en = (n + n) as f64 + twonu;
+        let mut plast = 1.;
+        let mut p = en / x;
+        let mut pold;
+        // Calculate general significance test
+        let mut test = ENSIG_BESS + ENSIG_BESS;
+        if intx << 1 > NSIG_BESS * 5 {
+            test = (test * p).sqrt();
+        } else {
+            test /= CONST.powi(intx);
+        }
+        // Whether the overflow branch below has already found NCALC (L120).
+        let mut found = false;
+        if nbmx >= 3 {
+            // Calculate P-sequence until N = NB-1
+            // Check for possible overflow.
+            let mut tover = ENTEN_BESS / ENSIG_BESS;
+            let nstart = intx + 2;
+            let nend = nb - 1;
+            for k in nstart..=nend {
+                n = k;
+                en += 2.;
+                pold = plast;
+                plast = p;
+                p = en * plast / x + pold;
+                if p > tover {
+                    // To avoid overflow, divide P-sequence by TOVER.
+                    // Calculate P-sequence until ABS(P) > 1.
+                    tover = ENTEN_BESS;
+                    p /= tover;
+                    plast /= tover;
+                    let mut psave = p;
+                    let mut psavel = plast;
+                    let nstart = n + 1;
+                    loop {
+                        n += 1;
+                        en += 2.;
+                        pold = plast;
+                        plast = p;
+                        p = en * plast / x + pold;
+                        if p > 1. {
+                            break;
+                        }
+                    }
+
+                    let bb = en / x;
+                    // Calculate backward test, and find NCALC,
+                    // the highest N such that the test is passed.
+                    test = pold * plast / ENSIG_BESS;
+                    test *= 0.5 - 0.5 / (bb * bb);
+                    p = plast * tover;
+                    n -= 1;
+                    en -= 2.;
+                    let nend = nb.min(n);
+                    ncalc = nend + 1;
+                    for l in nstart..=nend {
+                        pold = psavel;
+                        psavel = psave;
+                        psave = en * psavel / x + pold;
+                        if psave * psavel > test {
+                            ncalc = l;
+                            break;
+                        }
+                    }
+                    ncalc -= 1;
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                n = nend;
+                en = (n + n) as f64 + twonu;
+                // Calculate special significance test for NBMX > 2.
+                test = fmax2(test, (plast * ENSIG_BESS).sqrt() * (p + p).sqrt());
+            }
+        }
+        if !found {
+            // Calculate P-sequence until significance test passed.
+            loop {
+                n += 1;
+                en += 2.;
+                pold = plast;
+                plast = p;
+                p = en * plast / x + pold;
+                if p >= test {
+                    break;
+                }
+            }
+        }
+
+        // L120:
+        // Initialize the backward recursion and the normalization sum.
+        n += 1;
+        en += 2.;
+        let mut bb = 0.;
+        let mut aa = 1. / p;
+        let mut em = n as f64 - 1.;
+        let mut empal = em + nu;
+        let mut emp2al = em - 1. + twonu;
+        let mut sum = aa * empal * emp2al / em;
+        let mut nend = n - nb;
+        // Emulate the C code's `goto L220` and `goto L230`.
+        let mut to_l220 = false;
+        let mut to_l230 = false;
+        if nend < 0 {
+            // N < NB, so store BI[N] and set higher orders to 0..
+            bi[(n - 1) as usize] = aa;
+            nend = -nend;
+            for l in 1..=nend {
+                bi[(n + l - 1) as usize] = 0.;
+            }
+        } else {
+            if nend > 0 {
+                // Recur backward via difference equation,
+                // calculating (but not storing) BI[N], until N = NB.
+                for _ in 1..=nend {
+                    n -= 1;
+                    en -= 2.;
+                    let mut cc = bb;
+                    bb = aa;
+                    // for x ~= 1500,  sum would overflow to 'inf' here,
+                    // and the final bi[] /= sum would give 0 wrongly;
+                    // RE-normalize (aa, sum) here -- no need to undo
+                    if nend > 100 && aa > 1e200 {
+                        // multiply by  2^-900 = 1.18e-271
+                        cc = ldexp(cc, -900);
+                        bb = ldexp(bb, -900);
+                        sum = ldexp(sum, -900);
+                    }
+                    aa = en * bb / x + cc;
+                    em -= 1.;
+                    emp2al -= 1.;
+                    if n == 1 {
+                        break;
+                    }
+                    if n == 2 {
+                        emp2al = 1.;
+                    }
+                    empal -= 1.;
+                    sum = (sum + aa * empal) * emp2al / em;
+                }
+            }
+            // Store BI[NB]
+            bi[(n - 1) as usize] = aa;
+            if nb <= 1 {
+                sum = sum + sum + aa;
+                to_l230 = true;
+            } else {
+                // Calculate and Store BI[NB-1]
+                n -= 1;
+                en -= 2.;
+                bi[(n - 1) as usize] = en * aa / x + bb;
+                if n == 1 {
+                    to_l220 = true;
+                } else {
+                    em -= 1.;
+                    if n == 2 {
+                        emp2al = 1.;
+                    } else {
+                        emp2al -= 1.;
+                    }
+                    empal -= 1.;
+                    sum = (sum + bi[(n - 1) as usize] * empal) * emp2al / em;
+                }
+            }
+        }
+        if !to_l220 && !to_l230 {
+            nend = n - 2;
+            if nend > 0 {
+                // Calculate via difference equation
+                // and store BI[N], until N = 2.
+                for _ in 1..=nend {
+                    n -= 1;
+                    en -= 2.;
+                    bi[(n - 1) as usize] = en * bi[n as usize] / x + bi[(n + 1) as usize];
+                    em -= 1.;
+                    if n == 2 {
+                        emp2al = 1.;
+                    } else {
+                        emp2al -= 1.;
+                    }
+                    empal -= 1.;
+                    sum = (sum + bi[(n - 1) as usize] * empal) * emp2al / em;
+                }
+            }
+            // Calculate BI[1]
+            bi[0] = 2. * empal * bi[1] / x + bi[2];
+        }
+        if !to_l230 {
+            // L220:
+            sum = sum + sum + bi[0];
+        }
+
+        // L230:
+        // Normalize.  Divide all BI[N] by sum.
+        if nu != 0. {
+            sum *= gamma_cody(1. + nu) * (x * 0.5).powf(-nu);
+        }
+        if ize == 1 {
+            sum *= (-x).exp();
+        }
+        aa = ENMTEN_BESS;
+        if sum > 1. {
+            aa *= sum;
+        }
+        for n in 1..=nb {
+            if bi[(n - 1) as usize] < aa {
+                bi[(n - 1) as usize] = 0.;
+            } else {
+                bi[(n - 1) as usize] /= sum;
+            }
+        }
+    } else {
+        // small x  < 1e-4
+        // Two-term ascending series for small X.
+        let mut aa = 1.;
+        let mut empal = 1. + nu;
+        // No need to check for underflow
+        let halfx = 0.5 * x;
+        if nu != 0. {
+            aa = halfx.powf(nu) / gamma_cody(empal);
+        }
+        if ize == 2 {
+            aa *= (-x).exp();
+        }
+        let bb = halfx * halfx;
+        bi[0] = aa + aa * bb / empal;
+        if x != 0. && bi[0] == 0. {
+            ncalc = 0;
+        }
+        if nb > 1 {
+            if x == 0. {
+                for n in 2..=nb {
+                    bi[(n - 1) as usize] = 0.;
+                }
+            } else {
+                // Calculate higher-order functions.
+                let cc = halfx;
+                let mut tover = (ENMTEN_BESS + ENMTEN_BESS) / x;
+                if bb != 0. {
+                    tover = ENMTEN_BESS / bb;
+                }
+                for n in 2..=nb {
+                    aa /= empal;
+                    empal += 1.;
+                    aa *= cc;
+                    if aa <= tover * empal {
+                        aa = 0.;
+                        bi[(n - 1) as usize] = 0.;
+                    } else {
+                        bi[(n - 1) as usize] = aa + aa * bb / empal;
+                    }
+                    if bi[(n - 1) as usize] == 0. && ncalc > n {
+                        ncalc = n - 1;
+                    }
+                }
+            }
+        }
+    }
+    ncalc
+}
@@ -0,0 +1,561 @@
+use crate::bessel::ENMTEN_BESS;
+use crate::bessel::ENSIG_BESS;
+use crate::bessel::ENTEN_BESS;
+use crate::bessel::RTNSIG_BESS;
+use crate::bessel::XLRG_BESS_IJ;
+use crate::bessel_y::bessel_y_ex;
+use crate::cospi;
+use crate::fmax2::fmax2;
+use crate::gamma_cody::gamma_cody;
+use crate::nmath::ML_NAN;
+use crate::sinpi;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998-2014 Ross Ihaka and the R Core team.
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+// From http://www.netlib.org/specfun/rjbesl  Fortran translated by f2c,...
+//   ------------------------------=#----  Martin Maechler, ETH Zurich
+// Additional code for nu == alpha < 0  MM
+
+/// The Bessel function of the first kind, `J_alpha(x)`.
+///
+/// Negative orders `alpha` use the reflection formula
+/// `J_{-a}(x) = cos(pi a) J_a(x) - sin(pi a) Y_a(x)`
+/// (Abramowitz & Stegun 9.1.2).
+///
+/// This allocates a work buffer of `1 + floor(|alpha|)` values;
+/// see `bessel_j_ex` to provide one instead.
+///
+/// An `MlWarningKind::Range` warning is raised for negative `x`, for
+/// `x > 1e5` where 0 is returned, and for `|alpha| > 1e7` where NaN is
+/// returned.
+/// An `MlWarningKind::Precision` warning is raised when precision was
+/// lost in the result; see `take_warnings`.
+pub fn bessel_j(x: f64, alpha: f64) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    // No buffer is used when alpha is too large for the algorithm.
+    let nb = if alpha.abs() > 1e7 {
+        0
+    } else {
+        1 + alpha.abs().floor() as usize
+    };
+    let mut bj = vec![0.0; nb];
+    bessel_j_ex(x, alpha, &mut bj)
+}
+
+/// Modified version of `bessel_j` that accepts a work buffer instead of
+/// allocating one.
+///
+/// # Panics
+///
+/// If `bj` is shorter than `1 + floor(|alpha|)`.
+pub fn bessel_j_ex(x: f64, alpha: f64, bj: &mut [f64]) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    if x < 0. {
+        ml_warning(MlWarningKind::Range, "bessel_j");
+        return ML_NAN;
+    }
+    let na = alpha.floor();
+    if alpha < 0. {
+        // Using Abramowitz & Stegun  9.1.2
+        // this may not be quite optimal (CPU and accuracy wise)
+        return (if alpha - na == 0.5 {
+            0.
+        } else {
+            bessel_j_ex(x, -alpha, bj) * cospi(alpha)
+        }) + (if alpha == na {
+            0.
+        } else {
+            bessel_y_ex(x, -alpha, bj) * sinpi(alpha)
+        });
+    } else if alpha > 1e7 {
+        // besselJ(x, nu): nu too large for bessel_j() algorithm
+        ml_warning(MlWarningKind::Range, "bessel_j");
+        return ML_NAN;
+    }
+    let nb = 1 + na as i32; // nb-1 <= alpha < nb
+    let alpha = alpha - (nb - 1) as f64; // ==> alpha' in [0, 1)
+    let ncalc = j_bessel(x, alpha, nb, bj);
+    if ncalc != nb {
+        // error input
+        if ncalc < 0 {
+            // bessel_j(x): ncalc != nb; alpha. Arg. out of range?
+            ml_warning(MlWarningKind::Range, "bessel_j");
+        } else {
+            // bessel_j(x,nu): precision lost in result
+            ml_warning(MlWarningKind::Precision, "bessel_j");
+        }
+    }
+    bj[nb as usize - 1]
+}
+
+/// Calculates Bessel functions J_{n+alpha} (x)
+/// for non-negative argument x, and non-negative order n+alpha, n = 0,1,..,nb-1.
+///
+/// X     - Non-negative argument for which J's are to be calculated.
+/// ALPHA - Fractional part of order for which
+///         J's are to be calculated.  0 <= ALPHA < 1.
+/// NB    - Number of functions to be calculated, NB >= 1.
+///         The first function calculated is of order ALPHA, and the
+///         last is of order (NB - 1 + ALPHA).
+/// B     - Output vector of length NB.  If RJBESL
+///         terminates normally (NCALC=NB), the vector B contains the
+///         functions J/ALPHA/(X) through J/NB-1+ALPHA/(X).
+///
+/// Returns NCALC, indicating possible errors.
+/// Before using the vector B, the user should check that
+/// NCALC=NB, i.e., all orders have been calculated to
+/// the desired accuracy.
+///
+/// In case of an error,  NCALC != NB, and not all J's are
+/// calculated to the desired accuracy.
+///
+/// NCALC < 0:  An argument is out of range. For example,
+///    NBES <= 0, ALPHA < 0 or > 1, or X is too large.
+///    In this case, b[1] is set to zero, the remainder of the
+///    B-vector is not calculated, and NCALC is set to
+///    MIN(NB,0)-1 so that NCALC != NB.
+///
+/// NB > NCALC > 0: Not all requested function values could
+///    be calculated accurately.  This usually occurs because NB is
+///    much larger than ABS(X).  In this case, b[N] is calculated
+///    to the desired accuracy for N <= NCALC, but precision
+///    is lost for NCALC < N <= NB.  If b[N] does not vanish
+///    for N > NCALC (because it is too small to be represented),
+///    and b[N]/b[NCALC] = 10^(-K), then only the first NSIG - K
+///    significant figures of b[N] can be trusted.
+///
+/// This program is based on a program written by David J. Sookne
+/// (2) that computes values of the Bessel functions J or I of float
+/// argument and long order.  Modifications include the restriction
+/// of the computation to the J Bessel function of non-negative float
+/// argument, the extension of the computation to arbitrary positive
+/// order, and the elimination of most underflow.
+///
+/// References:
+/// - Olver, F.W.J., and Sookne, D.J. (1972)
+///   "A Note on Backward Recurrence Algorithms";
+///   Math. Comp. 26, 941-947.
+/// - Sookne, D.J. (1973)
+///   "Bessel Functions of Real Argument and Integer Order";
+///   NBS Jour. of Res. B. 77B, 125-132.
+///
+/// Latest modification: March 19, 1990
+///
+/// Author: W. J. Cody
+///         Applied Mathematics Division
+///         Argonne National Laboratory
+///         Argonne, IL  60439
+#[allow(clippy::excessive_precision)]
+fn j_bessel(x: f64, alpha: f64, nb: i32, b: &mut [f64]) -> i32 {
+    // Mathematical constants
+    //
+    //  PI2    = 2 / PI
+    //  TWOPI1 = first few significant digits of 2 * PI
+    //  TWOPI2 = (2*PI - TWOPI1) to working precision, i.e.,
+    //           TWOPI1 + TWOPI2 = 2 * PI to extra precision.
+    #[allow(clippy::approx_constant)]
+    const PI2: f64 = 0.636619772367581343075535;
+    const TWOPI1: f64 = 6.28125;
+    const TWOPI2: f64 = 0.001935307179586476925286767;
+
+    // Factorial(N)
+    const FACT: [f64; 25] = [
+        1.,
+        1.,
+        2.,
+        6.,
+        24.,
+        120.,
+        720.,
+        5040.,
+        40320.,
+        362880.,
+        3628800.,
+        39916800.,
+        479001600.,
+        6227020800.,
+        87178291200.,
+        1.307674368e12,
+        2.0922789888e13,
+        3.55687428096e14,
+        6.402373705728e15,
+        1.21645100408832e17,
+        2.43290200817664e18,
+        5.109094217170944e19,
+        1.12400072777760768e21,
+        2.585201673888497664e22,
+        6.2044840173323943936e23,
+    ];
+
+    let nu = alpha;
+    let twonu = nu + nu;
+
+    // Check for out of range arguments.
+    if !(nb > 0 && x >= 0. && 0. <= nu && nu < 1.) {
+        // Error return -- X, NB, or ALPHA is out of range :
+        b[0] = 0.;
+        return nb.min(0) - 1;
+    }
+
+    let mut ncalc = nb;
+    if x > XLRG_BESS_IJ {
+        ml_warning(MlWarningKind::Range, "J_bessel");
+        // indeed, the limit is 0,
+        // but the cutoff happens too early
+        for bi in b.iter_mut().take(nb as usize) {
+            *bi = 0.; // was ML_POSINF (really nonsense)
+        }
+        return ncalc;
+    }
+    let intx = x as i32;
+    // Initialize result array to zero.
+    for bi in b.iter_mut().take(nb as usize) {
+        *bi = 0.;
+    }
+
+    // Branch into  3 cases :
+    // 1) use 2-term ascending series for small X
+    // 2) use asymptotic form for large X when NB is not too large
+    // 3) use recursion otherwise
+
+    if x < RTNSIG_BESS {
+        // Two-term ascending series for small X.
+        let mut alpem = 1. + nu;
+
+        let halfx = if x > ENMTEN_BESS { 0.5 * x } else { 0. };
+        let mut aa = if nu != 0. {
+            halfx.powf(nu) / (nu * gamma_cody(nu))
+        } else {
+            1.
+        };
+        let bb = if x + 1. > 1. { -halfx * halfx } else { 0. };
+        b[0] = aa + aa * bb / alpem;
+        if x != 0. && b[0] == 0. {
+            ncalc = 0;
+        }
+
+        if nb != 1 {
+            if x <= 0. {
+                for n in 2..=nb {
+                    b[(n - 1) as usize] = 0.;
+                }
+            } else {
+                // Calculate higher order functions.
+                let tover = if bb == 0. {
+                    (ENMTEN_BESS + ENMTEN_BESS) / x
+                } else {
+                    ENMTEN_BESS / bb
+                };
+                let cc = halfx;
+                for n in 2..=nb {
+                    aa /= alpem;
+                    alpem += 1.;
+                    aa *= cc;
+                    if aa <= tover * alpem {
+                        aa = 0.;
+                    }
+
+                    b[(n - 1) as usize] = aa + aa * bb / alpem;
+                    if b[(n - 1) as usize] == 0. && ncalc > n {
+                        ncalc = n - 1;
+                    }
+                }
+            }
+        }
+    } else if x > 25. && nb <= intx + 1 {
+        // Asymptotic series for X > 25 (and not too large nb)
+        let xc = (PI2 / x).sqrt();
+        let xin = 1. / (64. * x * x);
+        let m: usize = if x >= 130. {
+            4
+        } else if x >= 35. {
+            8
+        } else {
+            11
+        };
+        let xm = 4. * m as f64;
+        // Argument reduction for SIN and COS routines.
+        let t = (x / (TWOPI1 + TWOPI2) + 0.5).trunc();
+        let z = (x - t * TWOPI1) - t * TWOPI2 - (nu + 0.5) / PI2;
+        let mut vsin = z.sin();
+        let mut vcos = z.cos();
+        let mut gnu = twonu;
+        for bi in b.iter_mut().take(2) {
+            let mut s = (xm - 1. - gnu) * (xm - 1. + gnu) * xin * 0.5;
+            let mut t = (gnu - (xm - 3.)) * (gnu + (xm - 3.));
+            let mut t1 = (gnu - (xm + 1.)) * (gnu + (xm + 1.));
+            let mut k = m + m;
+            let mut capp = s * t / FACT[k];
+            let mut capq = s * t1 / FACT[k + 1];
+            let mut xk = xm;
+            while k >= 4 {
+                // k + 2(j-2) == 2m
+                xk -= 4.;
+                s = (xk - 1. - gnu) * (xk - 1. + gnu);
+                t1 = t;
+                t = (gnu - (xk - 3.)) * (gnu + (xk - 3.));
+                capp = (capp + 1. / FACT[k - 2]) * s * t * xin;
+                capq = (capq + 1. / FACT[k - 1]) * s * t1 * xin;
+                k -= 2;
+            }
+            capp += 1.;
+            capq = (capq + 1.) * (gnu * gnu - 1.) * (0.125 / x);
+            *bi = xc * (capp * vcos - capq * vsin);
+            if nb == 1 {
+                return ncalc;
+            }
+
+            // vsin <--> vcos
+            let t = vsin;
+            vsin = -vcos;
+            vcos = t;
+            gnu += 2.;
+        }
+        // If  NB > 2, compute J(X,ORDER+I)  for I = 2, NB-1
+        if nb > 2 {
+            gnu = twonu + 2.;
+            for j in 2..nb as usize {
+                b[j] = gnu * b[j - 1] / x - b[j - 2];
+                gnu += 2.;
+            }
+        }
+    } else {
+        // rtnsig_BESS <= x && ( x <= 25 || intx+1 < *nb ) :
+        // Use recurrence to generate results.
+        // First initialize the calculation of P*S.
+        let nbmx = nb - intx;
+        let mut n = intx + 1;
+        let mut en = (n + n) as f64 + twonu;
+        let mut plast = 1.;
+        let mut p = en / x;
+        let mut pold;
+        // Calculate general significance test.
+        let mut test = ENSIG_BESS + ENSIG_BESS;
+        // Whether the overflow branch below has already found NCALC (L190).
+        let mut found = false;
+        if nbmx >= 3 {
+            // Calculate P*S until N = NB-1.  Check for possible overflow.
+            let mut tover = ENTEN_BESS / ENSIG_BESS;
+            let nstart = intx + 2;
+            let nend = nb - 1;
+            en = (nstart + nstart) as f64 - 2. + twonu;
+            for k in nstart..=nend {
+                n = k;
+                en += 2.;
+                pold = plast;
+                plast = p;
+                p = en * plast / x - pold;
+                if p > tover {
+                    // To avoid overflow, divide P*S by TOVER.
+                    // Calculate P*S until ABS(P) > 1.
+                    tover = ENTEN_BESS;
+                    p /= tover;
+                    plast /= tover;
+                    let mut psave = p;
+                    let mut psavel = plast;
+                    let nstart = n + 1;
+                    loop {
+                        n += 1;
+                        en += 2.;
+                        pold = plast;
+                        plast = p;
+                        p = en * plast / x - pold;
+                        if p > 1. {
+                            break;
+                        }
+                    }
+
+                    let bb = en / x;
+                    // Calculate backward test and find NCALC,
+                    // the highest N such that the test is passed.
+                    test = pold * plast * (0.5 - 0.5 / (bb * bb));
+                    test /= ENSIG_BESS;
+                    p = plast * tover;
+                    n -= 1;
+                    en -= 2.;
+                    let nend = nb.min(n);
+                    ncalc = nend;
+                    for l in nstart..=nend {
+                        pold = psavel;
+                        psavel = psave;
+                        psave = en * psavel / x - pold;
+                        if psave * psavel > test {
+                            ncalc = l - 1;
+                            break;
+                        }
+                    }
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                n = nend;
+                en = (n + n) as f64 + twonu;
+                // Calculate special significance test for NBMX > 2.
+                test = fmax2(test, (plast * ENSIG_BESS).sqrt() * (p + p).sqrt());
+            }
+        }
+        if !found {
+            // Calculate P*S until significance test passes.
+            loop {
+                n += 1;
+                en += 2.;
+                pold = plast;
+                plast = p;
+                p = en * plast / x - pold;
+                if p >= test {
+                    break;
+                }
+            }
+        }
+
+        // L190:
+        // Initialize the backward recursion and the normalization sum.
+        n += 1;
+        en += 2.;
+        let mut bb = 0.;
+        let mut aa = 1. / p;
+        let mut m = n / 2;
+        let mut em = m as f64;
+        // = 2 n - 4 (n/2) = 0 for even, 2 for odd n
+        m = (n << 1) - (m << 2);
+        let mut sum = if m == 0 {
+            0.
+        } else {
+            let alpem = em - 1. + nu;
+            let alp2em = em + em + nu;
+            aa * alpem * alp2em / em
+        };
+        let nend = n - nb;
+        // Recur backward via difference equation, calculating
+        // (but not storing) b[N], until N = NB.
+        for _ in 1..=nend {
+            n -= 1;
+            en -= 2.;
+            let cc = bb;
+            bb = aa;
+            aa = en * bb / x - cc;
+            m = if m != 0 { 0 } else { 2 };
+            if m != 0 {
+                em -= 1.;
+                let alp2em = em + em + nu;
+                if n == 1 {
+                    break;
+                }
+
+                let mut alpem = em - 1. + nu;
+                if alpem == 0. {
+                    alpem = 1.;
+                }
+                sum = (sum + aa * alp2em) * alpem / em;
+            }
+        }
+        // Store b[NB].
+        b[(n - 1) as usize] = aa;
+        // Emulate the C code's `goto L240` and `goto L250`.
+        let mut to_l240 = false;
+        let mut to_l250 = false;
+        if nend >= 0 {
+            if nb <= 1 {
+                let alp2em = if nu + 1. == 1. { 1. } else { nu };
+                sum += b[0] * alp2em;
+                to_l250 = true;
+            } else {
+                // nb >= 2 :
+                // Calculate and store b[NB-1].
+                n -= 1;
+                en -= 2.;
+                b[(n - 1) as usize] = en * aa / x - bb;
+                if n == 1 {
+                    to_l240 = true;
+                } else {
+                    m = if m != 0 { 0 } else { 2 };
+                    if m != 0 {
+                        em -= 1.;
+                        let alp2em = em + em + nu;
+                        let mut alpem = em - 1. + nu;
+                        if alpem == 0. {
+                            alpem = 1.;
+                        }
+                        sum = (sum + b[(n - 1) as usize] * alp2em) * alpem / em;
+                    }
+                }
+            }
+        }
+
+        if !to_l240 && !to_l250 {
+            // Calculate via difference equation and store b[N],
+            // until N = 2.
+            for n in (2..n).rev() {
+                en -= 2.;
+                b[(n - 1) as usize] = en * b[n as usize] / x - b[(n + 1) as usize];
+                m = if m != 0 { 0 } else { 2 };
+                if m != 0 {
+                    em -= 1.;
+                    let alp2em = em + em + nu;
+                    let mut alpem = em - 1. + nu;
+                    if alpem == 0. {
+                        alpem = 1.;
+                    }
+                    sum = (sum + b[(n - 1) as usize] * alp2em) * alpem / em;
+                }
+            }
+            // Calculate b[1].
+            b[0] = 2. * (nu + 1.) * b[1] / x - b[2];
+        }
+
+        if !to_l250 {
+            // L240:
+            em -= 1.;
+            let mut alp2em = em + em + nu;
+            if alp2em == 0. {
+                alp2em = 1.;
+            }
+            sum += b[0] * alp2em;
+        }
+
+        // L250:
+        // Normalize.  Divide all b[N] by sum.
+        // if (nu + 1. != 1.) poor test
+        if nu.abs() > 1e-15 {
+            sum *= gamma_cody(nu) * (0.5 * x).powf(-nu);
+        }
+
+        aa = ENMTEN_BESS;
+        if sum > 1. {
+            aa *= sum;
+        }
+        for bi in b.iter_mut().take(nb as usize) {
+            if bi.abs() < aa {
+                *bi = 0.;
+            } else {
+                *bi /= sum;
+            }
+        }
+    }
+    ncalc
+}
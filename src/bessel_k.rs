@@ -0,0 +1,528 @@
+use crate::bessel::SQXMIN_BESS_K;
+use crate::bessel::XMAX_BESS_K;
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MAX;
+use crate::libc::DBL_MIN;
+use crate::nmath::M_SQRT_2dPI;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_POSINF;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998-2014 Ross Ihaka and the R Core team.
+//  Copyright (C) 2002-3    The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+// From http://www.netlib.org/specfun/rkbesl  Fortran translated by f2c,...
+//   ------------------------------=#----  Martin Maechler, ETH Zurich
+
+/// The modified Bessel function of the third kind, `K_alpha(x)`.
+///
+/// If `expo` is 2, the exponentially scaled `exp(x) K_alpha(x)` is
+/// returned instead.
+/// Since `K_{-a}(x) = K_a(x)`, negative orders use `|alpha|`.
+///
+/// This allocates a work buffer of `1 + floor(|alpha|)` values;
+/// see `bessel_k_ex` to provide one instead.
+///
+/// An `MlWarningKind::Range` warning is raised for negative `x`, for
+/// `|alpha| > 1e7` where NaN is returned, and for other arguments out of
+/// range.
+/// An `MlWarningKind::Precision` warning is raised when precision was
+/// lost in the result; see `take_warnings`.
+pub fn bessel_k(x: f64, alpha: f64, expo: f64) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    // No buffer is used when alpha is too large for the algorithm.
+    let nb = if alpha.abs() > 1e7 {
+        0
+    } else {
+        1 + alpha.abs().floor() as usize
+    };
+    let mut bk = vec![0.0; nb];
+    bessel_k_ex(x, alpha, expo, &mut bk)
+}
+
+/// Modified version of `bessel_k` that accepts a work buffer instead of
+/// allocating one.
+///
+/// # Panics
+///
+/// If `bk` is shorter than `1 + floor(|alpha|)`.
+pub fn bessel_k_ex(x: f64, alpha: f64, expo: f64, bk: &mut [f64]) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    if x < 0. {
+        ml_warning(MlWarningKind::Range, "bessel_k");
+        return ML_NAN;
+    }
+    if alpha.abs() > 1e7 {
+        // besselK(x, nu): nu too large for bessel_k() algorithm
+        ml_warning(MlWarningKind::Range, "bessel_k");
+        return ML_NAN;
+    }
+    let ize = expo as i32;
+    let alpha = alpha.abs();
+    let nb = 1 + alpha.floor() as i32; // nb-1 <= |alpha| < nb
+    let alpha = alpha - (nb - 1) as f64;
+    let ncalc = k_bessel(x, alpha, nb, ize, bk);
+    if ncalc != nb {
+        // error input
+        if ncalc < 0 {
+            // bessel_k(x): ncalc != nb; alpha. Arg. out of range?
+            ml_warning(MlWarningKind::Range, "bessel_k");
+        } else {
+            // bessel_k(x,nu): precision lost in result
+            ml_warning(MlWarningKind::Precision, "bessel_k");
+        }
+    }
+    bk[nb as usize - 1]
+}
+
+/// This routine calculates modified Bessel functions
+/// of the third kind, K_(N+ALPHA) (X), for non-negative
+/// argument X, and non-negative order N+ALPHA, with or without
+/// exponential scaling.
+///
+/// X     - Non-negative argument for which
+///         K's or exponentially scaled K's (K*EXP(X))
+///         are to be calculated.  If K's are to be calculated,
+///         X must not be greater than XMAX_BESS_K.
+/// ALPHA - Fractional part of order for which
+///         K's or exponentially scaled K's (K*EXP(X)) are
+///         to be calculated.  0 <= ALPHA < 1.0.
+/// NB    - Number of functions to be calculated, NB > 0.
+///         The first function calculated is of order ALPHA, and the
+///         last is of order (NB - 1 + ALPHA).
+/// IZE   - Type.  IZE = 1 if unscaled K's are to be calculated,
+///                    = 2 if exponentially scaled K's are to be calculated.
+/// BK    - Output vector of length NB.  If the
+///         routine terminates normally (NCALC=NB), the vector BK
+///         contains the functions K(ALPHA,X), ... , K(NB-1+ALPHA,X),
+///         or the corresponding exponentially scaled functions.
+///         If (0 < NCALC < NB), BK(I) contains correct function
+///         values for I <= NCALC, and contains the ratios
+///         K(ALPHA+I-1,X)/K(ALPHA+I-2,X) for the rest of the array.
+///
+/// Returns NCALC, indicating possible errors.
+/// Before using the vector BK, the user should check that
+/// NCALC=NB, i.e., all orders have been calculated to
+/// the desired accuracy.
+///
+/// In case of an error, NCALC != NB, and not all K's are
+/// calculated to the desired accuracy.
+///
+/// NCALC < -1:  An argument is out of range. For example,
+///       NB <= 0, IZE is not 1 or 2, or IZE=1 and ABS(X) >= XMAX_BESS_K.
+///       In this case, the B-vector is not calculated,
+///       and NCALC is set to MIN0(NB,0)-2 so that NCALC != NB.
+/// NCALC = -1:  Either  K(ALPHA,X) >= XINF  or
+///       K(ALPHA+NB-1,X)/K(ALPHA+NB-2,X) >= XINF.  In this case,
+///       the B-vector is not calculated.  Note that again
+///       NCALC != NB.
+///
+/// 0 < NCALC < NB: Not all requested function values could
+///       be calculated accurately.  BK(I) contains correct function
+///       values for I <= NCALC, and contains the ratios
+///       K(ALPHA+I-1,X)/K(ALPHA+I-2,X) for the rest of the array.
+///
+/// This program is based on a program written by J. B. Campbell
+/// (2) that computes values of the Bessel functions K of float
+/// argument and float order.  Modifications include the addition
+/// of non-scaled functions, parameterization of machine
+/// dependencies, and the use of more accurate approximations
+/// for SINH and SIN.
+///
+/// References:
+/// - "On Temme's Algorithm for the Modified Bessel
+///   Functions of the Third Kind," Campbell, J. B.,
+///   TOMS 6(4), Dec. 1980, pp. 581-586.
+/// - "A FORTRAN IV Subroutine for the Modified Bessel
+///   Functions of the Third Kind of Real Order and Real
+///   Argument," Campbell, J. B., Report NRC/ERB-925,
+///   National Research Council, Canada.
+///
+/// Latest modification: May 30, 1989
+///
+/// Modified by: W. J. Cody and L. Stoltz
+///              Applied Mathematics Division
+///              Argonne National Laboratory
+///              Argonne, IL  60439
+#[allow(clippy::excessive_precision)]
+fn k_bessel(x: f64, alpha: f64, nb: i32, ize: i32, bk: &mut [f64]) -> i32 {
+    // Mathematical constants
+    //  A = LOG(2) - Euler's constant
+    //  D = SQRT(2/PI)
+    const A: f64 = 0.11593151565841244881;
+
+    // P, Q - Approximation for LOG(GAMMA(1+ALPHA))/ALPHA + Euler's constant
+    // Coefficients converted from hex to decimal and modified
+    // by W. J. Cody, 2/26/82
+    const P: [f64; 8] = [
+        0.805629875690432845,
+        20.4045500205365151,
+        157.705605106676174,
+        536.671116469207504,
+        900.382759291288778,
+        730.923886650660393,
+        229.299301509425145,
+        0.822467033424113231,
+    ];
+    const Q: [f64; 7] = [
+        29.4601986247850434,
+        277.577868510221208,
+        1206.70325591027438,
+        2762.91444159791519,
+        3443.74050506564618,
+        2210.63190113378647,
+        572.267338359892221,
+    ];
+    // R, S - Approximation for (1-ALPHA*PI/SIN(ALPHA*PI))/(2.D0*ALPHA)
+    const R: [f64; 5] = [
+        -0.48672575865218401848,
+        13.079485869097804016,
+        -101.96490580880537526,
+        347.65409106507813131,
+        3.495898124521934782e-4,
+    ];
+    const S: [f64; 4] = [
+        -25.579105509976461286,
+        212.57260432226544008,
+        -610.69018684944109624,
+        422.69668805777760407,
+    ];
+    // T    - Approximation for SINH(Y)/Y
+    const T: [f64; 6] = [
+        1.6125990452916363814e-10,
+        2.5051878502858255354e-8,
+        2.7557319615147964774e-6,
+        1.9841269840928373686e-4,
+        0.0083333333333334751799,
+        0.16666666666666666446,
+    ];
+    const ESTM: [f64; 6] = [52.0583, 5.7607, 2.7782, 14.4303, 185.3004, 9.3715];
+    const ESTF: [f64; 7] = [41.8341, 7.1075, 6.4306, 42.511, 1.35633, 84.5096, 20.];
+
+    let ex = x;
+    let mut nu = alpha;
+    if !(nb > 0 && (0. <= nu && nu < 1.) && (1 <= ize && ize <= 2)) {
+        return nb.min(0) - 2;
+    }
+    if ex <= 0. || (ize == 1 && ex > XMAX_BESS_K) {
+        if ex <= 0. {
+            if ex < 0. {
+                ml_warning(MlWarningKind::Range, "K_bessel");
+            }
+            for b in bk.iter_mut().take(nb as usize) {
+                *b = ML_POSINF;
+            }
+        } else {
+            // would only have underflow
+            for b in bk.iter_mut().take(nb as usize) {
+                *b = 0.;
+            }
+        }
+        return nb;
+    }
+    let mut k = 0;
+    if nu < SQXMIN_BESS_K {
+        nu = 0.;
+    } else if nu > 0.5 {
+        k = 1;
+        nu -= 1.;
+    }
+    let mut twonu = nu + nu;
+    let iend = nb + k - 1;
+    let mut c = nu * nu;
+    let mut d3 = -c;
+    let mut bk1;
+    let mut bk2;
+    let wminf;
+    let mut ratio;
+    if ex <= 1. {
+        // Calculation of P0 = GAMMA(1+ALPHA) * (2/X)**ALPHA
+        //                Q0 = GAMMA(1-ALPHA) * (X/2)**ALPHA
+        let mut d1 = 0.;
+        let mut d2 = P[0];
+        let mut t1 = 1.;
+        let mut t2 = Q[0];
+        for i in (2..=7).step_by(2) {
+            d1 = c * d1 + P[i - 1];
+            d2 = c * d2 + P[i];
+            t1 = c * t1 + Q[i - 1];
+            t2 = c * t2 + Q[i];
+        }
+        d1 *= nu;
+        t1 *= nu;
+        let mut f1 = ex.ln();
+        let mut f0 = A + nu * (P[7] - nu * (d1 + d2) / (t1 + t2)) - f1;
+        let mut q0 = (-nu * (A - nu * (P[7] + nu * (d1 - d2) / (t1 - t2)) - f1)).exp();
+        f1 = nu * f0;
+        let mut p0 = f1.exp();
+        // Calculation of F0 =
+        d1 = R[4];
+        t1 = 1.;
+        for (r, s) in R.iter().zip(S.iter()) {
+            d1 = c * d1 + r;
+            t1 = c * t1 + s;
+        }
+        // d2 := sinh(f1)/ nu = sinh(f1)/(f1/f0)
+        //     = f0 * sinh(f1)/f1
+        if f1.abs() <= 0.5 {
+            f1 *= f1;
+            d2 = 0.;
+            for t in T.iter() {
+                d2 = f1 * d2 + t;
+            }
+            d2 = f0 + f0 * f1 * d2;
+        } else {
+            d2 = f1.sinh() / nu;
+        }
+        f0 = d2 - nu * d1 / (t1 * p0);
+        if ex <= 1e-10 {
+            // X <= 1.0E-10
+            // Calculation of K(ALPHA,X) and X*K(ALPHA+1,X)/K(ALPHA,X)
+            bk[0] = f0 + ex * f0;
+            if ize == 1 {
+                bk[0] -= ex * bk[0];
+            }
+            ratio = p0 / f0;
+            c = ex * DBL_MAX;
+            if k != 0 {
+                // Calculation of K(ALPHA,X)
+                // and  X*K(ALPHA+1,X)/K(ALPHA,X),  ALPHA >= 1/2
+                if bk[0] >= c / ratio {
+                    return -1;
+                }
+                bk[0] = ratio * bk[0] / ex;
+                twonu += 2.;
+                ratio = twonu;
+            }
+            if nb == 1 {
+                return 1;
+            }
+
+            // Calculate  K(ALPHA+L,X)/K(ALPHA+L-1,X),
+            // L = 1, 2, ... , NB-1
+            for b in bk.iter_mut().take(nb as usize).skip(1) {
+                if ratio >= c {
+                    return -1;
+                }
+                *b = ratio / ex;
+                twonu += 2.;
+                ratio = twonu;
+            }
+            return multiply_ratios(bk, nb, 1);
+        } else {
+            // 10^-10 < X <= 1.0
+            c = 1.;
+            let x2by4 = ex * ex / 4.;
+            p0 *= 0.5;
+            q0 *= 0.5;
+            d1 = -1.;
+            d2 = 0.;
+            bk1 = 0.;
+            bk2 = 0.;
+            f1 = f0;
+            let f2 = p0;
+            loop {
+                d1 += 2.;
+                d2 += 1.;
+                d3 += d1;
+                c = x2by4 * c / d2;
+                f0 = (d2 * f0 + p0 + q0) / d3;
+                p0 /= d2 - nu;
+                q0 /= d2 + nu;
+                t1 = c * f0;
+                t2 = c * (p0 - d2 * f0);
+                bk1 += t1;
+                bk2 += t2;
+                if !((t1 / (f1 + bk1)).abs() > DBL_EPSILON || (t2 / (f2 + bk2)).abs() > DBL_EPSILON)
+                {
+                    break;
+                }
+            }
+            bk1 += f1;
+            bk2 = 2. * (f2 + bk2) / ex;
+            if ize == 2 {
+                d1 = ex.exp();
+                bk1 *= d1;
+                bk2 *= d1;
+            }
+            wminf = ESTF[0] * ex + ESTF[1];
+        }
+    } else if DBL_EPSILON * ex > 1. {
+        // X > 1./EPS
+        bk1 = 1. / (M_SQRT_2dPI * ex.sqrt());
+        for b in bk.iter_mut().take(nb as usize) {
+            *b = bk1;
+        }
+        return nb;
+    } else {
+        // X > 1.0
+        let twox = ex + ex;
+        let mut blpha = 0.;
+        ratio = 0.;
+        if ex <= 4. {
+            // Calculation of K(ALPHA+1,X)/K(ALPHA,X),  1.0 <= X <= 4.0
+            let mut d2 = (ESTM[0] / ex + ESTM[1]).trunc();
+            let m = d2 as i32;
+            let mut d1 = d2 + d2;
+            d2 -= 0.5;
+            d2 *= d2;
+            for _ in 2..=m {
+                d1 -= 2.;
+                d2 -= d1;
+                ratio = (d3 + d2) / (twox + d1 - ratio);
+            }
+            // Calculation of I(|ALPHA|,X) and I(|ALPHA|+1,X) by backward
+            // recurrence and K(ALPHA,X) from the wronskian
+            d2 = (ESTM[2] * ex + ESTM[3]).trunc();
+            let m = d2 as i32;
+            c = nu.abs();
+            d3 = c + c;
+            d1 = d3 - 1.;
+            let mut f1 = DBL_MIN;
+            let mut f0 = (2. * (c + d2) / ex + 0.5 * ex / (c + d2 + 1.)) * DBL_MIN;
+            let mut f2;
+            for _ in 3..=m {
+                d2 -= 1.;
+                f2 = (d3 + d2 + d2) * f0;
+                blpha = (1. + d1 / d2) * (f2 + blpha);
+                f2 = f2 / ex + f1;
+                f1 = f0;
+                f0 = f2;
+            }
+            f1 += (d3 + 2.) * f0 / ex;
+            d1 = 0.;
+            let mut t1 = 1.;
+            for (p, q) in P.iter().zip(Q.iter()) {
+                d1 = c * d1 + p;
+                t1 = c * t1 + q;
+            }
+            let p0 = (c * (A + c * (P[7] - c * d1 / t1) - ex.ln())).exp() / ex;
+            f2 = (c + 0.5 - ratio) * f1 / ex;
+            bk1 = p0 + (d3 * f0 - f2 + f0 + blpha) / (f2 + f1 + f0) * p0;
+            if ize == 1 {
+                bk1 *= (-ex).exp();
+            }
+            wminf = ESTF[2] * ex + ESTF[3];
+        } else {
+            // Calculation of K(ALPHA,X) and K(ALPHA+1,X)/K(ALPHA,X), by
+            // backward recurrence, for  X > 4.0
+            let mut dm = (ESTM[4] / ex + ESTM[5]).trunc();
+            let m = dm as i32;
+            let mut d2 = dm - 0.5;
+            d2 *= d2;
+            let mut d1 = dm + dm;
+            for _ in 2..=m {
+                dm -= 1.;
+                d1 -= 2.;
+                d2 -= d1;
+                ratio = (d3 + d2) / (twox + d1 - ratio);
+                blpha = (ratio + ratio * blpha) / dm;
+            }
+            bk1 = 1. / ((M_SQRT_2dPI + M_SQRT_2dPI * blpha) * ex.sqrt());
+            if ize == 1 {
+                bk1 *= (-ex).exp();
+            }
+            wminf = ESTF[4] * (ex - (ex - ESTF[6]).abs()) + ESTF[5];
+        }
+        // Calculation of K(ALPHA+1,X)
+        // from K(ALPHA,X) and  K(ALPHA+1,X)/K(ALPHA,X)
+        bk2 = bk1 + bk1 * (nu + 0.5 - ratio) / ex;
+    }
+    // Calculation of 'NCALC', K(ALPHA+I,X),  I  =  0, 1, ... , NCALC-1,
+    // &   K(ALPHA+I,X)/K(ALPHA+I-1,X),  I = NCALC, NCALC+1, ... , NB-1
+    bk[0] = bk1;
+    if iend == 0 {
+        return nb;
+    }
+
+    let mut j = 1 - k;
+    if j >= 0 {
+        bk[j as usize] = bk2;
+    }
+
+    if iend == 1 {
+        return nb;
+    }
+
+    let mut ii = 0;
+    let m = ((wminf - nu) as i32).min(iend);
+    for i in 2..=m {
+        let t1 = bk1;
+        bk1 = bk2;
+        twonu += 2.;
+        if ex < 1. {
+            if bk1 >= DBL_MAX / twonu * ex {
+                break;
+            }
+        } else if bk1 / ex >= DBL_MAX / twonu {
+            break;
+        }
+        bk2 = twonu / ex * bk1 + t1;
+        ii = i;
+        j += 1;
+        if j >= 0 {
+            bk[j as usize] = bk2;
+        }
+    }
+
+    let m = ii;
+    if m == iend {
+        return nb;
+    }
+    ratio = bk2 / bk1;
+    let mplus1 = m + 1;
+    for _ in mplus1..=iend {
+        twonu += 2.;
+        ratio = twonu / ex + 1. / ratio;
+        j += 1;
+        if j >= 1 {
+            bk[j as usize] = ratio;
+        } else {
+            if bk2 >= DBL_MAX / ratio {
+                return -1;
+            }
+            bk2 *= ratio;
+        }
+    }
+    let ncalc = 1.max(mplus1 - k);
+    if ncalc == 1 {
+        bk[0] = bk2;
+    }
+    if nb == 1 {
+        return ncalc;
+    }
+    multiply_ratios(bk, nb, ncalc)
+}
+
+/// Turn the ratios `K(ALPHA+I,X)/K(ALPHA+I-1,X)` in `bk[ncalc..nb]` into
+/// function values and return the new NCALC (label L420 in the C code).
+fn multiply_ratios(bk: &mut [f64], nb: i32, mut ncalc: i32) -> i32 {
+    for i in ncalc as usize..nb as usize {
+        bk[i] *= bk[i - 1];
+        ncalc += 1;
+    }
+    ncalc
+}
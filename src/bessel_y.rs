@@ -0,0 +1,469 @@
+use crate::bessel::M_EPS_SINC;
+use crate::bessel::THRESH_BESS_Y;
+use crate::bessel::XLRG_BESS_Y;
+use crate::bessel_j::bessel_j_ex;
+use crate::cospi;
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MAX;
+use crate::libc::DBL_MIN;
+use crate::nmath::M_SQRT_2dPI;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+use crate::rmath::M_1_PI;
+use crate::rmath::M_PI;
+use crate::rmath::M_PI_2;
+use crate::sinpi;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998-2014 Ross Ihaka and the R Core team.
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+// From http://www.netlib.org/specfun/rybesl  Fortran translated by f2c,...
+//   ------------------------------=#----  Martin Maechler, ETH Zurich
+
+/// The Bessel function of the second kind, `Y_alpha(x)`.
+///
+/// Negative orders `alpha` use the reflection formula
+/// `Y_{-a}(x) = cos(pi a) Y_a(x) + sin(pi a) J_a(x)`
+/// (Abramowitz & Stegun 9.1.2).
+///
+/// This allocates a work buffer of `1 + floor(|alpha|)` values;
+/// see `bessel_y_ex` to provide one instead.
+///
+/// An `MlWarningKind::Range` warning is raised for negative `x` and for
+/// `|alpha| > 1e7`, where NaN is returned.
+/// An `MlWarningKind::Precision` warning is raised when precision was
+/// lost in the result; see `take_warnings`.
+pub fn bessel_y(x: f64, alpha: f64) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    // No buffer is used when alpha is too large for the algorithm.
+    let nb = if alpha.abs() > 1e7 {
+        0
+    } else {
+        1 + alpha.abs().floor() as usize
+    };
+    let mut by = vec![0.0; nb];
+    bessel_y_ex(x, alpha, &mut by)
+}
+
+/// Modified version of `bessel_y` that accepts a work buffer instead of
+/// allocating one.
+///
+/// # Panics
+///
+/// If `by` is shorter than `1 + floor(|alpha|)`.
+pub fn bessel_y_ex(x: f64, alpha: f64, by: &mut [f64]) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || alpha.is_nan() {
+        return x + alpha;
+    }
+    if x < 0. {
+        ml_warning(MlWarningKind::Range, "bessel_y");
+        return ML_NAN;
+    }
+    let na = alpha.floor();
+    if alpha < 0. {
+        // Using Abramowitz & Stegun  9.1.2
+        // this may not be quite optimal (CPU and accuracy wise)
+        return (if alpha - na == 0.5 {
+            0.
+        } else {
+            bessel_y_ex(x, -alpha, by) * cospi(alpha)
+        }) - (if alpha == na {
+            0.
+        } else {
+            bessel_j_ex(x, -alpha, by) * sinpi(alpha)
+        });
+    } else if alpha > 1e7 {
+        // besselY(x, nu): nu too large for bessel_y() algorithm
+        ml_warning(MlWarningKind::Range, "bessel_y");
+        return ML_NAN;
+    }
+    let nb = 1 + na as i32; // nb-1 <= alpha < nb
+    let alpha = alpha - (nb - 1) as f64;
+    let ncalc = y_bessel(x, alpha, nb, by);
+    if ncalc != nb {
+        // error input
+        if ncalc == -1 {
+            return ML_POSINF;
+        } else if ncalc < -1 {
+            // bessel_y(x): ncalc != nb; alpha. Arg. out of range?
+            ml_warning(MlWarningKind::Range, "bessel_y");
+        } else {
+            // ncalc >= 0
+            // bessel_y(x,nu): precision lost in result
+            ml_warning(MlWarningKind::Precision, "bessel_y");
+        }
+    }
+    by[nb as usize - 1]
+}
+
+/// This routine calculates Bessel functions Y_(N+ALPHA) (X)
+/// for non-negative argument X, and non-negative order N+ALPHA.
+///
+/// X     - Non-negative argument for which
+///         Y's are to be calculated.
+/// ALPHA - Fractional part of order for which
+///         Y's are to be calculated.  0 <= ALPHA < 1.0.
+/// NB    - Number of functions to be calculated, NB > 0.
+///         The first function calculated is of order ALPHA, and the
+///         last is of order (NB - 1 + ALPHA).
+/// BY    - Output vector of length NB.  If the
+///         routine terminates normally (NCALC=NB), the vector BY
+///         contains the functions Y(ALPHA,X), ... , Y(NB-1+ALPHA,X),
+///         If (0 < NCALC < NB), BY(I) contains correct function
+///         values for I <= NCALC, and contains the ratios
+///         Y(ALPHA+I-1,X)/Y(ALPHA+I-2,X) for the rest of the array.
+///
+/// Returns NCALC, indicating possible errors.
+/// Before using the vector BY, the user should check that
+/// NCALC=NB, i.e., all orders have been calculated to
+/// the desired accuracy.
+///
+/// In case of an error, NCALC != NB, and not all Y's are
+/// calculated to the desired accuracy.
+///
+/// NCALC < -1:  An argument is out of range. For example,
+///       NB <= 0, IZE is not 1 or 2, or IZE=1 and ABS(X) >=
+///       XMAX.  In this case, BY[0] = 0.0, the remainder of the
+///       BY-vector is not calculated, and NCALC is set to
+///       MIN0(NB,0)-2  so that NCALC != NB.
+/// NCALC = -1:  Y(ALPHA,X) >= XINF.  The requested function
+///       values are set to 0.0.
+/// 1 < NCALC < NB: Not all requested function values could
+///       be calculated accurately.  BY(I) contains correct function
+///       values for I <= NCALC, and and the remaining NB-NCALC
+///       array elements contain 0.0.
+///
+/// This program draws heavily on Temme's Algol program for Y(a,x)
+/// and Y(a+1,x) and on Campbell's programs for Y_nu(x).  Temme's
+/// scheme is used for  x < THRESH, and Campbell's scheme is used
+/// in the asymptotic region.  Segments of code from both sources
+/// have been translated into Fortran 77, merged, and heavily modified.
+/// Modifications include parameterization of machine dependencies,
+/// use of a new approximation for ln(gamma(x)), and built-in
+/// protection against over/underflow.
+///
+/// References:
+/// - "Bessel functions J_nu(x) and Y_nu(x) of float
+///   order and float argument," Campbell, J. B.,
+///   Comp. Phy. Comm. 18, 1979, pp. 133-142.
+/// - "On the numerical evaluation of the ordinary
+///   Bessel function of the second kind," Temme,
+///   N. M., J. Comput. Phys. 21, 1976, pp. 343-350.
+///
+/// Latest modification: March 19, 1990
+///
+/// Modified by: W. J. Cody
+///              Applied Mathematics Division
+///              Argonne National Laboratory
+///              Argonne, IL  60439
+#[allow(clippy::excessive_precision)]
+fn y_bessel(x: f64, alpha: f64, nb: i32, by: &mut [f64]) -> i32 {
+    // Mathematical constants
+    //   FIVPI = 5*PI
+    //   PIM5 = 5*PI - 15
+    const FIVPI: f64 = 15.707963267948966192;
+    const PIM5: f64 = 0.70796326794896619231;
+
+    // Coefficients for Chebyshev polynomial expansion of
+    // 1/gamma(1-x), abs(x) <= .5
+    const CH: [f64; 21] = [
+        -6.7735241822398840964e-24,
+        -6.1455180116049879894e-23,
+        2.9017595056104745456e-21,
+        1.3639417919073099464e-19,
+        2.3826220476859635824e-18,
+        -9.0642907957550702534e-18,
+        -1.4943667065169001769e-15,
+        -3.3919078305362211264e-14,
+        -1.7023776642512729175e-13,
+        9.1609750938768647911e-12,
+        2.4230957900482704055e-10,
+        1.7451364971382984243e-9,
+        -3.3126119768180852711e-8,
+        -8.6592079961391259661e-7,
+        -4.9717367041957398581e-6,
+        7.6309597585908126618e-5,
+        0.0012719271366545622927,
+        0.0017063050710955562222,
+        -0.07685284084478667369,
+        -0.28387654227602353814,
+        0.92187029365045265648,
+    ];
+
+    let ex = x;
+    let mut nu = alpha;
+    if !(nb > 0 && 0. <= nu && nu < 1.) {
+        by[0] = 0.;
+        return nb.min(0) - 1;
+    }
+    if ex < DBL_MIN || ex > XLRG_BESS_Y {
+        // Warning is not really appropriate, give
+        // proper limit:
+        // ML_WARNING(ME_RANGE, "Y_bessel");
+        let limit = if ex > XLRG_BESS_Y {
+            0. // was ML_POSINF
+        } else {
+            ML_NEGINF
+        };
+        for b in by.iter_mut().take(nb as usize) {
+            *b = limit;
+        }
+        return nb;
+    }
+    let xna = (nu + 0.5).trunc();
+    let mut na = xna as i32;
+    if na == 1 {
+        // <==>  .5 <= *alpha < 1  <==>  -5. <= nu < 0
+        nu -= xna;
+    }
+    let mut ya;
+    let mut ya1;
+    if nu == -0.5 {
+        let p = M_SQRT_2dPI / ex.sqrt();
+        ya = p * ex.sin();
+        ya1 = -p * ex.cos();
+    } else if ex < 3. {
+        // Use Temme's scheme for small X
+        let b = ex * 0.5;
+        let d = -b.ln();
+        let mut f = nu * d;
+        let mut e = b.powf(-nu);
+        let mut c = if nu.abs() < M_EPS_SINC {
+            M_1_PI
+        } else {
+            nu / sinpi(nu)
+        };
+
+        // Computation of sinh(f)/f
+        let s = if f.abs() < 1. {
+            let x2 = f * f;
+            let mut en = 19.;
+            let mut s = 1.;
+            for _ in 1..=9 {
+                s = s * x2 / en / (en - 1.) + 1.;
+                en -= 2.;
+            }
+            s
+        } else {
+            (e - 1. / e) * 0.5 / f
+        };
+        // Computation of 1/gamma(1-a) using Chebyshev polynomials
+        let x2 = nu * nu * 8.;
+        let mut aye = CH[0];
+        let mut even = 0.;
+        let mut alfa = CH[1];
+        let mut odd = 0.;
+        for i in (3..=19).step_by(2) {
+            even = -(aye + aye + even);
+            aye = -even * x2 - aye + CH[i - 1];
+            odd = -(alfa + alfa + odd);
+            alfa = -odd * x2 - alfa + CH[i];
+        }
+        even = (even * 0.5 + aye) * x2 - aye + CH[20];
+        odd = (odd + alfa) * 2.;
+        let gamma = odd * nu + even;
+        // End of computation of 1/gamma(1-a)
+        let mut g = e * gamma;
+        e = (e + 1. / e) * 0.5;
+        f = 2. * c * (odd * e + even * s * d);
+        e = nu * nu;
+        let mut p = g * c;
+        let mut q = M_1_PI / g;
+        c = nu * M_PI_2;
+        let mut r = if c.abs() < M_EPS_SINC {
+            1.
+        } else {
+            sinpi(nu / 2.) / c
+        };
+
+        r = M_PI * c * r * r;
+        c = 1.;
+        let d = -b * b;
+        let mut h = 0.;
+        ya = f + r * q;
+        ya1 = p;
+        let mut en = 1.;
+
+        while (g / (1. + ya.abs())).abs() + (h / (1. + ya1.abs())).abs() > DBL_EPSILON {
+            f = (f * en + p + q) / (en * en - e);
+            c *= d / en;
+            p /= en - nu;
+            q /= en + nu;
+            g = c * (f + r * q);
+            h = c * p - en * g;
+            ya += g;
+            ya1 += h;
+            en += 1.;
+        }
+        ya = -ya;
+        ya1 = -ya1 / b;
+    } else if ex < THRESH_BESS_Y {
+        // Use Temme's scheme for moderate X :  3 <= x < 16
+        let c = (0.5 - nu) * (0.5 + nu);
+        let b = ex + ex;
+        let mut e = ex * M_1_PI * cospi(nu) / DBL_EPSILON;
+        e *= e;
+        let mut p = 1.;
+        let mut q = -ex;
+        let mut r = 1. + ex * ex;
+        let mut s = r;
+        let mut en = 2.;
+        let mut en1 = 0.;
+        while r * en * en < e {
+            en1 = en + 1.;
+            let d = (en - 1. + c / en) / s;
+            p = (en + en - p * d) / en1;
+            q = (-b + q * d) / en1;
+            s = p * p + q * q;
+            r *= s;
+            en = en1;
+        }
+        let mut f = p / s;
+        p = f;
+        let mut g = -q / s;
+        q = g;
+        loop {
+            // L220:
+            en -= 1.;
+            if en <= 0. {
+                break;
+            }
+            r = en1 * (2. - p) - 2.;
+            s = b + en1 * q;
+            let d = (en - 1. + c / en) / (r * r + s * s);
+            p = d * r;
+            q = d * s;
+            let e = f + 1.;
+            f = p * e - g * q;
+            g = q * e + p * g;
+            en1 = en;
+        }
+        f += 1.;
+        let d = f * f + g * g;
+        let pa = f / d;
+        let qa = -g / d;
+        let d = nu + 0.5 - p;
+        q += ex;
+        let pa1 = (pa * q - qa * d) / ex;
+        let qa1 = (qa * q + pa * d) / ex;
+        let b = ex - M_PI_2 * (nu + 0.5);
+        let c = b.cos();
+        let s = b.sin();
+        let d = M_SQRT_2dPI / ex.sqrt();
+        ya = d * (pa * s + qa * c);
+        ya1 = d * (qa1 * s - pa1 * c);
+    } else {
+        // x > thresh_BESS_Y
+        // Use Campbell's asymptotic scheme.
+        na = 0;
+        let d1 = (ex / FIVPI).trunc();
+        let i = d1 as i32;
+        let dmu = ex - 15. * d1 - d1 * PIM5 - (alpha + 0.5) * M_PI_2;
+        let (mut cosmu, mut sinmu) = if i - ((i / 2) << 1) == 0 {
+            (dmu.cos(), dmu.sin())
+        } else {
+            (-dmu.cos(), -dmu.sin())
+        };
+        let ddiv = 8. * ex;
+        let mut dmu = alpha;
+        let den = ex.sqrt();
+        ya = 0.;
+        ya1 = 0.;
+        for k in 1..=2 {
+            let mut p = cosmu;
+            cosmu = sinmu;
+            sinmu = -p;
+            let mut d1 = (2. * dmu - 1.) * (2. * dmu + 1.);
+            let mut d2 = 0.;
+            let mut div = ddiv;
+            p = 0.;
+            let mut q = 0.;
+            let q0 = d1 / div;
+            let mut term = q0;
+            for _ in 2..=20 {
+                d2 += 8.;
+                d1 -= d2;
+                div += ddiv;
+                term = -term * d1 / div;
+                p += term;
+                d2 += 8.;
+                d1 -= d2;
+                div += ddiv;
+                term *= d1 / div;
+                q += term;
+                if term.abs() <= DBL_EPSILON {
+                    break;
+                }
+            }
+            p += 1.;
+            q += q0;
+            if k == 1 {
+                ya = M_SQRT_2dPI * (p * cosmu - q * sinmu) / den;
+            } else {
+                ya1 = M_SQRT_2dPI * (p * cosmu - q * sinmu) / den;
+            }
+            dmu += 1.;
+        }
+    }
+    if na == 1 {
+        let mut h = 2. * (nu + 1.) / ex;
+        if h > 1. && ya1.abs() > DBL_MAX / h {
+            h = 0.;
+            ya = 0.;
+        }
+        h = h * ya1 - ya;
+        ya = ya1;
+        ya1 = h;
+    }
+
+    // Now have first one or two Y's
+    by[0] = ya;
+    let mut ncalc = 1;
+    if nb > 1 {
+        by[1] = ya1;
+        if ya1 != 0. {
+            let mut aye = 1. + alpha;
+            let twobyx = 2. / ex;
+            ncalc = 2;
+            for i in 2..nb as usize {
+                if twobyx < 1. {
+                    if by[i - 1].abs() * twobyx >= DBL_MAX / aye {
+                        break;
+                    }
+                } else if by[i - 1].abs() >= DBL_MAX / aye / twobyx {
+                    break;
+                }
+                by[i] = twobyx * aye * by[i - 1] - by[i - 2];
+                aye += 1.;
+                ncalc += 1;
+            }
+        }
+    }
+    // L450:
+    for b in by.iter_mut().take(nb as usize).skip(ncalc as usize) {
+        *b = ML_NEGINF; // was 0
+    }
+    ncalc
+}
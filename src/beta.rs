@@ -0,0 +1,68 @@
+use crate::gammafn;
+use crate::lbeta;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::ML_POSINF;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2014 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// For IEEE double precision, `a + b` below this uses `gammafn`, see
+/// `gammalims`; typically = 171.61447887 for IEEE.
+const XMAX: f64 = 171.61447887182298;
+
+/// This function returns the value of the beta function
+///
+/// B(a,b) = G(a) G(b) / G(a+b)
+///
+/// For large `a + b`, `exp(lbeta(a, b))` is returned instead, which may
+/// underflow to 0.
+///
+/// ## NOTES
+///
+/// This routine is a translation into C of a Fortran subroutine
+/// by W. Fullerton of Los Alamos Scientific Laboratory.
+/// Some modifications have been made so that the routines
+/// conform to the IEEE 754 standard.
+pub fn beta(a: f64, b: f64) -> f64 {
+    // NaNs propagated correctly
+    if a.is_nan() || b.is_nan() {
+        return a + b;
+    }
+
+    if a < 0. || b < 0. {
+        return ml_warn_return_nan();
+    } else if a == 0. || b == 0. {
+        return ML_POSINF;
+    } else if !a.is_finite() || !b.is_finite() {
+        return 0.;
+    }
+
+    if a + b < XMAX {
+        // ~= 171.61 for IEEE
+        //	return gammafn(a) * gammafn(b) / gammafn(a+b);
+        // All the terms are positive, and all can be large for large
+        // or small arguments.  They are never much less than one.
+        // gammafn(x) can still overflow for x ~ 1e-308,
+        // but the result would too.
+        (1. / gammafn(a + b)) * gammafn(a) * gammafn(b)
+    } else {
+        // underflow to 0 is not harmful per se;  exp(-999) also gives no warning
+        lbeta(a, b).exp()
+    }
+}
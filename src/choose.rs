@@ -0,0 +1,154 @@
+use crate::lbeta::lbeta;
+use crate::lgamma::lgammafn;
+use crate::lgamma::lgammafn_sign;
+use crate::nmath::r_forceint;
+use crate::nmath::r_nonint;
+use crate::nmath::ML_NEGINF;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998      Ross Ihaka
+//  Copyright (C) 2004-2014 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// log(choose(n, k)) via `lbeta`, without any checks on `n` and `k`.
+pub fn lfastchoose(n: f64, k: f64) -> f64 {
+    -(n + 1.).ln() - lbeta(n - k + 1., k + 1.)
+}
+
+/// log(choose(n, k)) via `lgammafn`; mathematically the same as
+/// `lfastchoose`.
+///
+/// Less stable typically, but useful if n-k+1 < 0.
+fn lfastchoose2(n: f64, k: f64, s_choose: &mut i32) -> f64 {
+    let r = lgammafn_sign(n - k + 1., Some(s_choose));
+    lgammafn(n + 1.) - lgammafn(k + 1.) - r
+}
+
+fn odd(k: f64) -> bool {
+    k != 2. * (k / 2.).floor()
+}
+
+fn r_is_int(x: f64) -> bool {
+    !r_nonint(x)
+}
+
+/// The logarithm of the absolute value of the binomial coefficient.
+pub fn lchoose(n: f64, k: f64) -> f64 {
+    let k0 = k;
+    let k = r_forceint(k);
+    // NaNs propagated correctly
+    if n.is_nan() || k.is_nan() {
+        return n + k;
+    }
+    if (k - k0).abs() > 1e-7 {
+        ml_warning(MlWarningKind::NonInteger, "lchoose");
+    }
+    if k < 2. {
+        if k < 0. {
+            return ML_NEGINF;
+        }
+        if k == 0. {
+            return 0.;
+        }
+        // else: k == 1
+        return n.abs().ln();
+    }
+    // else: k >= 2
+    if n < 0. {
+        return lchoose(-n + k - 1., k);
+    } else if r_is_int(n) {
+        let n = r_forceint(n);
+        if n < k {
+            return ML_NEGINF;
+        }
+        // k <= n :
+        if n - k < 2. {
+            return lchoose(n, n - k); // <- Symmetry
+        }
+        // else: n >= k+2
+        return lfastchoose(n, k);
+    }
+    // else non-integer n >= 0 :
+    if n < k - 1. {
+        let mut s = 0;
+        return lfastchoose2(n, k, &mut s);
+    }
+    lfastchoose(n, k)
+}
+
+/// 30 is somewhat arbitrary: it is on the *safe* side:
+/// both speed and precision are clearly improved for k < 30.
+const K_SMALL_MAX: f64 = 30.;
+
+/// The binomial coefficient.
+///
+/// `n` may be any real number; `k` is rounded to the nearest integer,
+/// with an `MlWarningKind::NonInteger` warning if it was not one.
+pub fn choose(n: f64, k: f64) -> f64 {
+    let k0 = k;
+    let mut k = r_forceint(k);
+    // NaNs propagated correctly
+    if n.is_nan() || k.is_nan() {
+        return n + k;
+    }
+    if (k - k0).abs() > 1e-7 {
+        ml_warning(MlWarningKind::NonInteger, "choose");
+    }
+    if k < K_SMALL_MAX {
+        if n - k < k && n >= 0. && r_is_int(n) {
+            k = r_forceint(n - k); // <- Symmetry, ensure k still integer
+        }
+        if k < 0. {
+            return 0.;
+        }
+        if k == 0. {
+            return 1.;
+        }
+        // else: k >= 1
+        let mut r = n;
+        let mut j = 2.;
+        while j <= k {
+            r *= (n - j + 1.) / j;
+            j += 1.;
+        }
+        // might have got rounding errors
+        return if r_is_int(n) { r_forceint(r) } else { r };
+    }
+    // else: k >= k_small_max
+    if n < 0. {
+        let r = choose(-n + k - 1., k);
+        return if odd(k) { -r } else { r };
+    } else if r_is_int(n) {
+        let n = r_forceint(n);
+        if n < k {
+            return 0.;
+        }
+        if n - k < K_SMALL_MAX {
+            return choose(n, n - k); // <- Symmetry
+        }
+        return r_forceint(lfastchoose(n, k).exp());
+    }
+    // else non-integer n >= 0 :
+    if n < k - 1. {
+        let mut s_choose = 0;
+        let r = lfastchoose2(n, k, &mut s_choose);
+        return s_choose as f64 * r.exp();
+    }
+    lfastchoose(n, k).exp()
+}
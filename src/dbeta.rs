@@ -0,0 +1,124 @@
+use crate::dbinom::dbinom_raw;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d_exp;
+use crate::dpq::r_d_val;
+use crate::lbeta::lbeta;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+
+//
+//  AUTHOR
+//    Catherine Loader, catherine@research.bell-labs.com.
+//    October 23, 2000.
+//
+//  Merge in to R:
+//  Copyright (C) 2000, The R Core Team
+//  Changes to case a, b < 2, use logs to avoid underflow
+//  Copyright (C) 2006-2014 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Computes the density of the beta distribution.
+///
+/// Beta density,
+///
+/// ```text
+///                    (a+b-1)!     a-1       b-1
+///   p(x;a,b) = ------------ x     (1-x)
+///                 (a-1)!(b-1)!
+///
+///            = (a+b-1) dbinom(a-1; a+b-2,x)
+/// ```
+///
+/// The basic formula for the log density is thus
+/// `(a-1) log x + (b-1) log (1-x) - lbeta(a, b)`.
+/// If either a or b <= 2 then 0 < lbeta(a, b) < 710 and so no
+/// term is large.  We use Loader's code only if both a and b > 2.
+pub fn dbeta(x: f64, a: f64, b: f64, give_log: bool) -> f64 {
+    if x.is_nan() || a.is_nan() || b.is_nan() {
+        return x + a + b;
+    }
+
+    if a < 0.0 || b < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if x < 0.0 || x > 1.0 {
+        return r_d__0(give_log);
+    }
+
+    // limit cases for (a,b), leading to point masses
+    if a == 0.0 || b == 0.0 || !r_finite(a) || !r_finite(b) {
+        if a == 0.0 && b == 0.0 {
+            // point mass 1/2 at each of {0,1} :
+            return if x == 0.0 || x == 1.0 {
+                ML_POSINF
+            } else {
+                r_d__0(give_log)
+            };
+        }
+        if a == 0.0 || a / b == 0.0 {
+            // point mass 1 at 0
+            return if x == 0.0 {
+                ML_POSINF
+            } else {
+                r_d__0(give_log)
+            };
+        }
+        if b == 0.0 || b / a == 0.0 {
+            // point mass 1 at 1
+            return if x == 1.0 {
+                ML_POSINF
+            } else {
+                r_d__0(give_log)
+            };
+        }
+        // else, remaining case:  a = b = Inf : point mass 1 at 1/2
+        return if x == 0.5 {
+            ML_POSINF
+        } else {
+            r_d__0(give_log)
+        };
+    }
+
+    if x == 0.0 {
+        if a > 1.0 {
+            return r_d__0(give_log);
+        }
+        if a < 1.0 {
+            return ML_POSINF;
+        }
+        // a == 1 :
+        return r_d_val(b, give_log);
+    }
+    if x == 1.0 {
+        if b > 1.0 {
+            return r_d__0(give_log);
+        }
+        if b < 1.0 {
+            return ML_POSINF;
+        }
+        // b == 1 :
+        return r_d_val(a, give_log);
+    }
+
+    let lval = if a <= 2.0 || b <= 2.0 {
+        (a - 1.0) * x.ln() + (b - 1.0) * (-x).ln_1p() - lbeta(a, b)
+    } else {
+        (a + b - 1.0).ln() + dbinom_raw(a - 1.0, a + b - 2.0, x, 1.0 - x, true)
+    };
+
+    r_d_exp(lval, give_log)
+}
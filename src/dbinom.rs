@@ -0,0 +1,120 @@
+use crate::bd0::bd0;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d__1;
+use crate::dpq::r_d_exp;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_d_neg_i_nonint;
+use crate::nmath::r_d_nonint_check;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+use crate::nmath::M_LN_2PI;
+use crate::stirlerr::stirlerr;
+
+//
+//  AUTHOR
+//    Catherine Loader, catherine@research.bell-labs.com.
+//    October 23, 2000.
+//
+//  Merge in to R and further tweaks :
+//  Copyright (C) 2000-2015 The R Core Team
+//  Copyright (C) 2008 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Computes the binomial probability via the saddle point expansion.
+///
+/// `dbinom_raw` has both `p` and `q` arguments, when one may be represented
+/// more accurately than the other (in particular, in `df`).
+///
+/// It does NOT check that inputs `x` and `n` are integers, nor that
+/// 0 <= p <= 1 and 0 <= q <= 1 or NaN's.
+/// This should be done in the calling function, where necessary.
+pub fn dbinom_raw(x: f64, n: f64, p: f64, q: f64, give_log: bool) -> f64 {
+    if p == 0.0 {
+        return if x == 0.0 {
+            r_d__1(give_log)
+        } else {
+            r_d__0(give_log)
+        };
+    }
+    if q == 0.0 {
+        return if x == n {
+            r_d__1(give_log)
+        } else {
+            r_d__0(give_log)
+        };
+    }
+
+    if x == 0.0 {
+        if n == 0.0 {
+            return r_d__1(give_log);
+        }
+        let lc = if p < 0.1 {
+            -bd0(n, n * q) - n * p
+        } else {
+            n * q.ln()
+        };
+        return r_d_exp(lc, give_log);
+    }
+    if x == n {
+        let lc = if q < 0.1 {
+            -bd0(n, n * p) - n * q
+        } else {
+            n * p.ln()
+        };
+        return r_d_exp(lc, give_log);
+    }
+    if x < 0.0 || x > n {
+        return r_d__0(give_log);
+    }
+
+    // n*p or n*q can underflow to zero if n and p or q are small.  This
+    // used to occur in dbeta, and gives NaN as from R 2.3.0.
+    let lc = stirlerr(n) - stirlerr(x) - stirlerr(n - x) - bd0(x, n * p) - bd0(n - x, n * q);
+
+    // f = (M_2PI*x*(n-x))/n; could overflow or underflow
+    // Upto R 2.7.1:
+    // lf = log(M_2PI) + log(x) + log(n-x) - log(n);
+    // -- following is much better for  x << n :
+    let lf = M_LN_2PI + x.ln() + (-x / n).ln_1p();
+
+    r_d_exp(lc - 0.5 * lf, give_log)
+}
+
+/// The density of the binomial distribution.
+///
+/// Returns the probability of `x` successes in `n` independent trials
+/// with success probability `p`.
+pub fn dbinom(x: f64, n: f64, p: f64, give_log: bool) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || n.is_nan() || p.is_nan() {
+        return x + n + p;
+    }
+
+    if p < 0.0 || p > 1.0 || r_d_neg_i_nonint(n) {
+        return ml_warn_return_nan();
+    }
+    if let Some(r) = r_d_nonint_check(x, give_log, "dbinom") {
+        return r;
+    }
+    if x < 0.0 || !r_finite(x) {
+        return r_d__0(give_log);
+    }
+
+    let n = r_forceint(n);
+    let x = r_forceint(x);
+
+    dbinom_raw(x, n, p, 1.0 - p, give_log)
+}
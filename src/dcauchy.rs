@@ -0,0 +1,39 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::rmath::M_PI;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density of the Cauchy distribution.
+pub fn dcauchy(x: f64, location: f64, scale: f64, give_log: bool) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    let y = (x - location) / scale;
+    if give_log {
+        -(M_PI * scale * (1. + y * y)).ln()
+    } else {
+        1. / (M_PI * scale * (1. + y * y))
+    }
+}
@@ -0,0 +1,6 @@
+use crate::dgamma::dgamma;
+
+/// The density of the chi-squared distribution.
+pub fn dchisq(x: f64, df: f64, give_log: bool) -> f64 {
+    dgamma(x, df / 2.0, 2.0, give_log)
+}
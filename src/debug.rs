@@ -1,3 +1,8 @@
+/// Whether to print the debug output of `bratio`, like `DEBUG_bratio` in C.
+///
+/// Callers check this before formatting a message for `debug_print`.
+pub const DEBUG_BRATIO: bool = false;
+
 pub fn debug_print(text: &str) {
     println!("    Rust: {}", text);
 }
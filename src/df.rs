@@ -0,0 +1,96 @@
+use crate::dbinom::dbinom_raw;
+use crate::dgamma::dgamma;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d__1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+
+//
+//  AUTHOR
+//    Catherine Loader, catherine@research.bell-labs.com.
+//    October 23, 2000.
+//
+//  Merge in to R:
+//	Copyright (C) 2000, 2005 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density function of the F distribution.
+///
+/// To evaluate it, write it as a Binomial probability with p = x*m/(n+x*m).
+/// For m >= 2, we use the simplest conversion.
+/// For m < 2, (m-2)/2 < 0 so the conversion will not work, and we must use
+/// a second conversion.
+/// Note the division by p; this seems unavoidable
+/// for m < 2, since the F density has a singularity as x (or p) -> 0.
+pub fn df(x: f64, m: f64, n: f64, give_log: bool) -> f64 {
+    if x.is_nan() || m.is_nan() || n.is_nan() {
+        return x + m + n;
+    }
+    if m <= 0.0 || n <= 0.0 {
+        return ml_warn_return_nan();
+    }
+    if x < 0.0 {
+        return r_d__0(give_log);
+    }
+    if x == 0.0 {
+        return if m > 2.0 {
+            r_d__0(give_log)
+        } else if m == 2.0 {
+            r_d__1(give_log)
+        } else {
+            ML_POSINF
+        };
+    }
+    if !r_finite(m) && !r_finite(n) {
+        // both +Inf
+        return if x == 1.0 {
+            ML_POSINF
+        } else {
+            r_d__0(give_log)
+        };
+    }
+    if !r_finite(n) {
+        // must be +Inf by now
+        return dgamma(x, m / 2.0, 2.0 / m, give_log);
+    }
+    if m > 1e14 {
+        // includes +Inf: code below is inaccurate there
+        let dens = dgamma(1.0 / x, n / 2.0, 2.0 / n, give_log);
+        return if give_log {
+            dens - 2.0 * x.ln()
+        } else {
+            dens / (x * x)
+        };
+    }
+
+    let mut f = 1.0 / (n + x * m);
+    let q = n * f;
+    let p = x * m * f;
+
+    let dens = if m >= 2.0 {
+        f = m * q / 2.0;
+        dbinom_raw((m - 2.0) / 2.0, (m + n - 2.0) / 2.0, p, q, give_log)
+    } else {
+        f = m * m * q / (2.0 * p * (m + n));
+        dbinom_raw(m / 2.0, (m + n) / 2.0, p, q, give_log)
+    };
+    if give_log {
+        f.ln() + dens
+    } else {
+        f * dens
+    }
+}
@@ -5,28 +5,28 @@ use crate::nmath::r_finite;
 use crate::nmath::ML_POSINF;
 use libm::log;
 
-///
-///  AUTHOR
-///    Catherine Loader, catherine@research.bell-labs.com.
-///    October 23, 2000.
-///
-///  Merge in to R:
-///  Copyright (C) 2000-2019 The R Core Team
-///  Copyright (C) 2004-2019 The R Foundation
-///
-///  This program is free software; you can redistribute it and/or modify
-///  it under the terms of the GNU General Public License as published by
-///  the Free Software Foundation; either version 2 of the License, or
-///  (at your option) any later version.
-///
-///  This program is distributed in the hope that it will be useful,
-///  but WITHOUT ANY WARRANTY; without even the implied warranty of
-///  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
-///  GNU General Public License for more details.
-///
-///  You should have received a copy of the GNU General Public License
-///  along with this program; if not, a copy is available at
-///  <https://www.R-project.org/Licenses/>
+//
+//  AUTHOR
+//    Catherine Loader, catherine@research.bell-labs.com.
+//    October 23, 2000.
+//
+//  Merge in to R:
+//  Copyright (C) 2000-2019 The R Core Team
+//  Copyright (C) 2004-2019 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
 
 /// Computes the density of the Gamma distribution.
 ///
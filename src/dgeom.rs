@@ -0,0 +1,59 @@
+use crate::dbinom::dbinom_raw;
+use crate::dpq::r_d__0;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_d_nonint_check;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+
+//
+//  AUTHOR
+//    Catherine Loader, catherine@research.bell-labs.com.
+//    October 23, 2000.
+//
+//  Merge in to R:
+//	Copyright (C) 2000-2014 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density of the geometric distribution.
+///
+/// Computes the probability of `x` failures before the first success with
+/// success probability `p`.
+pub fn dgeom(x: f64, p: f64, give_log: bool) -> f64 {
+    if x.is_nan() || p.is_nan() {
+        return x + p;
+    }
+
+    if p <= 0.0 || p > 1.0 {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(r) = r_d_nonint_check(x, give_log, "dgeom") {
+        return r;
+    }
+    if x < 0.0 || !r_finite(x) || p == 0.0 {
+        return r_d__0(give_log);
+    }
+    let x = r_forceint(x);
+
+    // prob = (1-p)^x, stable for small p
+    let prob = dbinom_raw(0.0, x, p, 1.0 - p, give_log);
+
+    if give_log {
+        p.ln() + prob
+    } else {
+        p * prob
+    }
+}
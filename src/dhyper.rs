@@ -0,0 +1,93 @@
+use crate::dbinom::dbinom_raw;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d__1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_d_neg_i_nonint;
+use crate::nmath::r_d_nonint_check;
+use crate::nmath::r_forceint;
+
+//
+//  AUTHOR
+//    Catherine Loader, catherine@research.bell-labs.com.
+//    October 23, 2000.
+//
+//  Merge in to R:
+//	Copyright (C) 2000-2014 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density of the hypergeometric distribution.
+///
+/// Given a sequence of r successes and b failures, we sample n (<= b+r)
+/// items without replacement. The hypergeometric probability is the
+/// probability of x successes:
+///
+/// ```text
+///                choose(r, x) * choose(b, n-x)
+/// p(x; r,b,n) =  -----------------------------  =
+///                       choose(r+b, n)
+///
+///               dbinom(x,r,p) * dbinom(n-x,b,p)
+///             = --------------------------------
+///                       dbinom(n,r+b,p)
+/// ```
+///
+/// for any p. For numerical stability, we take p=n/(r+b); with this choice,
+/// the denominator is not exponentially small.
+pub fn dhyper(x: f64, r: f64, b: f64, n: f64, give_log: bool) -> f64 {
+    if x.is_nan() || r.is_nan() || b.is_nan() || n.is_nan() {
+        return x + r + b + n;
+    }
+
+    if r_d_neg_i_nonint(r) || r_d_neg_i_nonint(b) || r_d_neg_i_nonint(n) || n > r + b {
+        return ml_warn_return_nan();
+    }
+    if x < 0.0 {
+        return r_d__0(give_log);
+    }
+    // incl warning
+    if let Some(r) = r_d_nonint_check(x, give_log, "dhyper") {
+        return r;
+    }
+
+    let x = r_forceint(x);
+    let r = r_forceint(r);
+    let b = r_forceint(b);
+    let n = r_forceint(n);
+
+    if n < x || r < x || n - x > b {
+        return r_d__0(give_log);
+    }
+    if n == 0.0 {
+        return if x == 0.0 {
+            r_d__1(give_log)
+        } else {
+            r_d__0(give_log)
+        };
+    }
+
+    let p = n / (r + b);
+    let q = (r + b - n) / (r + b);
+
+    let p1 = dbinom_raw(x, r, p, q, give_log);
+    let p2 = dbinom_raw(n - x, b, p, q, give_log);
+    let p3 = dbinom_raw(n, r + b, p, q, give_log);
+
+    if give_log {
+        p1 + p2 - p3
+    } else {
+        p1 * p2 / p3
+    }
+}
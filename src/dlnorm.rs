@@ -0,0 +1,57 @@
+use crate::dpq::r_d__0;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_POSINF;
+use crate::nmath::M_LN_SQRT_2PI;
+use crate::rmath::M_1_SQRT_2PI;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 2000-2019 The R Core Team
+//  Copyright (C) 1998 Ross Ihaka
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density of the lognormal distribution.
+pub fn dlnorm(x: f64, meanlog: f64, sdlog: f64, give_log: bool) -> f64 {
+    if x.is_nan() || meanlog.is_nan() || sdlog.is_nan() {
+        return x + meanlog + sdlog;
+    }
+    if sdlog < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(x) && x.ln() == meanlog {
+        // log(x) - meanlog is NaN
+        return ML_NAN;
+    }
+    if sdlog == 0.0 {
+        return if x.ln() == meanlog {
+            ML_POSINF
+        } else {
+            r_d__0(give_log)
+        };
+    }
+    if x <= 0.0 {
+        return r_d__0(give_log);
+    }
+
+    let y = (x.ln() - meanlog) / sdlog;
+    if give_log {
+        -(M_LN_SQRT_2PI + 0.5 * y * y + (x * sdlog).ln())
+    } else {
+        M_1_SQRT_2PI * (-0.5 * y * y).exp() / (x * sdlog)
+    }
+}
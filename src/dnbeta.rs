@@ -0,0 +1,136 @@
+use crate::dbeta::dbeta;
+use crate::dpois::dpois_raw;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d_exp;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::LDouble;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-12 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Computes the density of the noncentral beta distribution with
+/// noncentrality parameter ncp.
+///
+/// The noncentral beta distribution has density:
+///
+/// ```text
+///                Inf
+/// f(x|a,b,ncp) = SUM  p(i) *  x^(a+i-1) * (1-x)^(b-1) / B(a+i,b)
+///                i=0
+/// ```
+///
+/// where:
+///
+/// ```text
+///   p(k) = exp(-ncp/2) (ncp/2)^k / k!
+///
+/// B(a,b) = Gamma(a) * Gamma(b) / Gamma(a+b)
+/// ```
+///
+/// This can be computed efficiently by using the recursions:
+///
+/// ```text
+///     p(k+1) = ncp/2 / (k+1) * p(k)
+///
+/// B(a+k+1,b) = (a+k)/(a+b+k) * B(a+k,b)
+/// ```
+///
+/// The algorithm first determines for which k the k-th term is maximal,
+/// and then sums outwards to both sides from the 'mid', in `LDouble`.
+pub fn dnbeta(x: f64, a: f64, b: f64, ncp: f64, give_log: bool) -> f64 {
+    const EPS: f64 = 1.0e-15;
+
+    if x.is_nan() || a.is_nan() || b.is_nan() || ncp.is_nan() {
+        return x + a + b + ncp;
+    }
+    if ncp < 0.0 || a <= 0.0 || b <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if !r_finite(a) || !r_finite(b) || !r_finite(ncp) {
+        return ml_warn_return_nan();
+    }
+
+    if x < 0.0 || x > 1.0 {
+        return r_d__0(give_log);
+    }
+    if ncp == 0.0 {
+        return dbeta(x, a, b, give_log);
+    }
+
+    // New algorithm, starting with *largest* term :
+    let ncp2 = 0.5 * ncp;
+    let dx2 = ncp2 * x;
+    let d = (dx2 - a - 1.0) / 2.0;
+    let d_ = d * d + dx2 * (a + b) - a;
+    let k_max = if d_ <= 0.0 {
+        0.0
+    } else {
+        let d_ = (d + d_.sqrt()).ceil();
+        if d_ > 0.0 {
+            d_
+        } else {
+            0.0
+        }
+    };
+
+    // The starting "middle term" --- first look at it's log scale:
+    let term = dbeta(x, a + k_max, b, true);
+    let p_k = dpois_raw(k_max, ncp2, true);
+    if x == 0.0 || !r_finite(term) || !r_finite(p_k) {
+        // if term = +Inf
+        return r_d_exp(p_k + term, give_log);
+    }
+    let mut p_k = LDouble::new(p_k);
+
+    // Now if s_k := p_k * t_k  {here = exp(p_k + term)} would underflow,
+    // we should rather scale everything and re-scale at the end:
+
+    // = log(p_k) + log(t_k) == log(s_k) -- used at end to rescale
+    p_k += term;
+    // mid = 1 = the rescaled value, instead of  mid = exp(p_k);
+
+    // Now sum from the inside out
+    let mut sum = LDouble::new(1.0); // = mid term
+    let mut term = LDouble::new(1.0);
+    // middle to the left
+    let mut k = k_max;
+    while k > 0.0 && term.to_f64() > sum.to_f64() * EPS {
+        k -= 1.0;
+        let q = (k + 1.0) * (k + a) / (k + a + b) / dx2; // 1 / r_k
+        term *= q;
+        sum += term;
+    }
+    // middle to the right
+    term = LDouble::new(1.0);
+    k = k_max;
+    loop {
+        let q = dx2 * (k + a + b) / (k + a) / (k + 1.0); // r_{old k}
+        k += 1.0;
+        term *= q;
+        sum += term;
+        if !(term.to_f64() > sum.to_f64() * EPS) {
+            break;
+        }
+    }
+
+    p_k += sum.ln();
+    r_d_exp(p_k.to_f64(), give_log)
+}
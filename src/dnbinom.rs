@@ -0,0 +1,142 @@
+use crate::dbinom::dbinom_raw;
+use crate::dpois::dpois_raw;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d__1;
+use crate::dpq::r_d_exp;
+use crate::libc::DBL_MAX;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_d_nonint_check;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+
+//
+//  AUTHOR
+//    Catherine Loader, catherine@research.bell-labs.com.
+//    October 23, 2000 and Feb, 2001.
+//
+//    dnbinom_mu(): Martin Maechler, June 2008
+//
+//  Merge in to R:
+//	Copyright (C) 2000--2016, The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density of the negative binomial distribution.
+///
+/// For integer `size`, this is the probability of `x` failures before the
+/// `size`th success in a sequence of Bernoulli trials. Integer `size` is
+/// not enforced, since the distribution is well defined for non-integers,
+/// and this can be useful for e.g. overdispersed discrete survival times.
+pub fn dnbinom(x: f64, size: f64, prob: f64, give_log: bool) -> f64 {
+    if x.is_nan() || size.is_nan() || prob.is_nan() {
+        return x + size + prob;
+    }
+
+    if prob <= 0.0 || prob > 1.0 || size < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if let Some(r) = r_d_nonint_check(x, give_log, "dnbinom") {
+        return r;
+    }
+    if x < 0.0 || !r_finite(x) {
+        return r_d__0(give_log);
+    }
+    // limiting case as size approaches zero is point mass at zero
+    if x == 0.0 && size == 0.0 {
+        return r_d__1(give_log);
+    }
+    let x = r_forceint(x);
+    let size = if r_finite(size) { size } else { DBL_MAX };
+
+    let ans = dbinom_raw(size, x + size, prob, 1.0 - prob, give_log);
+    let p = size / (size + x);
+    if give_log {
+        p.ln() + ans
+    } else {
+        p * ans
+    }
+}
+
+/// The density of the negative binomial distribution, parametrized by the
+/// mean `mu`.
+///
+/// Originally, this just set `prob := size / (size + mu)` and called
+/// `dbinom_raw()`, but that suffers from cancellation when `mu << size`.
+pub fn dnbinom_mu(x: f64, size: f64, mu: f64, give_log: bool) -> f64 {
+    if x.is_nan() || size.is_nan() || mu.is_nan() {
+        return x + size + mu;
+    }
+
+    if mu < 0.0 || size < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if let Some(r) = r_d_nonint_check(x, give_log, "dnbinom_mu") {
+        return r;
+    }
+    if x < 0.0 || !r_finite(x) {
+        return r_d__0(give_log);
+    }
+
+    // limiting case as size approaches zero is point mass at zero,
+    // even if mu is kept constant. limit distribution does not
+    // have mean mu, though.
+    if x == 0.0 && size == 0.0 {
+        return r_d__1(give_log);
+    }
+    let x = r_forceint(x);
+    if !r_finite(size) {
+        // limit case: Poisson
+        return dpois_raw(x, mu, give_log);
+    }
+
+    if x == 0.0 {
+        // be accurate, both for n << mu, and n >> mu :
+        let lc = if size < mu {
+            (size / (size + mu)).ln()
+        } else {
+            (-mu / (size + mu)).ln_1p()
+        };
+        return r_d_exp(size * lc, give_log);
+    }
+    if x < 1e-10 * size {
+        // don't use dbinom_raw() but MM's formula:
+        // FIXME --- 1e-8 shows problem; rather use algdiv() from ./toms708.c
+        let p = if size < mu {
+            (size / (1.0 + size / mu)).ln()
+        } else {
+            (mu / (1.0 + mu / size)).ln()
+        };
+        r_d_exp(
+            x * p - mu - libm::lgamma(x + 1.0) + (x * (x - 1.0) / (2.0 * size)).ln_1p(),
+            give_log,
+        )
+    } else {
+        // no unnecessary cancellation inside dbinom_raw, when
+        // x_ = size and n_ = x+size are so close that n_ - x_ loses accuracy
+        let p = size / (size + x);
+        let ans = dbinom_raw(
+            size,
+            x + size,
+            size / (size + mu),
+            mu / (size + mu),
+            give_log,
+        );
+        if give_log {
+            p.ln() + ans
+        } else {
+            p * ans
+        }
+    }
+}
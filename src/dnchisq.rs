@@ -0,0 +1,130 @@
+use crate::dchisq::dchisq;
+use crate::dpois::dpois_raw;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d_val;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::LDouble;
+use crate::nmath::ML_POSINF;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-15 The R Core Team
+//  Copyright (C) 2004-15 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density of the noncentral chi-squared distribution with "df"
+/// degrees of freedom and noncentrality parameter "ncp".
+///
+/// The sum of the Poisson weighted central densities is evaluated in
+/// `LDouble`, starting at its largest term and going outwards in both
+/// directions.
+pub fn dnchisq(x: f64, df: f64, ncp: f64, give_log: bool) -> f64 {
+    const EPS: f64 = 5e-15;
+
+    if x.is_nan() || df.is_nan() || ncp.is_nan() {
+        return x + df + ncp;
+    }
+
+    if !r_finite(df) || !r_finite(ncp) || ncp < 0.0 || df < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if x < 0.0 {
+        return r_d__0(give_log);
+    }
+    if x == 0.0 && df < 2.0 {
+        return ML_POSINF;
+    }
+    if ncp == 0.0 {
+        return if df > 0.0 {
+            dchisq(x, df, give_log)
+        } else {
+            r_d__0(give_log)
+        };
+    }
+    if x == ML_POSINF {
+        return r_d__0(give_log);
+    }
+
+    let ncp2 = 0.5 * ncp;
+
+    // find max element of sum
+    let mut imax = ((-(2.0 + df) + ((2.0 - df) * (2.0 - df) + 4.0 * ncp * x).sqrt()) / 4.0).ceil();
+    if imax < 0.0 {
+        imax = 0.0;
+    }
+    let dfmid = df + 2.0 * imax;
+    let mid = if r_finite(imax) {
+        dpois_raw(imax, ncp2, false) * dchisq(x, dfmid, false)
+    } else {
+        // imax = Inf
+        0.0
+    };
+
+    if mid == 0.0 {
+        // underflow to 0 -- maybe numerically correct; maybe can be more accurate,
+        // particularly when  give_log = TRUE
+        // Use  central-chisq approximation formula when appropriate;
+        // ((FIXME: the optimal cutoff also depends on (x,df);  use always here? ))
+        if give_log || ncp > 1000.0 {
+            let nl = df + ncp;
+            let ic = nl / (nl + ncp); // = "1/(1+b)" Abramowitz & St.
+            return dchisq(x * ic, nl * ic, give_log);
+        } else {
+            return r_d__0(give_log);
+        }
+    }
+
+    let mut sum = LDouble::new(mid);
+
+    // errorbound := term * q / (1-q)  now subsumed in the loop conditions below
+
+    // upper tail
+    let mut term = LDouble::new(mid);
+    let mut df = dfmid;
+    let mut i = imax;
+    let x2 = x * ncp2;
+    loop {
+        i += 1.0;
+        let q = x2 / i / df;
+        df += 2.0;
+        term *= q;
+        sum += term;
+        if !(q >= 1.0
+            || (term * q).to_f64() > (1.0 - q) * EPS
+            || term.to_f64() > 1e-10 * sum.to_f64())
+        {
+            break;
+        }
+    }
+    // lower tail
+    term = LDouble::new(mid);
+    df = dfmid;
+    i = imax;
+    while i != 0.0 {
+        df -= 2.0;
+        let q = i * df / x2;
+        i -= 1.0;
+        term *= q;
+        sum += term;
+        if q < 1.0 && (term * q).to_f64() <= (1.0 - q) * EPS {
+            break;
+        }
+    }
+    r_d_val(sum.to_f64(), give_log)
+}
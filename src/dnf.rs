@@ -0,0 +1,90 @@
+use crate::dgamma::dgamma;
+use crate::dnbeta::dnbeta;
+use crate::dnchisq::dnchisq;
+use crate::dpq::r_d__0;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+
+//
+//  AUTHOR
+//    Peter Ruckdeschel, peter.ruckdeschel@uni-bayreuth.de.
+//    April 13, 2006.
+//
+//  Merge in to R:
+//	Copyright (C) 2006 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density function of the non-central F distribution.
+///
+/// Obtained by differentiating the corresponding cumulative distribution
+/// function using `dnbeta`.
+/// For df1 < 2, since the F density has a singularity as x -> Inf.
+pub fn dnf(x: f64, df1: f64, df2: f64, ncp: f64, give_log: bool) -> f64 {
+    if x.is_nan() || df1.is_nan() || df2.is_nan() || ncp.is_nan() {
+        return x + df2 + df1 + ncp;
+    }
+
+    // want to compare dnf(ncp=0) behavior with df() one, hence *NOT* :
+    // if (ncp == 0)
+    //   return df(x, df1, df2, give_log);
+
+    if df1 <= 0.0 || df2 <= 0.0 || ncp < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if x < 0.0 {
+        return r_d__0(give_log);
+    }
+    if !r_finite(ncp) {
+        // ncp = +Inf -- FIXME?: in some cases, limit exists
+        return ml_warn_return_nan();
+    }
+
+    // This is not correct for  df1 == 2, ncp > 0 - and seems unneeded:
+    //  if (x == 0.) return(df1 > 2 ? R_D__0 : (df1 == 2 ? R_D__1 : ML_POSINF));
+    if !r_finite(df1) && !r_finite(df2) {
+        // both +Inf
+        // PR: not sure about this (taken from  ncp==0)  -- FIXME ?
+        return if x == 1.0 {
+            ML_POSINF
+        } else {
+            r_d__0(give_log)
+        };
+    }
+    if !r_finite(df2) {
+        // i.e.  = +Inf
+        return df1 * dnchisq(x * df1, df1, ncp, give_log);
+    }
+    //	 ==  dngamma(x, df1/2, 2./df1, ncp, give_log)  -- but that does not exist
+    if df1 > 1e14 && ncp < 1e7 {
+        // includes df1 == +Inf: code below is inaccurate there
+        let f = 1.0 + ncp / df1; // assumes  ncp << df1 [ignores 2*ncp^(1/2)/df1*x term]
+        let z = dgamma(1.0 / x / f, df2 / 2.0, 2.0 / df2, give_log);
+        return if give_log {
+            z - 2.0 * x.ln() - f.ln()
+        } else {
+            z / (x * x) / f
+        };
+    }
+
+    let y = (df1 / df2) * x;
+    let z = dnbeta(y / (1.0 + y), df1 / 2.0, df2 / 2.0, ncp, give_log);
+    if give_log {
+        z + df1.ln() - df2.ln() - 2.0 * y.ln_1p()
+    } else {
+        z * (df1 / df2) / (1.0 + y) / (1.0 + y)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::dnorm::dnorm4;
+use crate::dpq::r_d__0;
+use crate::dt::dt;
+use crate::lgamma::lgammafn;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::M_LN_SQRT_PI;
+use crate::pnt::pnt;
+
+/// The density of the non-central t-distribution.
+///
+/// ## AUTHOR
+///
+/// Claus Ekstrøm, ekstrom@dina.kvl.dk
+/// July 15, 2003.
+///
+/// ## NOTES
+///
+/// From Johnson, Kotz and Balakrishnan (1995) [2nd ed.; formula (31.15), p.516],
+/// the functional relationship
+///
+/// ```text
+/// f(x, df, ncp) = df/x * (F(sqrt((df+2)/df)*x, df+2, ncp) - F(x, df, ncp))
+/// ```
+///
+/// is used to evaluate the density at x != 0 and
+///
+/// ```text
+/// f(0, df, ncp) = exp(-.5*ncp^2) / (sqrt(pi)*sqrt(df)*gamma(df/2))*gamma((df+1)/2)
+/// ```
+///
+/// is used for x=0.
+///
+/// All calculations are done on log-scale to increase stability.
+pub fn dnt(x: f64, df: f64, ncp: f64, give_log: bool) -> f64 {
+    if x.is_nan() || df.is_nan() {
+        return x + df;
+    }
+
+    // If non-positive df then error
+    if df <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if ncp == 0.0 {
+        return dt(x, df, give_log);
+    }
+
+    // If x is infinite then return 0
+    if !r_finite(x) {
+        return r_d__0(give_log);
+    }
+
+    // If infinite df then the density is identical to a
+    // normal distribution with mean = ncp.  However, the formula
+    // loses a lot of accuracy around df=1e9
+    if !r_finite(df) || df > 1e8 {
+        return dnorm4(x, ncp, 1.0, give_log);
+    }
+
+    // Do calculations on log scale to stabilize
+
+    // Consider two cases: x ~= 0 or not
+    let u = if x.abs() > (df * DBL_EPSILON).sqrt() {
+        // FIXME: the above still suffers from cancellation (but not horribly)
+        df.ln() - x.abs().ln()
+            + (pnt(x * ((df + 2.0) / df).sqrt(), df + 2.0, ncp, true, false)
+                - pnt(x, df, ncp, true, false))
+            .abs()
+            .ln()
+    } else {
+        // x ~= 0 : -> same value as for  x = 0
+        lgammafn((df + 1.0) / 2.0)
+            - lgammafn(df / 2.0)
+            - (M_LN_SQRT_PI + 0.5 * (df.ln() + ncp * ncp))
+    };
+
+    if give_log {
+        u
+    } else {
+        u.exp()
+    }
+}
@@ -1,5 +1,9 @@
+use crate::dpq::r_d__0;
+use crate::dpq::r_d__1;
+use crate::dpq::r_d_exp;
 use crate::ebd0;
 use crate::lgammafn;
+use crate::libc::DBL_MIN;
 use crate::nmath::ml_warn_return_nan;
 use crate::nmath::r_d_nonint_check;
 use crate::nmath::r_forceint;
@@ -16,22 +20,28 @@ const x_LRG: f64 = 2.861_117_485_757_028_3e307; // = 2^1023 / pi
 /// checks should be done in the calling function.
 pub fn dpois_raw(x: f64, lambda: f64, give_log: bool) -> f64 {
     if lambda == 0.0 {
-        return if x == 0.0 { 1.0 } else { 0.0 };
+        return if x == 0.0 {
+            r_d__1(give_log)
+        } else {
+            r_d__0(give_log)
+        };
     }
     if !lambda.is_finite() {
-        return 0.0;
+        // including for the case where  x = lambda = +Inf
+        return r_d__0(give_log);
     }
     if x < 0.0 {
-        return 0.0;
+        return r_d__0(give_log);
     }
-    if x <= lambda * f64::MIN {
-        return (-lambda).exp();
+    if x <= lambda * DBL_MIN {
+        return r_d_exp(-lambda, give_log);
     }
-    if lambda < x * f64::MIN {
+    if lambda < x * DBL_MIN {
         if !x.is_finite() {
-            return 0.0;
+            // lambda < x = +Inf
+            return r_d__0(give_log);
         }
-        return (-lambda + x * lambda.ln() - lgammafn(x + 1.0)).exp();
+        return r_d_exp(-lambda + x * lambda.ln() - lgammafn(x + 1.0), give_log);
     }
 
     let (yh, yl) = ebd0(x, lambda);
@@ -59,9 +69,11 @@ pub fn dpois(x: f64, lambda: f64, give_log: bool) -> f64 {
     if lambda < 0.0 {
         return ml_warn_return_nan();
     }
-    r_d_nonint_check(x, give_log);
+    if let Some(r) = r_d_nonint_check(x, give_log, "dpois") {
+        return r;
+    }
     if x < 0.0 || !x.is_finite() {
-        return 0.0;
+        return r_d__0(give_log);
     }
 
     let x = r_forceint(x);
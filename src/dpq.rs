@@ -53,6 +53,15 @@ pub fn r_d_cval(p: f64, lower_tail: bool) -> f64 {
     }
 }
 
+/// R_D_qIv(p) := log_p ? exp(p) : p
+pub fn r_d_qiv(p: f64, log_p: bool) -> f64 {
+    if log_p {
+        p.exp()
+    } else {
+        p
+    }
+}
+
 pub fn r_dt_qiv(p: f64, lower_tail: bool, log_p: bool) -> f64 {
     if log_p {
         if lower_tail {
@@ -73,8 +82,92 @@ pub fn r_dt_civ(p: f64, lower_tail: bool, log_p: bool) -> f64 {
             p.exp()
         }
     } else {
-        r_d_cval(p, !lower_tail)
+        r_d_cval(p, lower_tail)
+    }
+}
+
+/// R_D_val(x) := log_p ? log(x) : x
+pub fn r_d_val(x: f64, log_p: bool) -> f64 {
+    if log_p {
+        x.ln()
+    } else {
+        x
+    }
+}
+
+/// R_D_exp(x) := log_p ? x : exp(x)
+pub fn r_d_exp(x: f64, log_p: bool) -> f64 {
+    if log_p {
+        x
+    } else {
+        x.exp()
+    }
+}
+
+/// R_D_log(p) := log_p ? p : log(p)
+pub fn r_d_log(p: f64, log_p: bool) -> f64 {
+    if log_p {
+        p
+    } else {
+        p.ln()
+    }
+}
+
+/// R_D_Clog(p) := log_p ? log(1-p) : 1-p
+pub fn r_d_clog(p: f64, log_p: bool) -> f64 {
+    if log_p {
+        (-p).ln_1p()
+    } else {
+        0.5 - p + 0.5
+    }
+}
+
+/// R_D_LExp(x) := log(1 - exp(x)) in log scale, log(1 - x) otherwise.
+pub fn r_d_lexp(x: f64, log_p: bool) -> f64 {
+    if log_p {
+        r_log1_exp(x)
+    } else {
+        (-x).ln_1p()
+    }
+}
+
+/// R_DT_val(x) := (lower_tail ? R_D_val(x) : R_D_Clog(x))
+pub fn r_dt_val(x: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if lower_tail {
+        r_d_val(x, log_p)
+    } else {
+        r_d_clog(x, log_p)
+    }
+}
+
+/// R_DT_log(p) := log(p) in the lower tail.
+pub fn r_dt_log(p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if lower_tail {
+        r_d_log(p, log_p)
+    } else {
+        r_d_lexp(p, log_p)
+    }
+}
+
+/// R_DT_Clog(p) := log(1-p) in the lower tail.
+pub fn r_dt_clog(p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if lower_tail {
+        r_d_lexp(p, log_p)
+    } else {
+        r_d_log(p, log_p)
+    }
+}
+
+/// Check that `p` is a valid probability for q*() functions.
+///
+/// This was originally the macro R_Q_P01_check.
+/// At the caller site, if the return value is not None, then return the
+/// result immediately.
+pub fn r_q_p01_check(p: f64, log_p: bool) -> Option<f64> {
+    if (log_p && p > 0.0) || (!log_p && !(0.0..=1.0).contains(&p)) {
+        return Some(ml_warn_return_nan());
     }
+    None
 }
 
 /// Calculate the boundaries exactly for q*() functions.
@@ -100,7 +193,7 @@ pub fn r_q_p01_boundaries(
 ) -> Option<f64> {
     if log_p {
         if p > 0.0 {
-            ml_warn_return_nan();
+            return Some(ml_warn_return_nan());
         }
         if p == 0.0 {
             return Some(if lower_tail { right } else { left });
@@ -111,7 +204,7 @@ pub fn r_q_p01_boundaries(
         None
     } else {
         if !(0.0..=1.0).contains(&p) {
-            ml_warn_return_nan();
+            return Some(ml_warn_return_nan());
         }
         if p == 0.0 {
             return Some(if lower_tail { left } else { right });
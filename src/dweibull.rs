@@ -0,0 +1,52 @@
+use crate::dpq::r_d__0;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-6 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The density of the Weibull distribution.
+pub fn dweibull(x: f64, shape: f64, scale: f64, give_log: bool) -> f64 {
+    if x.is_nan() || shape.is_nan() || scale.is_nan() {
+        return x + shape + scale;
+    }
+    if shape <= 0.0 || scale <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if x < 0.0 {
+        return r_d__0(give_log);
+    }
+    if !r_finite(x) {
+        return r_d__0(give_log);
+    }
+    // need to handle x == 0 separately
+    if x == 0.0 && shape < 1.0 {
+        return ML_POSINF;
+    }
+    let tmp1 = (x / scale).powf(shape - 1.0);
+    let tmp2 = tmp1 * (x / scale);
+    // These are incorrect if tmp1 == 0
+    if give_log {
+        -tmp2 + (shape * tmp1 / scale).ln()
+    } else {
+        shape * tmp1 * (-tmp2).exp() / scale
+    }
+}
@@ -0,0 +1,11 @@
+/// The maximum of `x` and `y`, propagating NaN like R's `fmax2`.
+pub fn fmax2(x: f64, y: f64) -> f64 {
+    if x.is_nan() || y.is_nan() {
+        return x + y;
+    }
+    if x < y {
+        y
+    } else {
+        x
+    }
+}
@@ -0,0 +1,11 @@
+/// The minimum of `x` and `y`, propagating NaN like R's `fmin2`.
+pub fn fmin2(x: f64, y: f64) -> f64 {
+    if x.is_nan() || y.is_nan() {
+        return x + y;
+    }
+    if x < y {
+        x
+    } else {
+        y
+    }
+}
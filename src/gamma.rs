@@ -2,6 +2,9 @@ use crate::chebyshev::*;
 use crate::nmath::*;
 use crate::rmath::*;
 use crate::sinpi;
+use crate::stirlerr::stirlerr;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 
 /// Chebyshev coefficients for gamma function
 const GAMCS: [f64; 42] = [
@@ -50,6 +53,10 @@ const GAMCS: [f64; 42] = [
 ];
 
 /// Machine dependent constants for IEEE double precision
+///
+/// NGAM is the number of GAMCS terms needed for DBL_EPSILON/20, see
+/// `chebyshev_init`.
+const NGAM: i32 = 22;
 const XMIN: f64 = -170.5674972726612;
 const XMAX: f64 = 171.61447887182298;
 const XSML: f64 = 2.2474362225598545e-308;
@@ -86,7 +93,7 @@ pub fn gammafn(x: f64) -> f64 {
         }
         let y = x - n as f64;
         n -= 1;
-        let mut value = chebyshev_eval(y * 2.0 - 1.0, &GAMCS, GAMCS.len() as i32) + 0.9375;
+        let mut value = chebyshev_eval(y * 2.0 - 1.0, &GAMCS, NGAM) + 0.9375;
 
         if n == 0 {
             return value;
@@ -96,7 +103,7 @@ pub fn gammafn(x: f64) -> f64 {
                 return ml_warn_return_nan();
             }
             if y < XSML {
-                println!("gammafn: Range warning");
+                ml_warning(MlWarningKind::Range, "gammafn");
                 return if x > 0.0 { ML_POSINF } else { ML_NEGINF };
             }
             n = -n;
@@ -124,7 +131,9 @@ pub fn gammafn(x: f64) -> f64 {
                 value *= i as f64;
             }
         } else {
-            value = ((y - 0.5) * y.ln() - y + M_LN2 + M_LN_SQRT_2PI).exp();
+            // The C code tests `2*y == (int)2*y`, which always holds, and so
+            // always uses stirlerr(y) instead of lgammacor(y).
+            value = ((y - 0.5) * y.ln() - y + M_LN_SQRT_2PI + stirlerr(y)).exp();
         }
         if x > 0.0 {
             value
@@ -134,7 +143,7 @@ pub fn gammafn(x: f64) -> f64 {
             }
             let sinpiy = sinpi(y);
             if sinpiy == 0.0 {
-                println!("gammafn: Range warning - Negative integer arg - overflow");
+                ml_warning(MlWarningKind::Range, "gammafn");
                 return ML_POSINF;
             }
             -M_PI / (y * sinpiy * value)
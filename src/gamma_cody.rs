@@ -0,0 +1,168 @@
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MIN;
+use crate::nmath::ML_POSINF;
+use crate::rmath::M_PI;
+use crate::sinpi;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998-2014 Ross Ihaka and the R Core team.
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+// From http://www.netlib.org/specfun/gamma  Fortran translated by f2c,...
+//   ------------------------------#####  Martin Maechler, ETH Zurich
+//
+// =========== was part of  ribesl (Bessel I(.))
+
+/// The gamma function, used by `bessel_i` and `bessel_j`.
+///
+/// Computation is based on an algorithm outlined in "An Overview of
+/// Software Development for Special Functions", W. J. Cody, Lecture Notes
+/// in Mathematics, 506, 1976.
+/// The program uses rational functions that approximate the gamma
+/// function to at least 20 significant decimal digits.
+/// Returns `ML_POSINF` for singularities or when overflow would occur.
+///
+/// Authors: W. J. Cody and L. Stoltz, Argonne National Laboratory.
+#[allow(clippy::excessive_precision)]
+pub fn gamma_cody(x: f64) -> f64 {
+    // Mathematical constants
+    const SQRTPI: f64 = 0.9189385332046727417803297; // == ???
+
+    // Machine dependent parameters:
+    // XBIG - the largest argument for which GAMMA(X) is representable
+    const XBIG: f64 = 171.624;
+
+    // Numerator and denominator coefficients for rational minimax
+    // approximation over (1,2).
+    const P: [f64; 8] = [
+        -1.71618513886549492533811,
+        24.7656508055759199108314,
+        -379.804256470945635097577,
+        629.331155312818442661052,
+        866.966202790413211295064,
+        -31451.2729688483675254357,
+        -36144.4134186911729807069,
+        66456.1438202405440627855,
+    ];
+    const Q: [f64; 8] = [
+        -30.8402300119738975254353,
+        315.350626979604161529144,
+        -1015.15636749021914166146,
+        -3107.77167157231109440444,
+        22538.1184209801510330112,
+        4755.84627752788110767815,
+        -134659.959864969306392456,
+        -115132.259675553483497211,
+    ];
+
+    // Coefficients for minimax approximation over (12, INF).
+    const C: [f64; 7] = [
+        -0.001910444077728,
+        8.4171387781295e-4,
+        -5.952379913043012e-4,
+        7.93650793500350248e-4,
+        -0.002777777777777681622553,
+        0.08333333333333333331554247,
+        0.0057083835261,
+    ];
+
+    let mut parity = false;
+    let mut fact = 1.;
+    let mut n = 0;
+    let mut y = x;
+    let mut res;
+
+    if y <= 0. {
+        // Argument is negative
+        y = -x;
+        let yi = y.trunc();
+        res = y - yi;
+        if res != 0. {
+            if yi != (yi * 0.5).trunc() * 2. {
+                parity = true;
+            }
+            fact = -M_PI / sinpi(res);
+            y += 1.;
+        } else {
+            return ML_POSINF;
+        }
+    }
+
+    // Argument is positive
+    if y < DBL_EPSILON {
+        // Argument < EPS
+        if y >= DBL_MIN {
+            res = 1. / y;
+        } else {
+            return ML_POSINF;
+        }
+    } else if y < 12. {
+        let yi = y;
+        let z;
+        if y < 1. {
+            // EPS < argument < 1
+            z = y;
+            y += 1.;
+        } else {
+            // 1 <= argument < 12, reduce argument if necessary
+            n = y as i32 - 1;
+            y -= n as f64;
+            z = y - 1.;
+        }
+        // Evaluate approximation for 1. < argument < 2.
+        let mut xnum = 0.;
+        let mut xden = 1.;
+        for (p, q) in P.iter().zip(Q.iter()) {
+            xnum = (xnum + p) * z;
+            xden = xden * z + q;
+        }
+        res = xnum / xden + 1.;
+        if yi < y {
+            // Adjust result for case  0. < argument < 1.
+            res /= yi;
+        } else if yi > y {
+            // Adjust result for case  2. < argument < 12.
+            for _ in 0..n {
+                res *= y;
+                y += 1.;
+            }
+        }
+    } else {
+        // Evaluate for argument >= 12.,
+        if y <= XBIG {
+            let ysq = y * y;
+            let mut sum = C[6];
+            for c in C.iter().take(6) {
+                sum = sum / ysq + c;
+            }
+            sum = sum / y - y + SQRTPI;
+            sum += (y - 0.5) * y.ln();
+            res = sum.exp();
+        } else {
+            return ML_POSINF;
+        }
+    }
+
+    // Final adjustments and return
+    if parity {
+        res = -res;
+    }
+    if fact != 1. {
+        res = fact / res;
+    }
+    res
+}
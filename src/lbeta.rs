@@ -1,6 +1,3 @@
-use libm::log;
-use libm::log1p;
-
 use crate::gammafn;
 use crate::lgammacor;
 use crate::lgammafn;
@@ -38,22 +35,22 @@ pub fn lbeta(a: f64, b: f64) -> f64 {
     if p >= 10.0 {
         // p and q are big.
         let corr = lgammacor(p) + lgammacor(q) - lgammacor(p + q);
-        log(q) * -0.5
+        q.ln() * -0.5
             + M_LN_SQRT_2PI
             + corr
-            + (p - 0.5) * log(p / (p + q))
-            + q * log1p(-p / (p + q))
+            + (p - 0.5) * (p / (p + q)).ln()
+            + q * (-p / (p + q)).ln_1p()
     } else if q >= 10.0 {
         // p is small, but q is big.
         let corr = lgammacor(q) - lgammacor(p + q);
-        lgammafn(p) + corr + p - p * log(p + q) + (q - 0.5) * log1p(-p / (p + q))
+        lgammafn(p) + corr + p - p * (p + q).ln() + (q - 0.5) * (-p / (p + q)).ln_1p()
     } else {
         // p and q are small: p <= q < 10.
         // R change for very small args
         if p < 1e-306 {
             lgammafn(p) + (lgammafn(q) - lgammafn(p + q))
         } else {
-            log(gammafn(p) * (gammafn(q) / gammafn(p + q)))
+            (gammafn(p) * (gammafn(q) / gammafn(p + q))).ln()
         }
     }
 }
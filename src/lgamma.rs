@@ -2,6 +2,8 @@ use crate::gammafn;
 use crate::lgammacor;
 use crate::nmath::*;
 use crate::sinpi;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 
 /// Machine dependent constants for IEEE double precision
 const XMAX: f64 = 2.532_737_276_080_075_8e305;
@@ -65,9 +67,9 @@ pub fn lgammafn_sign(x: f64, sgn: Option<&mut i32>) -> f64 {
         if x > 1e17 {
             x * (x.ln() - 1.0)
         } else if x > 4934720.0 {
-            return M_LN_SQRT_2PI + (x - 0.5) * x.ln() - x;
+            M_LN_SQRT_2PI + (x - 0.5) * x.ln() - x
         } else {
-            return M_LN_SQRT_2PI + (x - 0.5) * x.ln() - x + lgammacor(x);
+            M_LN_SQRT_2PI + (x - 0.5) * x.ln() - x + lgammacor(x)
         }
     } else {
         // x < -10; y = -x
@@ -78,14 +80,12 @@ pub fn lgammafn_sign(x: f64, sgn: Option<&mut i32>) -> f64 {
             return ML_NAN;
         }
 
-        let ans = M_LN_SQRT_2PI + (x - 0.5) * y.ln() - x - sinpiy.ln() - lgammacor(y);
+        let ans = M_LN_SQRT_PId2 + (x - 0.5) * y.ln() - x - sinpiy.ln() - lgammacor(y);
 
-        // Check for accuracy
-        if ((x - x.trunc() - 0.5) * ans / x).abs() < DXREL {
-            // Warning: answer less than half precision
-            // because the argument is too near a negative integer
-            println!("** should NEVER happen! *** [lgamma.rs: Neg.int, y={}]", y);
-            return ML_NAN; // Placeholder for warning
+        if ((x - (x - 0.5).trunc()) * ans / x).abs() < DXREL {
+            // The answer is less than half precision because
+            // the argument is too near a negative integer; e.g. for  lgamma(1e-7 - 11)
+            ml_warning(MlWarningKind::Precision, "lgamma");
         }
         ans
     }
@@ -1,5 +1,7 @@
 use crate::chebyshev_eval;
 use crate::nmath::*;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 
 const ALGMCS: [f64; 15] = [
     1.666_389_480_451_863_4e-1,
@@ -43,7 +45,7 @@ pub fn lgammacor(x: f64) -> f64 {
     if x < 10.0 {
         return ml_warn_return_nan();
     } else if x >= XMAX {
-        println!("lgammacor: Underflow warning");
+        ml_warning(MlWarningKind::Underflow, "lgammacor");
         // Allow to underflow
     } else if x < XBIG {
         let tmp = 10.0 / x;
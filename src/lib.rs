@@ -2,18 +2,47 @@
 
 // Avoid converting `if n < 1 || n > 1000` to `if !(1..=1000).contains(&n)`.
 #![allow(clippy::manual_range_contains)]
+// Allow `!(a > b)` to keep the NaN behaviour of the loop conditions in the C code.
+#![allow(clippy::neg_cmp_op_on_partial_ord)]
 
 mod bd0;
+mod bessel;
+mod bessel_i;
+mod bessel_j;
+mod bessel_k;
+mod bessel_y;
+mod beta;
 mod chebyshev;
+mod choose;
 mod cospi;
 mod d1mach;
+mod dbeta;
+mod dbinom;
+mod dcauchy;
+mod dchisq;
 mod debug;
+mod dexp;
+mod df;
 mod dgamma;
+mod dgeom;
+mod dhyper;
+mod dlnorm;
+mod dlogis;
+mod dnbeta;
+mod dnbinom;
+mod dnchisq;
+mod dnf;
 mod dnorm;
+mod dnt;
 mod dpois;
 mod dpq;
 mod dt;
+mod dunif;
+mod dweibull;
+mod fmax2;
+mod fmin2;
 mod gamma;
+mod gamma_cody;
 mod i1mach;
 mod lbeta;
 mod lgamma;
@@ -21,27 +50,118 @@ mod lgammacor;
 mod libc;
 mod nmath;
 mod pbeta;
+mod pbinom;
+mod pcauchy;
+mod pchisq;
+mod pexp;
+mod pf;
 mod pgamma;
+mod pgeom;
+mod phyper;
+mod plnorm;
+mod plogis;
+mod pnbeta;
+mod pnbinom;
+mod pnchisq;
+mod pnf;
 mod pnorm;
 mod pnt;
+mod polygamma;
+mod ppois;
 mod pt;
+mod ptukey;
+mod punif;
+mod pweibull;
+mod qbeta;
+mod qbinom;
+mod qcauchy;
+mod qchisq;
+mod qexp;
+mod qf;
+mod qgamma;
+mod qgeom;
+mod qhyper;
+mod qlnorm;
+mod qlogis;
+mod qnbeta;
+mod qnbinom;
+mod qnchisq;
+mod qnf;
 mod qnorm;
+mod qnt;
+mod qpois;
+mod qt;
+mod qtukey;
+mod qunif;
+mod qweibull;
+mod rbeta;
+mod rbinom;
+mod rcauchy;
+mod rchisq;
+mod rexp;
+mod rgamma;
+mod rgeom;
+mod rhyper;
+mod rlnorm;
+mod rlogis;
 mod rmath;
+mod rnbinom;
+mod rng;
+mod rnorm;
+mod rpois;
+mod rrng;
+mod runif;
+mod rweibull;
+mod sexp;
+mod signrank;
+mod snorm;
 mod stirlerr;
+mod sunif;
 mod toms708;
+mod warning;
+mod wilcox;
 
 // Use only explicit exports and no wildcard exports to avoid accidentally
 // exporting symbols that should not be exported.
 pub use bd0::bd0;
 pub use bd0::ebd0;
+pub use bessel_i::bessel_i;
+pub use bessel_i::bessel_i_ex;
+pub use bessel_j::bessel_j;
+pub use bessel_j::bessel_j_ex;
+pub use bessel_k::bessel_k;
+pub use bessel_k::bessel_k_ex;
+pub use bessel_y::bessel_y;
+pub use bessel_y::bessel_y_ex;
+pub use beta::beta;
 pub use chebyshev::chebyshev_eval;
 pub use chebyshev::chebyshev_init;
+pub use choose::choose;
+pub use choose::lchoose;
 pub use cospi::cospi;
 pub use cospi::sinpi;
 pub use cospi::tanpi;
+pub use dbeta::dbeta;
+pub use dbinom::dbinom;
+pub use dcauchy::dcauchy;
+pub use dchisq::dchisq;
+pub use dexp::dexp;
+pub use df::df;
 pub use dgamma::dgamma;
+pub use dgeom::dgeom;
+pub use dhyper::dhyper;
+pub use dlnorm::dlnorm;
+pub use dlogis::dlogis;
+pub use dnbeta::dnbeta;
+pub use dnbinom::dnbinom;
+pub use dnbinom::dnbinom_mu;
+pub use dnchisq::dnchisq;
+pub use dnf::dnf;
+pub use dnt::dnt;
 pub use dpois::dpois;
 pub use dt::dt;
+pub use dunif::dunif;
+pub use dweibull::dweibull;
 pub use gamma::gammafn;
 pub use i1mach::i1mach;
 pub use lbeta::lbeta;
@@ -49,13 +169,99 @@ pub use lgamma::lgammafn;
 pub use lgamma::lgammafn_sign;
 pub use lgammacor::lgammacor;
 pub use pbeta::pbeta;
+pub use pbinom::pbinom;
+pub use pcauchy::pcauchy;
+pub use pchisq::pchisq;
+pub use pexp::pexp;
+pub use pf::pf;
 pub use pgamma::log1pmx;
 pub use pgamma::logspace_add;
+pub use pgamma::logspace_sub;
 pub use pgamma::pgamma;
+pub use pgeom::pgeom;
+pub use phyper::phyper;
+pub use plnorm::plnorm;
+pub use plogis::log1mexp;
+pub use plogis::log1pexp;
+pub use plogis::plogis;
+pub use pnbeta::pnbeta;
+pub use pnbinom::pnbinom;
+pub use pnbinom::pnbinom_mu;
+pub use pnchisq::pnchisq;
+pub use pnf::pnf;
 pub use pnt::pnt;
+pub use polygamma::digamma;
+pub use polygamma::dpsifn;
+pub use polygamma::pentagamma;
+pub use polygamma::psigamma;
+pub use polygamma::tetragamma;
+pub use polygamma::trigamma;
+pub use ppois::ppois;
 pub use pt::pt;
+pub use ptukey::ptukey;
+pub use punif::punif;
+pub use pweibull::pweibull;
+pub use qbeta::qbeta;
+pub use qbinom::qbinom;
+pub use qcauchy::qcauchy;
+pub use qchisq::qchisq;
+pub use qexp::qexp;
+pub use qf::qf;
+pub use qgamma::qgamma;
+pub use qgeom::qgeom;
+pub use qhyper::qhyper;
+pub use qlnorm::qlnorm;
+pub use qlogis::qlogis;
+pub use qnbeta::qnbeta;
+pub use qnbinom::qnbinom;
+pub use qnbinom::qnbinom_mu;
+pub use qnchisq::qnchisq;
+pub use qnf::qnf;
+pub use qnt::qnt;
+pub use qpois::qpois;
+pub use qt::qt;
+pub use qtukey::qtukey;
+pub use qunif::qunif;
+pub use qweibull::qweibull;
+pub use rbeta::rbeta;
+pub use rbinom::rbinom;
+pub use rcauchy::rcauchy;
+pub use rchisq::rchisq;
+pub use rexp::rexp;
+pub use rgamma::rgamma;
+pub use rgeom::rgeom;
+pub use rhyper::rhyper;
+pub use rlnorm::rlnorm;
+pub use rlogis::rlogis;
 pub use rmath::dnorm;
 pub use rmath::pnorm;
 pub use rmath::qnorm;
+pub use rnbinom::rnbinom;
+pub use rnbinom::rnbinom_mu;
+pub use rng::Rng;
+pub use rnorm::rnorm;
+pub use rpois::rpois;
+pub use rrng::RRng;
+pub use rrng::RandomSeedError;
+pub use rrng::RngKind;
+pub use runif::runif;
+pub use rweibull::rweibull;
+pub use sexp::exp_rand;
+pub use signrank::dsignrank;
+pub use signrank::psignrank;
+pub use signrank::qsignrank;
+pub use signrank::rsignrank;
+pub use snorm::NormKind;
 pub use stirlerr::stirlerr;
+pub use sunif::SampleKind;
 pub use toms708::bratio;
+pub use warning::take_warnings;
+pub use warning::MlWarning;
+pub use warning::MlWarningKind;
+pub use wilcox::dwilcox;
+pub use wilcox::pwilcox;
+pub use wilcox::qwilcox;
+pub use wilcox::rwilcox;
+pub use wilcox::WilcoxCounts;
+pub use wilcox::WilcoxError;
+pub use wilcox::WILCOX_DEFAULT_LIMIT;
@@ -1,6 +1,7 @@
-pub const DBL_MIN: f64 = f64::MIN;
+pub const DBL_MIN: f64 = f64::MIN_POSITIVE;
 pub const DBL_MAX: f64 = f64::MAX;
 pub const DBL_EPSILON: f64 = f64::EPSILON;
+pub const DBL_MANT_DIG: i32 = f64::MANTISSA_DIGITS as i32;
 
 pub fn log(x: f64) -> f64 {
     x.ln()
@@ -1,11 +1,12 @@
 use crate::dpq::r_d__0;
 use crate::rmath::M_LN2;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 
 pub const ML_POSINF: f64 = f64::INFINITY;
 pub const ML_NEGINF: f64 = f64::NEG_INFINITY;
 
 pub fn ml_warn_return_nan() -> f64 {
-    println!("argument out of domain");
     ML_NAN
 }
 
@@ -17,8 +18,13 @@ pub const ML_NAN: f64 = f64::NAN;
 
 /// log(sqrt(pi))
 pub const M_LN_SQRT_PI: f64 = 0.572_364_942_924_700_1;
+/// log(2*pi)
+pub const M_LN_2PI: f64 = 1.837_877_066_409_345_6;
 /// log(sqrt(2*pi)) == log(2*pi)/2
 pub const M_LN_SQRT_2PI: f64 = 0.918_938_533_204_672_8;
+/// log(sqrt(pi/2))
+#[allow(non_upper_case_globals)]
+pub const M_LN_SQRT_PId2: f64 = 0.225_791_352_644_727_43;
 
 /// for IEEE, DBL_MIN_EXP is -1022 but "effective" is -1074
 pub const DBL_MIN_EXP: f64 = f64::MIN_EXP as f64;
@@ -45,9 +51,204 @@ pub fn r_forceint(x: f64) -> f64 {
     x.round()
 }
 
-pub fn r_d_nonint_check(x: f64, give_log: bool) -> f64 {
+/// Check that `x` is integer valued for discrete d*() functions.
+///
+/// This was originally the macro R_D_nonint_check.
+/// A non-integer `x` raises `MlWarningKind::NonInteger` for `function`.
+/// At the caller site, if the return value is not None, then return the
+/// result immediately.
+pub fn r_d_nonint_check(x: f64, give_log: bool, function: &'static str) -> Option<f64> {
     if r_nonint(x) {
-        println!("non-integer x = {}", x);
+        ml_warning(MlWarningKind::NonInteger, function);
+        return Some(r_d__0(give_log));
+    }
+    None
+}
+
+/// Whether `x` is negative or not integer valued.
+///
+/// This was originally the macro R_D_negInonint.
+pub fn r_d_neg_i_nonint(x: f64) -> bool {
+    x < 0.0 || r_nonint(x)
+}
+
+/// A stand-in for the C code's `LDOUBLE` (`long double`) accumulators.
+///
+/// The value is carried as an unevaluated sum `hi + lo` of two `f64`s:
+/// additions use Neumaier's compensated summation and products keep the
+/// rounding error of `hi` through a fused multiply-add.
+/// This is at least as precise as the x87 80-bit `long double`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LDouble {
+    hi: f64,
+    lo: f64,
+}
+
+impl LDouble {
+    pub fn new(x: f64) -> Self {
+        Self { hi: x, lo: 0.0 }
+    }
+
+    /// Rounds to the nearest `f64`, like the C code's `(double)` casts.
+    pub fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    /// The natural logarithm, rounded to `f64`.
+    pub fn ln(self) -> f64 {
+        if self.lo == 0.0 {
+            self.hi.ln()
+        } else {
+            self.hi.ln() + self.lo / self.hi
+        }
+    }
+
+    /// The exponential, like C's `expl`.
+    ///
+    /// With `x = k log(2) + r`, `exp(r / 512) - 1` is summed as a Taylor
+    /// series and squared up via `expm1(2 y) = expm1(y) (expm1(y) + 2)`.
+    pub fn exp(self) -> Self {
+        // log(2) to double-double precision
+        const LN2_HI: f64 = std::f64::consts::LN_2;
+        const LN2_LO: f64 = 2.319_046_813_846_299_6e-17;
+
+        if !(self.hi.abs() < 700.0) {
+            return Self::new(self.hi.exp());
+        }
+        let k = (self.hi / LN2_HI).round();
+        let mut r = self;
+        r -= LDouble::new(LN2_HI) * k;
+        r -= LN2_LO * k;
+        r *= 1.0 / 512.0;
+
+        let mut s = r;
+        let mut term = r;
+        for n in 2..10 {
+            term = term * r / f64::from(n);
+            s += term;
+        }
+        for _ in 0..9 {
+            let mut s2 = s;
+            s2 += 2.0;
+            s = s * s2;
+        }
+        s += 1.0;
+        s * 2f64.powi(k as i32)
+    }
+
+    /// Builds the normalized pair from `hi` and a small correction `lo`.
+    fn renormalize(hi: f64, lo: f64) -> Self {
+        let s = hi + lo;
+        if !s.is_finite() {
+            return Self::new(s);
+        }
+        Self {
+            hi: s,
+            lo: lo - (s - hi),
+        }
+    }
+
+    /// Neumaier's error-free sum `a + b`, returning the rounded sum and the
+    /// rounding error.
+    fn two_sum(a: f64, b: f64) -> (f64, f64) {
+        let s = a + b;
+        let err = if a.abs() >= b.abs() {
+            (a - s) + b
+        } else {
+            (b - s) + a
+        };
+        (s, err)
+    }
+}
+
+impl std::ops::AddAssign<f64> for LDouble {
+    fn add_assign(&mut self, rhs: f64) {
+        let (s, err) = Self::two_sum(self.hi, rhs);
+        *self = Self::renormalize(s, err + self.lo);
+    }
+}
+
+impl std::ops::SubAssign<f64> for LDouble {
+    fn sub_assign(&mut self, rhs: f64) {
+        *self += -rhs;
+    }
+}
+
+impl std::ops::AddAssign for LDouble {
+    fn add_assign(&mut self, rhs: Self) {
+        let (s, err) = Self::two_sum(self.hi, rhs.hi);
+        *self = Self::renormalize(s, err + self.lo + rhs.lo);
+    }
+}
+
+impl std::ops::SubAssign for LDouble {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self += -rhs;
+    }
+}
+
+impl std::ops::Neg for LDouble {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            hi: -self.hi,
+            lo: -self.lo,
+        }
+    }
+}
+
+impl std::ops::Mul<f64> for LDouble {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        let p = self.hi * rhs;
+        if !p.is_finite() || p == 0.0 {
+            return Self::new(p);
+        }
+        let err = self.hi.mul_add(rhs, -p);
+        Self::renormalize(p, err + self.lo * rhs)
+    }
+}
+
+impl std::ops::MulAssign<f64> for LDouble {
+    fn mul_assign(&mut self, rhs: f64) {
+        *self = *self * rhs;
+    }
+}
+
+impl std::ops::Mul for LDouble {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let p = self.hi * rhs.hi;
+        if !p.is_finite() || p == 0.0 {
+            return Self::new(p);
+        }
+        let err = self.hi.mul_add(rhs.hi, -p);
+        Self::renormalize(p, err + (self.hi * rhs.lo + self.lo * rhs.hi))
+    }
+}
+
+impl std::ops::Div for LDouble {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        let q = self.hi / rhs.hi;
+        if !q.is_finite() || q == 0.0 {
+            return Self::new(q);
+        }
+        // the remainder self - q * rhs, and from it the next digits of q
+        let mut r = self;
+        r -= rhs * q;
+        Self::renormalize(q, r.hi / rhs.hi)
+    }
+}
+
+impl std::ops::Div<f64> for LDouble {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        self / Self::new(rhs)
     }
-    r_d__0(give_log)
 }
@@ -3,10 +3,12 @@ use crate::dpq::r_dt_1;
 use crate::nmath::ml_warn_return_nan;
 use crate::rmath::M_LN2;
 use crate::toms708::bratio;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 
 /// Returns distribution function of the beta distribution.
 /// ( = The incomplete beta ratio I_x(p,q) ).
-fn pbeta_raw(x: f64, a: f64, b: f64, lower_tail: bool, log_p: bool) -> f64 {
+pub fn pbeta_raw(x: f64, a: f64, b: f64, lower_tail: bool, log_p: bool) -> f64 {
     if x >= 1.0 {
         return r_dt_1(lower_tail, log_p);
     }
@@ -41,10 +43,7 @@ fn pbeta_raw(x: f64, a: f64, b: f64, lower_tail: bool, log_p: bool) -> f64 {
     bratio(a, b, x, x1, &mut w, &mut w1, &mut ierr, log_p);
 
     if ierr != 0 && ierr != 11 && ierr != 14 {
-        println!(
-            "pbeta_raw({}, a={}, b={}, lower_tail={}, log_p={}) -> bratio() gave error code{}",
-            x, a, b, lower_tail, log_p, ierr
-        );
+        ml_warning(MlWarningKind::Precision, "pbeta_raw");
     }
     if lower_tail {
         w
@@ -0,0 +1,61 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+use crate::nmath::r_nonint;
+use crate::pbeta::pbeta;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2015 The R Core Team
+//  Copyright (C) 2004-2015 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the binomial distribution.
+///
+/// Uses the relation to the incomplete beta function,
+/// P[X <= x] = pbeta(p, x + 1, n - x, lower_tail = FALSE).
+pub fn pbinom(x: f64, n: f64, p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || n.is_nan() || p.is_nan() {
+        return x + n + p;
+    }
+    if !r_finite(n) || !r_finite(p) {
+        return ml_warn_return_nan();
+    }
+
+    if r_nonint(n) {
+        ml_warning(MlWarningKind::NonInteger, "pbinom");
+        return ml_warn_return_nan();
+    }
+    let n = r_forceint(n);
+    // PR#8560: n=0 is a valid value
+    if n < 0.0 || p < 0.0 || p > 1.0 {
+        return ml_warn_return_nan();
+    }
+
+    if x < 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    let x = (x + 1e-7).floor();
+    if n <= x {
+        return r_dt_1(lower_tail, log_p);
+    }
+    pbeta(p, x + 1.0, n - x, !lower_tail, log_p)
+}
@@ -0,0 +1,64 @@
+use crate::dpq::r_d_clog;
+use crate::dpq::r_d_val;
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rmath::M_PI;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2014 The R Core Team
+//  Copyright (C) 2004 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the Cauchy distribution.
+pub fn pcauchy(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    let mut x = (x - location) / scale;
+    if x.is_nan() {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(x) {
+        if x < 0.0 {
+            return r_dt_0(lower_tail, log_p);
+        } else {
+            return r_dt_1(lower_tail, log_p);
+        }
+    }
+    if !lower_tail {
+        x = -x;
+    }
+    // for large x, the standard formula suffers from cancellation.
+    // This is from Morten Welinder thanks to  Ian Smith's  atan(1/x) :
+    if x.abs() > 1.0 {
+        let y = (1.0 / x).atan() / M_PI;
+        if x > 0.0 {
+            r_d_clog(y, log_p)
+        } else {
+            r_d_val(-y, log_p)
+        }
+    } else {
+        r_d_val(0.5 + x.atan() / M_PI, log_p)
+    }
+}
@@ -0,0 +1,6 @@
+use crate::pgamma::pgamma;
+
+/// The distribution function of the chi-squared distribution.
+pub fn pchisq(x: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
+    pgamma(x, df / 2.0, 2.0, lower_tail, log_p)
+}
@@ -0,0 +1,49 @@
+use crate::dpq::r_d_exp;
+use crate::dpq::r_dt_0;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_log1_exp;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2015 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the exponential distribution with mean
+/// `scale`.
+pub fn pexp(x: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || scale.is_nan() {
+        return x + scale;
+    }
+    if scale < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if x <= 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    // same as weibull( shape = 1):
+    let x = -(x / scale);
+    if lower_tail {
+        if log_p {
+            r_log1_exp(x)
+        } else {
+            -x.exp_m1()
+        }
+    } else {
+        r_d_exp(x, log_p)
+    }
+}
@@ -0,0 +1,92 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_POSINF;
+use crate::pbeta::pbeta;
+use crate::pchisq::pchisq;
+use crate::rmath::M_LN2;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-8 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the F distribution.
+pub fn pf(x: f64, df1: f64, df2: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || df1.is_nan() || df2.is_nan() {
+        return x + df2 + df1;
+    }
+    if df1 <= 0.0 || df2 <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if x <= 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if x >= ML_POSINF {
+        return r_dt_1(lower_tail, log_p);
+    }
+
+    // move to pchisq for very large values - was 'df1 > 4e5' in 2.0.x,
+    // now only needed for df1 = Inf or df2 = Inf {since pbeta(0,*)=0} :
+    if df2 == ML_POSINF {
+        if df1 == ML_POSINF {
+            if x < 1.0 {
+                return r_dt_0(lower_tail, log_p);
+            }
+            if x == 1.0 {
+                return if log_p { -M_LN2 } else { 0.5 };
+            }
+            if x > 1.0 {
+                return r_dt_1(lower_tail, log_p);
+            }
+        }
+
+        return pchisq(x * df1, df1, lower_tail, log_p);
+    }
+
+    if df1 == ML_POSINF {
+        // was "fudge"	'df1 > 4e5' in 2.0.x
+        return pchisq(df2 / x, df2, !lower_tail, log_p);
+    }
+
+    // Avoid squeezing pbeta's first parameter against 1 :
+    let x = if df1 * x > df2 {
+        pbeta(
+            df2 / (df2 + df1 * x),
+            df2 / 2.0,
+            df1 / 2.0,
+            !lower_tail,
+            log_p,
+        )
+    } else {
+        pbeta(
+            df1 * x / (df2 + df1 * x),
+            df1 / 2.0,
+            df2 / 2.0,
+            lower_tail,
+            log_p,
+        )
+    };
+
+    if !x.is_nan() {
+        x
+    } else {
+        ML_NAN
+    }
+}
@@ -1,17 +1,22 @@
-use libm::lgamma;
-use std::ops::Neg;
-
 use crate::dnorm::dnorm4;
 use crate::dpois::dpois_raw;
+use crate::dpq::r_d__0;
+use crate::dpq::r_d__1;
+use crate::dpq::r_d_exp;
 use crate::dpq::r_dt_0;
 use crate::dpq::r_dt_1;
 use crate::lgamma::lgammafn;
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MIN;
+use crate::nmath::ml_warn_return_nan;
 use crate::nmath::r_log1_exp;
-use crate::nmath::ML_NAN;
+use crate::nmath::LDouble;
 use crate::nmath::ML_NEGINF;
 use crate::nmath::ML_POSINF;
 use crate::pnorm::pnorm5;
 use crate::rmath::M_LN2;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 
 /// Computes the distribution function for the gamma distribution
 /// with shape parameter alph and scale parameter scale.
@@ -30,13 +35,11 @@ use crate::rmath::M_LN2;
 /// 2005-6 Morten Welinder <terra@gnome.org>
 /// 2005-10 The R Foundation
 pub fn pgamma(x: f64, alph: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
-    // Handling special cases
     if x.is_nan() || alph.is_nan() || scale.is_nan() {
-        return ML_NAN;
+        return x + alph + scale;
     }
-
-    if alph <= 0.0 || scale <= 0.0 {
-        return ML_NAN; // Undefined for non-positive alpha or scale
+    if alph < 0.0 || scale <= 0.0 {
+        return ml_warn_return_nan();
     }
 
     let x = x / scale;
@@ -47,12 +50,12 @@ pub fn pgamma(x: f64, alph: f64, scale: f64, lower_tail: bool, log_p: bool) -> f
     }
 
     if alph == 0.0 {
-        // Limit case; useful e.g., in pnchisq
+        // limit case; useful e.g. in pnchisq()
         return if x <= 0.0 {
             r_dt_0(lower_tail, log_p)
         } else {
             r_dt_1(lower_tail, log_p)
-        };
+        }; // <= assert  pgamma(0,0) ==> 0
     }
 
     pgamma_raw(x, alph, lower_tail, log_p)
@@ -61,7 +64,7 @@ pub fn pgamma(x: f64, alph: f64, scale: f64, lower_tail: bool, log_p: bool) -> f
 const SQR: fn(f64) -> f64 = |x| x * x;
 
 /// If |x| > |k| * M_cutoff,  then  log\[ exp(-x) * k^x \] =~= -x
-const M_CUTOFF: f64 = M_LN2 * f64::MAX / f64::EPSILON; // 3.196577e18
+const M_CUTOFF: f64 = M_LN2 * f64::MAX_EXP as f64 / f64::EPSILON; // 3.196577e18
 
 /// Continued fraction for calculation of
 /// 1/i + x/(i+d) + x^2/(i+2*d) + x^3/(i+3*d) + ... = sum_{k=0}^Inf x^k/(i+k*d)
@@ -136,7 +139,7 @@ pub fn log1pmx(x: f64) -> f64 {
 /// Compute log(gamma(a+1)) accurately also for small a (0 < a < 0.5).
 pub fn lgamma1p(a: f64) -> f64 {
     if a.abs() >= 0.5 {
-        lgamma(a + 1.0)
+        lgammafn(a + 1.0)
     } else {
         const EULERS_CONST: f64 = 0.577_215_664_901_532_9;
         const COEFFS: [f64; 40] = [
@@ -208,9 +211,8 @@ pub fn logspace_add(logx: f64, logy: f64) -> f64 {
 
 /// Compute the log of a difference from logs of terms, i.e., log(exp(logx) - exp(logy))
 /// without causing overflows and without throwing away large handfuls of accuracy.
-#[allow(dead_code)]
-fn logspace_sub(logx: f64, logy: f64) -> f64 {
-    logx + (logy - logx).exp().ln_1p().neg()
+pub fn logspace_sub(logx: f64, logy: f64) -> f64 {
+    logx + r_log1_exp(logy - logx)
 }
 
 /// Compute the log of a sum from logs of terms, i.e.,
@@ -224,7 +226,10 @@ fn logspace_sum(logx: &[f64]) -> f64 {
         _ => {
             // Find the maximum log value to scale other values
             let mx = logx.iter().cloned().fold(ML_NEGINF, f64::max);
-            let sum: f64 = logx.iter().map(|&x| (x - mx).exp()).sum();
+            let mut sum = LDouble::new(0.0);
+            for &x in logx {
+                sum += LDouble::new(x - mx).exp();
+            }
             mx + sum.ln()
         }
     }
@@ -233,22 +238,13 @@ fn logspace_sum(logx: &[f64]) -> f64 {
 /// dpois_wrap (x_plus_1, lambda) := dpois(x_plus_1 - 1, lambda)
 /// where dpois(k, L) := exp(-L) L^k / gamma(k+1) {the usual Poisson probabilities}
 /// and dpois*(.., give_log = true) := log(dpois*(..))
-fn dpois_wrap(x_plus_1: f64, lambda: f64, give_log: bool) -> f64 {
+pub fn dpois_wrap(x_plus_1: f64, lambda: f64, give_log: bool) -> f64 {
     if !lambda.is_finite() {
-        if give_log {
-            ML_NEGINF
-        } else {
-            0.0
-        }
+        r_d__0(give_log)
     } else if x_plus_1 > 1.0 {
         dpois_raw(x_plus_1 - 1.0, lambda, give_log)
     } else if lambda > (x_plus_1 - 1.0).abs() * M_CUTOFF {
-        let res = -lambda - lgammafn(x_plus_1);
-        if give_log {
-            res
-        } else {
-            res.exp()
-        }
+        r_d_exp(-lambda - lgammafn(x_plus_1), give_log)
     } else {
         let d = dpois_raw(x_plus_1, lambda, give_log);
         if give_log {
@@ -259,29 +255,29 @@ fn dpois_wrap(x_plus_1: f64, lambda: f64, give_log: bool) -> f64 {
     }
 }
 
-/// Compute the lower tail of the gamma distribution for small x values.
+/// Abramowitz and Stegun 6.5.29 \[right\]
 fn pgamma_smallx(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
     let mut sum = 0.0;
     let mut c = alph;
     let mut n = 0.0;
-    let mut term;
 
-    // Loop to sum up terms until convergence
+    // Relative to 6.5.29 all terms have been multiplied by alph
+    // and the first, thus being 1, is omitted.
     loop {
         n += 1.0;
         c *= -x / n;
-        term = c / (alph + n);
+        let term = c / (alph + n);
         sum += term;
 
-        if term.abs() <= f64::EPSILON * sum.abs() {
+        if !(term.abs() > DBL_EPSILON * sum.abs()) {
             break;
         }
     }
 
     if lower_tail {
-        let f1 = if log_p { (1.0 + sum).ln() } else { 1.0 + sum };
+        let f1 = if log_p { sum.ln_1p() } else { 1.0 + sum };
         let f2 = if alph > 1.0 {
-            let d = dpois_wrap(alph, x, log_p);
+            let d = dpois_raw(alph, x, log_p);
             if log_p {
                 d + x
             } else {
@@ -300,7 +296,7 @@ fn pgamma_smallx(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
     } else {
         let lf2 = alph * x.ln() - lgamma1p(alph);
         if log_p {
-            (1.0 + sum).ln_1p() + lf2
+            r_log1_exp(sum.ln_1p() + lf2)
         } else {
             let f1m1 = sum;
             let f2m1 = lf2.exp_m1();
@@ -309,18 +305,23 @@ fn pgamma_smallx(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
     }
 }
 
-/// Compute the upper tail of the Poisson distribution using a series expansion.
-fn pd_upper_series(x: f64, y: f64, log_p: bool) -> f64 {
+fn pd_upper_series(x: f64, mut y: f64, log_p: bool) -> f64 {
     let mut term = x / y;
     let mut sum = term;
-    let mut y = y;
 
-    while term > sum * f64::EPSILON {
+    loop {
         y += 1.0;
         term *= x / y;
         sum += term;
+        if !(term > sum * DBL_EPSILON) {
+            break;
+        }
     }
 
+    // sum =  \sum_{n=1}^ oo  x^n     / (y*(y+1)*...*(y+n-1))
+    //     =  \sum_{n=0}^ oo  x^(n+1) / (y*(y+1)*...*(y+n))
+    //     =  x/y * (1 + \sum_{n=1}^oo  x^n / ((y+1)*...*(y+n)))
+    //     ~  x/y +  o(x/y)   {which happens when alph -> Inf}
     if log_p {
         sum.ln()
     } else {
@@ -328,28 +329,9 @@ fn pd_upper_series(x: f64, y: f64, log_p: bool) -> f64 {
     }
 }
 
-/// Compute the lower tail of the Poisson distribution using a series expansion.
-fn pd_lower_series(lambda: f64, y: f64) -> f64 {
-    let mut term = 1.0;
-    let mut sum = 0.0;
-    let mut y = y;
-
-    while y >= 1.0 && term > sum * f64::EPSILON {
-        term *= y / lambda;
-        sum += term;
-        y -= 1.0;
-    }
-
-    if y != y.floor() {
-        // For non-integer y, add another term to the sum
-        let f = pd_lower_cf(y, lambda + 1.0 - y);
-        sum += term * f;
-    }
-
-    sum
-}
-
-/// Continued fraction for calculation of scaled lower-tail F distribution.
+/// Continued fraction for calculation of
+/// scaled upper-tail F_{gamma}
+/// ~=  (y / d) * \[1 +  (1-y)/d +  O( ((1-y)/d)^2 ) \]
 fn pd_lower_cf(y: f64, d: f64) -> f64 {
     // Scalefactor:= (2^32)^8 = 2^256 = 1.157921e+77
     #![allow(non_snake_case)]
@@ -357,63 +339,148 @@ fn pd_lower_cf(y: f64, d: f64) -> f64 {
     const MAX_IT: u32 = 200000;
 
     let mut f: f64 = 0.0;
-    let mut i = 0;
-    let mut of = -1.0; // Initialization far away
+
+    if y == 0.0 {
+        return 0.0;
+    }
+
+    let mut f0 = y / d;
+    // Needed, e.g. for  pgamma(10^c(100,295), shape= 1.1, log=TRUE):
+    if (y - 1.0).abs() < d.abs() * DBL_EPSILON {
+        // includes y < d = Inf
+        return f0;
+    }
+
+    if f0 > 1.0 {
+        f0 = 1.0;
+    }
     let mut c2 = y;
-    let mut c4 = d;
+    let mut c4 = d; // original (y,d), *not* potentially scaled ones!
+
     let mut a1 = 0.0;
     let mut b1 = 1.0;
     let mut a2 = y;
     let mut b2 = d;
 
-    while i < MAX_IT {
-        i += 1;
+    while b2 > SCALEFACTOR {
+        a1 /= SCALEFACTOR;
+        b1 /= SCALEFACTOR;
+        a2 /= SCALEFACTOR;
+        b2 /= SCALEFACTOR;
+    }
+
+    let mut i = 0.0;
+    let mut of = -1.0; // far away
+    while i < MAX_IT as f64 {
+        i += 1.0;
         c2 -= 1.0;
-        let c3 = i as f64 * c2;
+        let c3 = i * c2;
         c4 += 2.0;
+        // c2 = y - i,  c3 = i(y - i),  c4 = d + 2i,  for i odd
         a1 = c4 * a2 + c3 * a1;
         b1 = c4 * b2 + c3 * b1;
 
-        i += 1;
+        i += 1.0;
         c2 -= 1.0;
-        let c3 = i as f64 * c2;
+        let c3 = i * c2;
         c4 += 2.0;
+        // c2 = y - i,  c3 = i(y - i),  c4 = d + 2i,  for i even
         a2 = c4 * a1 + c3 * a2;
         b2 = c4 * b1 + c3 * b2;
 
-        if b2.abs() > SCALEFACTOR {
+        if b2 > SCALEFACTOR {
             a1 /= SCALEFACTOR;
             b1 /= SCALEFACTOR;
             a2 /= SCALEFACTOR;
             b2 /= SCALEFACTOR;
-        } else if b2.abs() < 1.0 / SCALEFACTOR {
-            a1 *= SCALEFACTOR;
-            b1 *= SCALEFACTOR;
-            a2 *= SCALEFACTOR;
-            b2 *= SCALEFACTOR;
         }
 
         if b2 != 0.0 {
             f = a2 / b2;
-            if (f - of).abs() <= f64::EPSILON * f.abs() {
+            // convergence check: relative; "absolute" for very small f :
+            if (f - of).abs() <= DBL_EPSILON * f0.max(f.abs()) {
                 return f;
             }
             of = f;
         }
     }
 
-    println!(
-        "Non-convergence in pd_lower_cf after {} iterations.",
-        MAX_IT
-    );
-    f // Returning the last computed value of `f` as a fallback.
+    ml_warning(MlWarningKind::NoConv, "pgamma");
+    f // should not happen ...
+}
+
+fn pd_lower_series(lambda: f64, mut y: f64) -> f64 {
+    let mut term = 1.0;
+    let mut sum = 0.0;
+
+    while y >= 1.0 && term > sum * DBL_EPSILON {
+        term *= y / lambda;
+        sum += term;
+        y -= 1.0;
+    }
+    // sum =  \sum_{n=0}^ oo  y*(y-1)*...*(y - n) / lambda^(n+1)
+    //     =  y/lambda * (1 + \sum_{n=1}^Inf  (y-1)*...*(y-n) / lambda^n)
+    //     ~  y/lambda + o(y/lambda)
+
+    if y != y.floor() {
+        // The series does not converge as the terms start getting
+        // bigger (besides flipping sign) for y < -lambda.
+        let f = pd_lower_cf(y, lambda + 1.0 - y);
+        sum += term * f;
+    }
+
+    sum
+}
+
+/// Compute the following ratio with higher accuracy that would be had
+/// from doing it directly.
+///
+/// ```text
+///          dnorm (x, 0, 1, FALSE)
+///    ----------------------------------
+///    pnorm (x, 0, 1, lower_tail, FALSE)
+/// ```
+///
+/// Abramowitz & Stegun 26.2.12
+fn dpnorm(mut x: f64, mut lower_tail: bool, lp: f64) -> f64 {
+    // So as not to repeat a pnorm call, we expect
+    //
+    //     lp == pnorm (x, 0, 1, lower_tail, TRUE)
+    //
+    // but use it only in the non-critical case where either x is small
+    // or p==exp(lp) is close to 1.
+    if x < 0.0 {
+        x = -x;
+        lower_tail = !lower_tail;
+    }
+
+    if x > 10.0 && !lower_tail {
+        let mut term = 1.0 / x;
+        let mut sum = term;
+        let x2 = x * x;
+        let mut i = 1.0;
+
+        loop {
+            term *= -i / x2;
+            sum += term;
+            i += 2.0;
+            if !(term.abs() > DBL_EPSILON * sum) {
+                break;
+            }
+        }
+
+        1.0 / sum
+    } else {
+        let d = dnorm4(x, 0.0, 1.0, false);
+        d / lp.exp()
+    }
 }
 
 /// Asymptotic expansion to calculate the probability that Poisson variate
 /// has value <= x.
 fn ppois_asymp(x: f64, lambda: f64, lower_tail: bool, log_p: bool) -> f64 {
     const COEFS_A: [f64; 8] = [
-        -1e99, // placeholder for 1-indexing
+        -1e99, // placeholder used for 1-indexing
         2.0 / 3.0,
         -4.0 / 135.0,
         8.0 / 2835.0,
@@ -435,94 +502,90 @@ fn ppois_asymp(x: f64, lambda: f64, lower_tail: bool, log_p: bool) -> f64 {
     ];
 
     let dfm = lambda - x;
-    /* If lambda is large, the distribution is highly concentrated
-       about lambda.  So representation error in x or lambda can lead
-       to arbitrarily large values of pt_ and hence divergence of the
-       coefficients of this approximation.
-    */
-    let pt_ = -(-dfm / x).ln_1p();
-    let s2pt = (2.0 * x * pt_).sqrt();
-    let s2pt = if dfm < 0.0 { -s2pt } else { s2pt };
-
-    let elfb = (0..8).map(|i| COEFS_B[i] / x.powi(i as i32)).sum::<f64>();
-    let elfb_term = x * elfb;
-
-    let res12 = (0..8)
-        .fold((0.0, 1.0 / x.sqrt(), s2pt / x), |(acc, term1, term2), i| {
-            let res1_ig = term1 / x + COEFS_A[i] * term1.powi(i as i32 + 1);
-            let res2_ig = term2 / x + COEFS_B[i] * term2.powi(i as i32 + 1);
-            (acc + res1_ig + res2_ig, res1_ig, res2_ig)
-        })
-        .0;
-
-    let f = res12 / elfb_term;
+    // If lambda is large, the distribution is highly concentrated
+    // about lambda.  So representation error in x or lambda can lead
+    // to arbitrarily large values of pt_ and hence divergence of the
+    // coefficients of this approximation.
+    let pt_ = -log1pmx(dfm / x);
+    let mut s2pt = (2.0 * x * pt_).sqrt();
+    if dfm < 0.0 {
+        s2pt = -s2pt;
+    }
+
+    let mut res12 = 0.0;
+    let mut res1_term = x.sqrt();
+    let mut res1_ig = res1_term;
+    let mut res2_term = s2pt;
+    let mut res2_ig = res2_term;
+    for i in 1..8 {
+        res12 += res1_ig * COEFS_A[i];
+        res12 += res2_ig * COEFS_B[i];
+        res1_term *= pt_ / i as f64;
+        res2_term *= 2.0 * pt_ / (2 * i + 1) as f64;
+        res1_ig = res1_ig / x + res1_term;
+        res2_ig = res2_ig / x + res2_term;
+    }
+
+    let mut elfb = x;
+    let mut elfb_term = 1.0;
+    for coef_b in COEFS_B.iter().skip(1) {
+        elfb += elfb_term * coef_b;
+        elfb_term /= x;
+    }
+    if !lower_tail {
+        elfb = -elfb;
+    }
+
+    let f = res12 / elfb;
+
     let np = pnorm5(s2pt, 0.0, 1.0, !lower_tail, log_p);
 
     if log_p {
-        np + (1.0 + f * dnorm4(s2pt, 0.0, 1.0, log_p).exp()).ln()
+        let n_d_over_p = dpnorm(s2pt, !lower_tail, np);
+        np + (f * n_d_over_p).ln_1p()
     } else {
-        np + f * dnorm4(s2pt, 0.0, 1.0, log_p)
+        let nd = dnorm4(s2pt, 0.0, 1.0, log_p);
+        np + f * nd
     }
 }
 
-/// Compute the lower tail probability of the gamma distribution.
-fn pgamma_raw(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
-    // Check boundaries
-    if x.is_nan() || alph.is_nan() || x < 0.0 || alph <= 0.0 {
-        return ML_NEGINF;
-    }
-
-    if x == 0.0 {
-        return if lower_tail {
-            if log_p {
-                ML_NEGINF
-            } else {
-                0.0
-            }
-        } else if log_p {
-            0.0
-        } else {
-            1.0
-        };
+/// Compute the gamma distribution function for `(x, alph)` which are not
+/// NaN and `alph > 0`.
+pub fn pgamma_raw(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
+    // R_P_bounds_01(x, 0., ML_POSINF);
+    if x <= 0.0 {
+        return r_dt_0(lower_tail, log_p);
     }
-
-    if alph == ML_POSINF {
-        return if lower_tail {
-            if log_p {
-                0.0
-            } else {
-                1.0
-            }
-        } else if log_p {
-            ML_NEGINF
-        } else {
-            0.0
-        };
+    if x >= ML_POSINF {
+        return r_dt_1(lower_tail, log_p);
     }
 
-    // Main computation
-    if x < 1.0 {
+    let res = if x < 1.0 {
         pgamma_smallx(x, alph, lower_tail, log_p)
     } else if x <= alph - 1.0 && x < 0.8 * (alph + 50.0) {
-        let sum = pd_upper_series(x, alph, log_p);
+        // incl. large alph compared to x
+        let sum = pd_upper_series(x, alph, log_p); // = x/alph + o(x/alph)
         let d = dpois_wrap(alph, x, log_p);
         if !lower_tail {
             if log_p {
                 r_log1_exp(d + sum)
             } else {
-                1.0 - d * sum.exp()
+                1.0 - d * sum
             }
         } else if log_p {
             sum + d
         } else {
-            sum.exp() * d
+            sum * d
         }
     } else if alph - 1.0 < x && alph < 0.8 * (x + 50.0) {
+        // incl. large x compared to alph
+        let d = dpois_wrap(alph, x, log_p);
         let sum = if alph < 1.0 {
-            if x * f64::EPSILON > 1.0 - alph {
-                1.0 // To avoid division by zero
+            if x * DBL_EPSILON > 1.0 - alph {
+                r_d__1(log_p)
             } else {
                 let f = pd_lower_cf(alph, x - (alph - 1.0)) * x / alph;
+                // = [alph/(x - alph+1) + o(alph/(x-alph+1))] * x/alph = 1 + o(1)
                 if log_p {
                     f.ln()
                 } else {
@@ -530,7 +593,7 @@ fn pgamma_raw(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
                 }
             }
         } else {
-            let sum = pd_lower_series(x, alph - 1.0);
+            let sum = pd_lower_series(x, alph - 1.0); // = (alph-1)/x + o((alph-1)/x)
             if log_p {
                 sum.ln_1p()
             } else {
@@ -538,7 +601,6 @@ fn pgamma_raw(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
             }
         };
 
-        let d = dpois_wrap(alph, x, log_p);
         if !lower_tail {
             if log_p {
                 sum + d
@@ -551,7 +613,17 @@ fn pgamma_raw(x: f64, alph: f64, lower_tail: bool, log_p: bool) -> f64 {
             1.0 - d * sum
         }
     } else {
-        // x >= 1 and x fairly near alph
+        // x >= 1 and x fairly near alph.
         ppois_asymp(alph - 1.0, x, !lower_tail, log_p)
+    };
+
+    // We lose a fair amount of accuracy to underflow in the cases
+    // where the final result is very close to DBL_MIN.  In those
+    // cases, simply redo via log space.
+    if !log_p && res < DBL_MIN / DBL_EPSILON {
+        // with(.Machine, double.xmin / double.eps) #|-> 1.002084e-292
+        pgamma_raw(x, alph, lower_tail, true).exp()
+    } else {
+        res
     }
 }
@@ -0,0 +1,57 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_clog;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2006 The R Core Team
+//  Copyright (C) 2004	    The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the geometric distribution.
+pub fn pgeom(x: f64, p: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || p.is_nan() {
+        return x + p;
+    }
+    if p <= 0.0 || p > 1.0 {
+        return ml_warn_return_nan();
+    }
+
+    if x < 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if !r_finite(x) {
+        return r_dt_1(lower_tail, log_p);
+    }
+    let x = (x + 1e-7).floor();
+
+    if p == 1.0 {
+        // we cannot assume IEEE
+        let x: f64 = if lower_tail { 1.0 } else { 0.0 };
+        return if log_p { x.ln() } else { x };
+    }
+    let x = (-p).ln_1p() * (x + 1.0);
+    if log_p {
+        r_dt_clog(x, lower_tail, log_p)
+    } else if lower_tail {
+        -x.exp_m1()
+    } else {
+        x.exp()
+    }
+}
@@ -0,0 +1,122 @@
+use crate::dhyper::dhyper;
+use crate::dpq::r_d_lval;
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_log;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+use crate::nmath::LDouble;
+use crate::nmath::ML_NEGINF;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1999-2020  The R Core Team
+//  Copyright (C) 1998       Ross Ihaka
+//  Copyright (C) 2004	     Morten Welinder
+//  Copyright (C) 2004	     The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Calculate
+///
+/// ```text
+///          phyper (x, NR, NB, n, TRUE, FALSE)
+/// [log]  ----------------------------------
+///           dhyper (x, NR, NB, n, FALSE)
+/// ```
+///
+/// without actually calling phyper.  This assumes that
+///
+/// ```text
+/// x * (NR + NB) <= n * NR
+/// ```
+///
+/// `sum` and `term` are `LDouble`s, as in the C code, since the sum can
+/// run over up to `x` terms.
+fn pdhyper(x: f64, nr: f64, nb: f64, n: f64, log_p: bool) -> f64 {
+    let mut x = x;
+    let mut sum = LDouble::new(0.0);
+    let mut term = LDouble::new(1.0);
+
+    while x > 0.0 && term.to_f64() >= DBL_EPSILON * sum.to_f64() {
+        term *= x * (nb - n + x) / (n + 1.0 - x) / (nr + 1.0 - x);
+        sum += term;
+        x -= 1.0;
+    }
+
+    let ss = sum.to_f64();
+    if log_p {
+        ss.ln_1p()
+    } else {
+        1.0 + ss
+    }
+}
+
+/// The distribution function of the hypergeometric distribution.
+///
+/// Sample of `n` balls from `nr` red and `nb` black ones; `x` are red.
+///
+/// The implementation is based on a posting by Morten Welinder to R-bugs,
+/// "phyper accuracy and efficiency (PR#6772)", which avoids the serious
+/// cancellation of the old code for small tails, e.g.
+/// `phyper(59, 150, 150, 60, FALSE, FALSE)` now gives `dhyper(0, 150, 150,
+/// 60, FALSE)`, 5.111204798e-22.
+pub fn phyper(x: f64, nr: f64, nb: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || nr.is_nan() || nb.is_nan() || n.is_nan() {
+        return x + nr + nb + n;
+    }
+
+    let mut x = (x + 1e-7).floor();
+    let mut nr = r_forceint(nr);
+    let mut nb = r_forceint(nb);
+    let n = r_forceint(n);
+    let mut lower_tail = lower_tail;
+
+    if nr < 0.0 || nb < 0.0 || !r_finite(nr + nb) || n < 0.0 || n > nr + nb {
+        return ml_warn_return_nan();
+    }
+
+    if x * (nr + nb) > n * nr {
+        // Swap tails.
+        std::mem::swap(&mut nr, &mut nb);
+        x = n - x - 1.0;
+        lower_tail = !lower_tail;
+    }
+
+    // support of dhyper() as a function of its parameters
+    // R:  .suppHyper <- function(m,n,k) max(0, k-n) : min(k, m)
+    // --  where R's (m,n, k) == (NR,NB, n)  here
+    if x < 0.0 || x < n - nb {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if x >= nr || x >= n {
+        return r_dt_1(lower_tail, log_p);
+    }
+
+    let d = dhyper(x, nr, nb, n, log_p);
+    // dhyper(.., log_p=FALSE) > 0 mathematically, but not always numerically :
+    if (!log_p && d == 0.0) || (log_p && d == ML_NEGINF) {
+        return r_dt_0(lower_tail, log_p);
+    }
+    let pd = pdhyper(x, nr, nb, n, log_p);
+
+    if log_p {
+        r_dt_log(d + pd, lower_tail, true)
+    } else {
+        r_d_lval(d * pd, lower_tail)
+    }
+}
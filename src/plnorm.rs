@@ -0,0 +1,37 @@
+use crate::dpq::r_dt_0;
+use crate::nmath::ml_warn_return_nan;
+use crate::pnorm::pnorm5;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-8 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the lognormal distribution.
+pub fn plnorm(x: f64, meanlog: f64, sdlog: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || meanlog.is_nan() || sdlog.is_nan() {
+        return x + meanlog + sdlog;
+    }
+    if sdlog < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if x > 0.0 {
+        return pnorm5(x.ln(), meanlog, sdlog, lower_tail, log_p);
+    }
+    r_dt_0(lower_tail, log_p)
+}
@@ -0,0 +1,76 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_log1_exp;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 2000--2020	The R Core Team
+//  Copyright (C) 1995, 1996	Robert Gentleman and Ross Ihaka
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Compute `log(1 + exp(x))` without overflow (and fast for x > 18).
+///
+/// For the two cutoffs, consider in R
+/// ```text
+/// curve(log1p(exp(x)) - x,       33.1, 33.5, n=2^10)
+/// curve(x+exp(-x) - log1p(exp(x)), 15, 25,   n=2^11)
+/// ```
+pub fn log1pexp(x: f64) -> f64 {
+    if x <= 18. {
+        return x.exp().ln_1p();
+    }
+    if x > 33.3 {
+        return x;
+    }
+    // else: 18.0 < x <= 33.3 :
+    x + (-x).exp()
+}
+
+/// Compute `log(1 - exp(-x))` accurately for `x > 0`.
+pub fn log1mexp(x: f64) -> f64 {
+    r_log1_exp(-x)
+}
+
+/// The distribution function of the logistic distribution.
+pub fn plogis(x: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || location.is_nan() || scale.is_nan() {
+        return x + location + scale;
+    }
+    if scale <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    let x = (x - location) / scale;
+    if x.is_nan() {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(x) {
+        if x > 0.0 {
+            return r_dt_1(lower_tail, log_p);
+        }
+        // x < 0
+        return r_dt_0(lower_tail, log_p);
+    }
+
+    if log_p {
+        // log(1 / (1 + exp( +- x ))) = -log(1 + exp( +- x))
+        -log1pexp(if lower_tail { -x } else { x })
+    } else {
+        1.0 / (1.0 + (if lower_tail { -x } else { x }).exp())
+    }
+}
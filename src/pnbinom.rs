@@ -0,0 +1,123 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::pbeta::pbeta;
+use crate::ppois::ppois;
+use crate::toms708::bratio;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2016 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the negative binomial distribution.
+pub fn pnbinom(x: f64, size: f64, prob: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || size.is_nan() || prob.is_nan() {
+        return x + size + prob;
+    }
+    if !r_finite(size) || !r_finite(prob) {
+        return ml_warn_return_nan();
+    }
+    if size < 0.0 || prob <= 0.0 || prob > 1.0 {
+        return ml_warn_return_nan();
+    }
+
+    // limiting case: point mass at zero
+    if size == 0.0 {
+        return if x >= 0.0 {
+            r_dt_1(lower_tail, log_p)
+        } else {
+            r_dt_0(lower_tail, log_p)
+        };
+    }
+
+    if x < 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if !r_finite(x) {
+        return r_dt_1(lower_tail, log_p);
+    }
+    let x = (x + 1e-7).floor();
+    pbeta(prob, size, x + 1.0, lower_tail, log_p)
+}
+
+/// The distribution function of the negative binomial distribution,
+/// parametrized by the mean `mu`.
+pub fn pnbinom_mu(x: f64, size: f64, mu: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || size.is_nan() || mu.is_nan() {
+        return x + size + mu;
+    }
+    if !r_finite(mu) {
+        return ml_warn_return_nan();
+    }
+    if size < 0.0 || mu < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    // limiting case: point mass at zero
+    if size == 0.0 {
+        return if x >= 0.0 {
+            r_dt_1(lower_tail, log_p)
+        } else {
+            r_dt_0(lower_tail, log_p)
+        };
+    }
+
+    if x < 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if !r_finite(x) {
+        return r_dt_1(lower_tail, log_p);
+    }
+    if !r_finite(size) {
+        // limit case: Poisson
+        return ppois(x, mu, lower_tail, log_p);
+    }
+
+    let x = (x + 1e-7).floor();
+    // return
+    // pbeta(pr, size, x + 1, lower_tail, log_p);  pr = size/(size + mu), 1-pr = mu/(size+mu)
+    //
+    //= pbeta_raw(pr, size, x + 1, lower_tail, log_p)
+    //            x.  pin   qin
+    //=  bratio (pin,  qin, x., 1-x., &w, &wc, &ierr, log_p),  and return w or wc ..
+    //=  bratio (size, x+1, pr, 1-pr, &w, &wc, &ierr, log_p)
+    let mut ierr = 0;
+    let mut w = 0.0;
+    let mut wc = 0.0;
+    bratio(
+        size,
+        x + 1.0,
+        size / (size + mu),
+        mu / (size + mu),
+        &mut w,
+        &mut wc,
+        &mut ierr,
+        log_p,
+    );
+    if ierr != 0 {
+        ml_warning(MlWarningKind::Precision, "pnbinom_mu");
+    }
+    if lower_tail {
+        w
+    } else {
+        wc
+    }
+}
@@ -0,0 +1,315 @@
+use crate::dpq::r_d__1;
+use crate::dpq::r_d_exp;
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_val;
+use crate::fmin2::fmin2;
+use crate::lgamma::lgammafn;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_log1_exp;
+use crate::nmath::LDouble;
+use crate::nmath::DBL_MIN_EXP;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+use crate::nmath::M_LN_SQRT_2PI;
+use crate::pchisq::pchisq;
+use crate::pgamma::logspace_add;
+use crate::rmath::M_LN10;
+use crate::rmath::M_LN2;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Algorithm AS 275 Appl.Statist. (1992), vol.41, no.2
+//  original  (C) 1992	     Royal Statistical Society
+//
+//  Other parts
+//  Copyright (C) 2000-2019  The R Core Team
+//  Copyright (C) 2003-2015  The R Foundation
+//
+
+/// = -708.3964 for IEEE double precision
+const DBL_MIN_EXP_LN: f64 = M_LN2 * DBL_MIN_EXP;
+
+/// Computes the noncentral chi-squared distribution function with
+/// positive real degrees of freedom df and nonnegative noncentrality
+/// parameter ncp.
+///
+/// ## REFERENCES
+///
+/// Ding, C. G. (1992)
+/// Algorithm AS275: Computing the non-central chi-squared
+/// distribution function. Appl.Statist., 41, 478-482.
+pub fn pnchisq(x: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || df.is_nan() || ncp.is_nan() {
+        return x + df + ncp;
+    }
+    if !r_finite(df) || !r_finite(ncp) {
+        return ml_warn_return_nan();
+    }
+
+    if df < 0.0 || ncp < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    let mut ans = pnchisq_raw(
+        x,
+        df,
+        ncp,
+        1e-12,
+        8.0 * DBL_EPSILON,
+        1000000,
+        lower_tail,
+        log_p,
+    );
+
+    if x <= 0.0 || x == ML_POSINF {
+        return ans; // because it's perfect
+    }
+
+    if ncp >= 80.0 {
+        if lower_tail {
+            ans = fmin2(ans, r_d__1(log_p)); // e.g., pchisq(555, 1.01, ncp = 80)
+        } else {
+            // !lower_tail
+            // since we computed the other tail cancellation is likely
+            // FIXME: There are cases where  ans == 0. if(!log_p) is perfect
+            if ans < (if log_p { -10.0 * M_LN10 } else { 1e-10 }) {
+                ml_warning(MlWarningKind::Precision, "pnchisq");
+            }
+            if !log_p && ans < 0.0 {
+                ans = 0.0; // Precaution PR#7099
+            }
+        }
+    }
+    // MM: the following "hack" from c51179 (<--> PR#14216, by Jerry Lewis)
+    // -- is "kind of ok" ... but potentially suboptimal: we do  log1p(- p(*, <other tail>, log=FALSE)),
+    //    but that  p(*, log=FALSE) may already be an exp(.) or even expm1(..)
+    //   <---> "in principle"  this check should happen there, not here
+    if !log_p || ans < -1e-8 {
+        ans
+    } else {
+        // log_p (==> ans <= 0) &&  -1e-8 <= ans <= 0
+        // prob. = exp(ans) is near one: we can do better using the other tail
+        let ans = pnchisq_raw(
+            x,
+            df,
+            ncp,
+            1e-12,
+            8.0 * DBL_EPSILON,
+            1000000,
+            !lower_tail,
+            false,
+        );
+        (-ans).ln_1p()
+    }
+}
+
+/// The non-central chi-squared distribution function, with the
+/// absolute and relative error bounds `errmax` and `reltol` and the
+/// iteration limit `itrmax` of the series expansion.
+///
+/// This is principally for use from `qnchisq`.
+///
+/// The Poisson weights and the partial sums are `LDouble`s, as in the C
+/// code; the series for `ncp >= 80` runs up to `itrmax` terms.
+#[allow(clippy::too_many_arguments)]
+pub fn pnchisq_raw(
+    x: f64,
+    f: f64,
+    theta: f64,
+    errmax: f64,
+    reltol: f64,
+    itrmax: i32,
+    lower_tail: bool,
+    log_p: bool,
+) -> f64 {
+    if x <= 0.0 {
+        if x == 0.0 && f == 0.0 {
+            // chi^2_0(.) has point mass at zero
+            let minus_lambda = -0.5 * theta;
+            return if lower_tail {
+                r_d_exp(minus_lambda, log_p)
+            } else if log_p {
+                r_log1_exp(minus_lambda)
+            } else {
+                -minus_lambda.exp_m1()
+            };
+        }
+        // x < 0  or {x==0, f > 0}
+        return r_dt_0(lower_tail, log_p);
+    }
+    if !r_finite(x) {
+        return r_dt_1(lower_tail, log_p);
+    }
+
+    if theta < 80.0 {
+        // use 110 for Inf, as ppois(110, 80/2, lower.tail=FALSE) is 2e-20
+
+        // Have  pgamma(x,s) < x^s / Gamma(s+1) (< and ~= for small x)
+        // ==> pchisq(x, f) = pgamma(x, f/2, 2) = pgamma(x/2, f/2)
+        //                  <  (x/2)^(f/2) / Gamma(f/2+1) < eps
+        // <==>  f/2 * log(x/2) - log(Gamma(f/2+1)) < log(eps) ( ~= -708.3964 )
+        // <==>        log(x/2) < 2/f*(log(Gamma(f/2+1)) + log(eps))
+        // <==> log(x) < log(2) + 2/f*(log(Gamma(f/2+1)) + log(eps))
+        if lower_tail
+            && f > 0.0
+            && x.ln() < M_LN2 + 2.0 / f * (libm::lgamma(f / 2.0 + 1.0) + DBL_MIN_EXP_LN)
+        {
+            // all  pchisq(x, f+2*i, lower_tail, FALSE), i=0,...,110 would underflow to 0.
+            // ==> work in log scale
+            let lambda = 0.5 * theta; // < 40
+            let mut pr = -lambda;
+            let log_lam = lambda.ln();
+            let mut sum = ML_NEGINF;
+            let mut sum2 = ML_NEGINF;
+            // we need to renormalize here: the result could be very close to 1
+            let mut i = 0;
+            while i < 110 {
+                sum2 = logspace_add(sum2, pr);
+                sum = logspace_add(sum, pr + pchisq(x, f + 2.0 * i as f64, lower_tail, true));
+                if sum2 >= -1e-15 {
+                    // <=> EXP(sum2) >= 1-1e-15
+                    break;
+                }
+                i += 1;
+                pr += log_lam - (i as f64).ln();
+            }
+            let ans = sum - sum2;
+            return if log_p { ans } else { ans.exp() };
+        } else {
+            let lambda = LDouble::new(0.5 * theta); // < 40
+            let mut sum = LDouble::new(0.0);
+            let mut sum2 = LDouble::new(0.0);
+            let mut pr = (-lambda).exp();
+            // we need to renormalize here: the result could be very close to 1
+            let mut i = 0;
+            while i < 110 {
+                // pr == exp(-lambda) lambda^i / i!  ==  dpois(i, lambda)
+                sum2 += pr;
+                // pchisq(*, i, *) is  strictly decreasing to 0 for lower_tail=TRUE
+                //                 and strictly increasing to 1 for lower_tail=FALSE
+                sum += pr * pchisq(x, f + 2.0 * i as f64, lower_tail, false);
+                if sum2.to_f64() >= 1.0 - 1e-15 {
+                    break;
+                }
+                i += 1;
+                pr = pr * (lambda / i as f64);
+            }
+            let ans = sum / sum2;
+            return if log_p { ans.ln() } else { ans.to_f64() };
+        }
+    } // if(theta < 80)
+
+    // else: theta == ncp >= 80 --------------------------------------------
+
+    // Series expansion ------- FIXME: log_p=TRUE, lower_tail=FALSE only applied at end ==> underflow
+
+    let lam = 0.5 * theta; // = lambda = ncp/2
+    let mut lam_sml = -lam < DBL_MIN_EXP_LN;
+    let mut lu = LDouble::new(-1.0);
+    let mut l_lam = -1.0;
+    let mut u;
+    if lam_sml {
+        // originally error: "non centrality parameter too large for current algorithm"
+        u = LDouble::new(0.0);
+        lu = LDouble::new(-lam); // == ln(u)
+        l_lam = lam.ln();
+    } else {
+        u = LDouble::new((-lam).exp());
+    }
+
+    // evaluate the first term
+    let mut v = u;
+    let x2 = 0.5 * x;
+    let f2 = 0.5 * f;
+    let mut f_x_2n = f - x;
+
+    let mut t = LDouble::new(x2 - f2);
+    // very large f and x ~= f: probably needs another algorithm anyway
+    let mut lt = if f2 * DBL_EPSILON > 0.125 && t.to_f64().abs() < DBL_EPSILON.sqrt() * f2 {
+        // evade cancellation error
+        // t = exp((1 - t)*(2 - t/(f2 + 1))) / sqrt(2*M_PI*(f2 + 1));
+        let t = t.to_f64();
+        LDouble::new((1.0 - t) * (2.0 - t / (f2 + 1.0)) - M_LN_SQRT_2PI - 0.5 * (f2 + 1.0).ln())
+    } else {
+        // Usual case 2: careful not to overflow .. :
+        LDouble::new(f2 * x2.ln() - x2 - lgammafn(f2 + 1.0))
+    };
+
+    let mut t_sml = lt.to_f64() < DBL_MIN_EXP_LN;
+    let mut l_x = -1.0;
+    let mut ans;
+    let mut term;
+    if t_sml {
+        if x > f + theta + 5.0 * (2.0 * (f + 2.0 * theta)).sqrt() {
+            // x > E[X] + 5* sigma(X)
+            return r_dt_1(lower_tail, log_p); // FIXME: could be more accurate than 0.
+        }
+        l_x = x.ln();
+        ans = LDouble::new(0.0);
+        term = 0.0;
+        t = LDouble::new(0.0);
+    } else {
+        t = lt.exp();
+        term = (v * t).to_f64();
+        ans = LDouble::new(term);
+    }
+
+    let mut n = 1;
+    let mut f_2n = f + 2.0;
+    f_x_2n += 2.0;
+    while n <= itrmax {
+        // f_2n    === f + 2*n
+        // f_x_2n  === f - x + 2*n   > 0  <==> (f+2n)  >   x
+        if f_x_2n > 0.0 {
+            // find the error bound and check for convergence
+            let bound = (t * x / f_x_2n).to_f64();
+            // convergence only if BOTH absolute and relative error < 'bnd'
+            if bound <= errmax && term <= reltol * ans.to_f64() {
+                break; // out completely
+            }
+        }
+
+        // evaluate the next term of the
+        // expansion and then the partial sum
+
+        if lam_sml {
+            lu += l_lam - (n as f64).ln(); // u = u* lam / n
+            if lu.to_f64() >= DBL_MIN_EXP_LN {
+                // no underflow anymore ==> change regime
+                u = lu.exp(); // the first non-0 'u'
+                v = u;
+                lam_sml = false;
+            }
+        } else {
+            u *= lam / n as f64;
+            v += u;
+        }
+        if t_sml {
+            lt += l_x - f_2n.ln(); // t <- t * (x / f2n)
+            if lt.to_f64() >= DBL_MIN_EXP_LN {
+                // no underflow anymore ==> change regime
+                t = lt.exp(); // the first non-0 't'
+                t_sml = false;
+            }
+        } else {
+            t *= x / f_2n;
+        }
+        if !lam_sml && !t_sml {
+            term = (v * t).to_f64();
+            ans += term;
+        }
+
+        n += 1;
+        f_2n += 2.0;
+        f_x_2n += 2.0;
+    } // for(n ...)
+
+    if n > itrmax {
+        ml_warning(MlWarningKind::NoConv, "pnchisq");
+    }
+    r_dt_val(ans.to_f64(), lower_tail, log_p)
+}
@@ -0,0 +1,66 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+use crate::pnbeta::pnbeta2;
+use crate::pnchisq::pnchisq;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998	Ross Ihaka
+//  Copyright (C) 2000-8 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the non-central F distribution.
+pub fn pnf(x: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || df1.is_nan() || df2.is_nan() || ncp.is_nan() {
+        return x + df2 + df1 + ncp;
+    }
+    if df1 <= 0.0 || df2 <= 0.0 || ncp < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(ncp) {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(df1) && !r_finite(df2) {
+        // both +Inf
+        return ml_warn_return_nan();
+    }
+
+    if x <= 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if x >= ML_POSINF {
+        return r_dt_1(lower_tail, log_p);
+    }
+
+    if df2 > 1e8 {
+        // avoid problems with +Inf and loss of accuracy
+        return pnchisq(x * df1, df1, ncp, lower_tail, log_p);
+    }
+
+    let y = (df1 / df2) * x;
+    pnbeta2(
+        y / (1.0 + y),
+        1.0 / (1.0 + y),
+        df1 / 2.0,
+        df2 / 2.0,
+        ncp,
+        lower_tail,
+        log_p,
+    )
+}
@@ -244,6 +244,5 @@ fn pnorm_both(x: f64, cum: &mut f64, ccum: &mut f64, i_tail: i32, log_p: bool) {
         }
 
         // R normalizes by default so NO_DENORMS is skipped.
-        return;
     }
 }
@@ -1,11 +1,13 @@
 #![allow(unused_assignments)]
 use crate::dpq::r_dt_0;
 use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_val;
 use crate::lgammafn;
 use crate::libc::fabs;
 use crate::libc::DBL_EPSILON;
 use crate::nmath::ml_warn_return_nan;
 use crate::nmath::r_finite;
+use crate::nmath::LDouble;
 use crate::nmath::M_SQRT_2dPI;
 use crate::nmath::DBL_MIN_EXP;
 use crate::nmath::M_LN_SQRT_PI;
@@ -13,11 +15,11 @@ use crate::pbeta;
 use crate::pnorm;
 use crate::pt;
 use crate::rmath::M_LN2;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
 use libm::exp;
 use libm::expm1;
 use libm::fmin;
-use libm::log1p;
-use libm::pow;
 use libm::sqrt;
 
 // NOTE: itrmax and errmax may be changed to suit one's needs.
@@ -42,14 +44,14 @@ pub fn pnt(t: f64, df: f64, ncp: f64, mut lower_tail: bool, log_p: bool) -> f64
     let mut rxb = 0.0;
     let mut tt = 0.0;
     let mut x = 0.0;
-    let mut geven = 0.0;
-    let mut godd = 0.0;
-    let mut p = 0.0;
-    let mut q = 0.0;
-    let mut s = 0.0;
-    let mut tnc = 0.0;
-    let mut xeven = 0.0;
-    let mut xodd = 0.0;
+    let mut geven = LDouble::new(0.0);
+    let mut godd = LDouble::new(0.0);
+    let mut p = LDouble::new(0.0);
+    let mut q = LDouble::new(0.0);
+    let mut s = LDouble::new(0.0);
+    let mut tnc = LDouble::new(0.0);
+    let mut xeven = LDouble::new(0.0);
+    let mut xodd = LDouble::new(0.0);
     let mut negdel = false;
 
     if df <= 0.0 {
@@ -108,35 +110,42 @@ pub fn pnt(t: f64, df: f64, ncp: f64, mut lower_tail: bool, log_p: bool) -> f64
     if x > 0.0 {
         // <==>  t != 0
         lambda = del * del;
-        p = 0.5 * exp(-0.5 * lambda);
+        p = LDouble::new(0.5 * exp(-0.5 * lambda));
 
-        if p == 0.0 {
+        if p.to_f64() == 0.0 {
             // underflow
-            println!("pnt: underflow occurred");
-            println!("pnt: value out of range");
+            ml_warning(MlWarningKind::Underflow, "pnt");
+            ml_warning(MlWarningKind::Range, "pnt");
             return r_dt_0(lower_tail, log_p);
         }
 
-        q = M_SQRT_2dPI * p * del;
-        s = 0.5 - p;
+        q = p * M_SQRT_2dPI * del;
+        s = -p;
+        s += 0.5;
         /* s = 0.5 - p = 0.5*(1 - exp(-.5 L)) =  -0.5*expm1(-.5 L)) */
-        if s < 1e-7 {
-            s = -0.5 * expm1(-0.5 * lambda);
+        if s.to_f64() < 1e-7 {
+            s = LDouble::new(-0.5 * expm1(-0.5 * lambda));
         }
         a = 0.5;
         b = 0.5 * df;
         /* rxb = (1 - x) ^ b   [ ~= 1 - b*x for tiny x --> see 'xeven' below]
          *       where '(1 - x)' =: rxb {accurately!} above */
-        rxb = pow(rxb, b);
+        rxb = rxb.powf(b);
         albeta = M_LN_SQRT_PI + lgammafn(b) - lgammafn(0.5 + b);
-        xodd = pbeta(x, a, b, true, false);
-        godd = 2.0 * rxb * exp(a * x.ln() - albeta);
-        tnc = b * x;
-        xeven = if tnc < DBL_EPSILON { tnc } else { 1.0 - rxb };
+        xodd = LDouble::new(pbeta(x, a, b, true, false));
+        godd = LDouble::new(2.0 * rxb * exp(a * x.ln() - albeta));
+        tnc = LDouble::new(b * x);
+        xeven = if tnc.to_f64() < DBL_EPSILON {
+            tnc
+        } else {
+            LDouble::new(1.0 - rxb)
+        };
         geven = tnc * rxb;
-        tnc = p * xodd + q * xeven;
+        tnc = p * xodd;
+        tnc += q * xeven;
 
         /* repeat until convergence or iteration limit */
+        let mut finished = false;
         for it in 1..=ITRMAX {
             a += 1.0;
             xodd -= godd;
@@ -145,69 +154,48 @@ pub fn pnt(t: f64, df: f64, ncp: f64, mut lower_tail: bool, log_p: bool) -> f64
             geven *= x * (a + b - 0.5) / (a + 0.5);
             p *= lambda / (2.0 * it as f64);
             q *= lambda / (2.0 * it as f64 + 1.0);
-            tnc += p * xodd + q * xeven;
+            let mut term = p * xodd;
+            term += q * xeven;
+            tnc += term;
             s -= p;
 
-            if s < 1e-10 {
+            // R 2.4.0 added test for rounding error here.
+            if s.to_f64() < -1e-10 {
                 /* happens e.g. for (t,df,ncp)=(40,10,38.5), after 799 it.*/
-                println!("pnt: full precision may not have been achieved");
-                finis(del, &mut tnc);
+                ml_warning(MlWarningKind::Precision, "pnt");
+                finished = true;
                 break;
             }
 
-            if s <= 0.0 && it > 1 {
-                finis(del, &mut tnc);
+            if s.to_f64() <= 0.0 && it > 1 {
+                finished = true;
                 break;
             }
 
-            errbd = 2.0 * s * (xodd - godd);
+            let mut d = xodd;
+            d -= godd;
+            errbd = (s * 2.0 * d).to_f64();
 
             if fabs(errbd) < ERRMAX {
                 // convergence
-                finis(del, &mut tnc);
+                finished = true;
                 break;
             }
         }
-        // non-convergence:
-        println!("pnt: convergence failed");
+        if !finished {
+            // non-convergence:
+            ml_warning(MlWarningKind::NoConv, "pnt");
+        }
     } else {
         /* x = t = 0 */
-        tnc = 0.0;
+        tnc = LDouble::new(0.0);
     }
+    tnc += pnorm(-del, 0.0, 1.0, true, false);
 
     lower_tail = lower_tail != negdel; /* xor */
-    if tnc > 1.0 - 1e-10 && lower_tail {
-        println!("pnt: full precision may not have been achieved");
-    }
-
-    r_dt_val(fmin(tnc, 1.0), lower_tail, log_p)
-}
-
-// converting the goto statement
-fn finis(del: f64, tnc: &mut f64) {
-    *tnc += pnorm(-del, 0.0, 1.0, true, false);
-}
-
-pub fn r_dt_val(x: f64, lower_tail: bool, log_p: bool) -> f64 {
-    if lower_tail {
-        r_d_val(x, log_p)
-    } else {
-        r_d_clog(x, log_p)
-    }
-}
-
-pub fn r_d_val(x: f64, log_p: bool) -> f64 {
-    if log_p {
-        x.ln()
-    } else {
-        x
+    if tnc.to_f64() > 1.0 - 1e-10 && lower_tail {
+        ml_warning(MlWarningKind::Precision, "pnt");
     }
-}
 
-pub fn r_d_clog(p: f64, log_p: bool) -> f64 {
-    if log_p {
-        log1p(-p)
-    } else {
-        0.5 - p + 0.5
-    }
+    r_dt_val(fmin(tnc.to_f64(), 1.0), lower_tail, log_p)
 }
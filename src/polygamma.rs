@@ -0,0 +1,562 @@
+use crate::d1mach::d1mach;
+use crate::fmax2::fmax2;
+use crate::fmin2::fmin2;
+use crate::i1mach::i1mach;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::r_forceint;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_POSINF;
+use crate::rmath::M_PI;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2015 The R Core Team
+//  Copyright (C) 2004-2009 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The largest derivative supported by `psigamma`.
+const N_MAX: i32 = 100;
+
+/// Compute the derivatives of the psi function and polygamma functions.
+///
+/// The following definitions are used:
+///
+/// - `psi(x) = d/dx (ln(gamma(x)))`, the first derivative of the log gamma
+///   function.
+/// - `psi(k, x) = d^k/dx^k (psi(x))`, the k-th derivative of `psi(x)`.
+///
+/// `dpsifn` computes a sequence of scaled derivatives of the psi function;
+/// i.e. for fixed x and m it computes the m-member sequence
+///
+/// `(-1)^(k+1) / gamma(k+1) * psi(k, x)` for `k = n, ..., n+m-1`
+///
+/// For `kode = 1`, `dpsifn` returns the scaled derivatives as described.
+/// `kode = 2` is operative only when `k = 0` and in that case `dpsifn`
+/// returns `-psi(x) + ln(x)`.
+/// That is, the logarithmic behavior for large x is removed when `kode = 2`
+/// and `k = 0`.
+/// When sums or differences of psi functions are computed the logarithmic
+/// terms can be combined analytically and computed separately to help
+/// retain significant digits.
+///
+/// Note that `dpsifn(x, 0, 1, 1, ans, ..)` results in `ans[0] = -psi(x)`.
+///
+/// Input:
+///
+/// - `x`: argument.
+///   Negative non-integer `x` uses the reflection formula and is only
+///   implemented for `m = 1` and `n <= 3`.
+/// - `n`: first member of the sequence, `0 <= n <= 100`.
+/// - `kode`: selection parameter, 1 or 2 as described above.
+/// - `m`: number of members of the sequence, `m >= 1`.
+///
+/// Output:
+///
+/// - `ans`: a vector of length at least m whose first m components contain
+///   the sequence of derivatives scaled according to kode.
+/// - `nz`: underflow flag.
+///   `nz == 0` is a normal return; otherwise the last nz components of ans
+///   are set to zero.
+/// - `ierr`: error flag.
+///   0 is a normal return, 1 an input error, 2 an overflow (x too small or
+///   n+m-1 too large or both), 3 when n is too large, and 4 when the
+///   reflection formula for `x <= 0` is not implemented.
+///
+/// The nominal computational accuracy is the maximum of unit roundoff and
+/// 1e-18 since critical constants are given to only 18 digits.
+///
+/// The basic method of evaluation is the asymptotic expansion for large
+/// `x >= xmin` followed by backward recursion on a two term recursion
+/// relation `w(x+1) + x^(-n-1) = w(x)`.
+/// This is supplemented by a series `sum((x+k)^(-n-1), k=0,1,2,...)` which
+/// converges rapidly for large n.
+///
+/// Authors: D. E. Amos (Fortran), Ross Ihaka (C translation),
+/// Martin Maechler (x < 0, and `psigamma`).
+///
+/// References:
+/// - Handbook of Mathematical Functions, National Bureau of Standards
+///   Applied Mathematics Series 55, Edited by M. Abramowitz and
+///   I. A. Stegun, equations 6.3.5, 6.3.18, 6.4.6, 6.4.9 and 6.4.10,
+///   pp.258-260, 1964.
+/// - D. E. Amos, (1983). "A Portable Fortran Subroutine for Derivatives of
+///   the Psi Function", Algorithm 610, TOMS 9(4), pp. 494-502.
+///
+/// # Panics
+///
+/// If `ans` is shorter than `m`.
+#[allow(clippy::excessive_precision)]
+pub fn dpsifn(x: f64, n: i32, kode: i32, m: i32, ans: &mut [f64], nz: &mut i32, ierr: &mut i32) {
+    // Bernoulli Numbers
+    const BVALUES: [f64; 22] = [
+        1.00000000000000000e+00,
+        -5.00000000000000000e-01,
+        1.66666666666666667e-01,
+        -3.33333333333333333e-02,
+        2.38095238095238095e-02,
+        -3.33333333333333333e-02,
+        7.57575757575757576e-02,
+        -2.53113553113553114e-01,
+        1.16666666666666667e+00,
+        -7.09215686274509804e+00,
+        5.49711779448621554e+01,
+        -5.29124242424242424e+02,
+        6.19212318840579710e+03,
+        -8.65802531135531136e+04,
+        1.42551716666666667e+06,
+        -2.72982310678160920e+07,
+        6.01580873900642368e+08,
+        -1.51163157670921569e+10,
+        4.29614643061166667e+11,
+        -1.37116552050883328e+13,
+        4.88332318973593167e+14,
+        -1.92965793419400681e+16,
+    ];
+
+    let mut trm = [0.0; 23];
+    let mut trmr = [0.0; N_MAX as usize + 1];
+
+    *ierr = 0;
+    if n < 0 || kode < 1 || kode > 2 || m < 1 {
+        *ierr = 1;
+        return;
+    }
+    if x <= 0. {
+        // use	Abramowitz & Stegun 6.4.7 "Reflection Formula"
+        //	psi(k, x) = (-1)^k psi(k, 1-x)	-  pi^{n+1} (d/dx)^n cot(x)
+        if x == x.round() {
+            // non-positive integer : +Inf or NaN depends on n
+            for (j, a) in ans.iter_mut().take(m as usize).enumerate() {
+                // k = j + n :
+                *a = if (j as i32 + n) % 2 != 0 {
+                    ML_POSINF
+                } else {
+                    ML_NAN
+                };
+            }
+            return;
+        }
+        // This could cancel badly
+        dpsifn(1. - x, n, 1, m, ans, nz, ierr);
+        // ans[j] == (-1)^(k+1) / gamma(k+1) * psi(k, 1 - x)
+        //	     for j = 0:(m-1) ,	k = n + j
+
+        // Cheat for now: only work for	 m = 1, n in {0,1,2,3} :
+        if m > 1 || n > 3 {
+            // doesn't happen for digamma() .. pentagamma()
+            // not yet implemented
+            *ierr = 4;
+            return;
+        }
+        let x = x * M_PI; // pi * x
+        let tt = match n {
+            0 => x.cos() / x.sin(),
+            1 => -1. / x.sin().powi(2),
+            2 => 2. * x.cos() / x.sin().powi(3),
+            3 => -2. * (2. * x.cos().powi(2) + 1.) / x.sin().powi(4),
+            // can not happen!
+            _ => ML_NAN,
+        };
+        // end cheat
+
+        // t := pi^(n+1) * d_n(x) / gamma(n+1)	, where
+        //		   d_n(x) := (d/dx)^n cot(x)
+        let mut t1 = 1.;
+        let mut t2 = 1.;
+        let mut s = 1.;
+        let mut k = 0;
+        let mut j = k - n;
+        while j < m {
+            // k == n+j , s = (-1)^k
+            t1 *= M_PI; // t1 == pi^(k+1)
+            if k >= 2 {
+                t2 *= k as f64; // t2 == k! == gamma(k+1)
+            }
+            if j >= 0 {
+                // by cheat above,  tt === d_k(x)
+                ans[j as usize] = s * (ans[j as usize] + t1 / t2 * tt);
+            }
+            k += 1;
+            j += 1;
+            s = -s;
+        }
+        // For n == 0 && kode == 2, C adds xln here, which is still 0.
+        return;
+    } // x <= 0
+
+    // else :  x > 0
+    *nz = 0;
+    let xln = x.ln();
+    if kode == 1 && m == 1 {
+        // the R case  ---  for very large x:
+        let lrg = 1. / (2. * DBL_EPSILON);
+        if n == 0 && x * xln > lrg {
+            ans[0] = -xln;
+            return;
+        } else if n >= 1 && x > n as f64 * lrg {
+            ans[0] = (-n as f64 * xln).exp() / n as f64; // == x^-n / n  ==  1/(n * x^n)
+            return;
+        }
+    }
+    let mut mm = m;
+    let mut nx = i32::min(-i1mach(15), i1mach(16)); // = 1021
+    let r1m5 = d1mach(5);
+    let r1m4 = d1mach(4) * 0.5;
+    let wdtol = fmax2(r1m4, 0.5e-18); // 1.11e-16
+
+    // elim = approximate exponential over and underflow limit
+    let elim = 2.302 * (nx as f64 * r1m5 - 3.0); // = 700.6174...
+
+    let mut nn;
+    let mut fn_;
+    let mut xdmy = x;
+    let mut xdmln = xln;
+    let mut xinc = 0.;
+    let mut t;
+    // The loop ends with None to use the asymptotic expansion (C label L10),
+    // or with the number of terms of the series, fln+1, to use the series.
+    let series_fln = loop {
+        nn = n + mm - 1;
+        fn_ = nn;
+        t = (fn_ + 1) as f64 * xln;
+
+        // overflow and underflow test for small and large x
+
+        if t.abs() > elim {
+            if t <= 0.0 {
+                *nz = 0;
+                *ierr = 2;
+                return;
+            }
+        } else {
+            if x < wdtol {
+                ans[0] = x.powi(-n - 1);
+                if mm != 1 {
+                    for k in 1..mm as usize {
+                        ans[k] = ans[k - 1] / x;
+                    }
+                }
+                if n == 0 && kode == 2 {
+                    ans[0] += xln;
+                }
+                return;
+            }
+
+            // compute xmin and the number of terms of the series,  fln+1
+
+            let mut rln = r1m5 * i1mach(14) as f64;
+            rln = fmin2(rln, 18.06);
+            let mut fln = fmax2(rln, 3.0) - 3.0;
+            let yint = 3.50 + 0.40 * fln;
+            let slope = 0.21 + fln * (0.0006038 * fln + 0.008677);
+            let mut xm = yint + slope * fn_ as f64;
+            let mx = xm as i32 + 1;
+            let xmin = mx as f64;
+            if n != 0 {
+                xm = -2.302 * rln - fmin2(0.0, xln);
+                let mut arg = xm / n as f64;
+                arg = fmin2(0.0, arg);
+                let eps = arg.exp();
+                xm = 1.0 - eps;
+                if arg.abs() < 1.0e-3 {
+                    xm = -arg;
+                }
+                fln = x * xm / eps;
+                xm = xmin - x;
+                if xm > 7.0 && fln < 15.0 {
+                    break Some(fln);
+                }
+            }
+            xdmy = x;
+            xdmln = xln;
+            xinc = 0.0;
+            if x < xmin {
+                nx = x as i32;
+                xinc = xmin - nx as f64;
+                xdmy = x + xinc;
+                xdmln = xdmy.ln();
+            }
+
+            // generate w(n+mm-1, x) by the asymptotic expansion
+
+            t = fn_ as f64 * xdmln;
+            let t1 = xdmln + xdmln;
+            let t2 = t + xdmln;
+            let tk = fmax2(t.abs(), fmax2(t1.abs(), t2.abs()));
+            if tk <= elim {
+                // for all but large x
+                break None;
+            }
+        }
+        *nz += 1; // underflow
+        mm -= 1;
+        ans[mm as usize] = 0.;
+        if mm == 0 {
+            return;
+        }
+    };
+
+    if let Some(fln) = series_fln {
+        let nn = fln as usize + 1;
+        let np = n + 1;
+        let t1 = (n + 1) as f64 * xln;
+        let mut t = (-t1).exp();
+        let mut s = t;
+        let mut den = x;
+        for tr in trm.iter_mut().skip(1).take(nn) {
+            den += 1.;
+            *tr = den.powf(-np as f64);
+            s += *tr;
+        }
+        ans[0] = s;
+        if n == 0 && kode == 2 {
+            ans[0] = s + xln;
+        }
+
+        if mm != 1 {
+            // generate higher derivatives, j > n
+
+            let tol = wdtol / 5.0;
+            for a in ans.iter_mut().take(mm as usize).skip(1) {
+                t /= x;
+                s = t;
+                let tols = t * tol;
+                den = x;
+                for tr in trm.iter_mut().skip(1).take(nn) {
+                    den += 1.;
+                    *tr /= den;
+                    s += *tr;
+                    if *tr < tols {
+                        break;
+                    }
+                }
+                *a = s;
+            }
+        }
+        return;
+    }
+
+    // L10:
+    let mut tss = (-t).exp();
+    let tt = 0.5 / xdmy;
+    let mut t1 = tt;
+    let tst = wdtol * tt;
+    if nn != 0 {
+        t1 = tt + 1.0 / fn_ as f64;
+    }
+    let rxsq = 1.0 / (xdmy * xdmy);
+    let ta = 0.5 * rxsq;
+    t = (fn_ + 1) as f64 * ta;
+    let mut s = t * BVALUES[2];
+    if s.abs() >= tst {
+        let mut tk = 2.0;
+        for k in 4..=22 {
+            t = t * ((tk + fn_ as f64 + 1.) / (tk + 1.0)) * ((tk + fn_ as f64) / (tk + 2.0)) * rxsq;
+            trm[k] = t * BVALUES[k - 1];
+            if trm[k].abs() < tst {
+                break;
+            }
+            s += trm[k];
+            tk += 2.;
+        }
+    }
+    s = (s + t1) * tss;
+
+    let mut to_l20 = false;
+    if xinc != 0.0 {
+        // backward recur from xdmy to x
+
+        nx = xinc as i32;
+        let np = nn + 1;
+        if nx > N_MAX {
+            *nz = 0;
+            *ierr = 3;
+            return;
+        } else if nn == 0 {
+            to_l20 = true;
+        } else {
+            let mut xm = xinc - 1.0;
+            let mut fx = x + xm;
+
+            // this loop should not be changed. fx is accurate when x is small
+            for tr in trmr.iter_mut().skip(1).take(nx as usize) {
+                *tr = fx.powf(-np as f64);
+                s += *tr;
+                xm -= 1.;
+                fx = x + xm;
+            }
+        }
+    }
+    if !to_l20 {
+        ans[mm as usize - 1] = s;
+        if fn_ != 0 {
+            // generate lower derivatives,  j < n+mm-1
+
+            let mut j = 2;
+            loop {
+                if j > mm {
+                    return;
+                }
+                fn_ -= 1;
+                tss *= xdmy;
+                t1 = tt;
+                if fn_ != 0 {
+                    t1 = tt + 1.0 / fn_ as f64;
+                }
+                t = (fn_ + 1) as f64 * ta;
+                s = t * BVALUES[2];
+                if s.abs() >= tst {
+                    let mut tk = (4 + fn_) as f64;
+                    for tr in trm.iter_mut().skip(4) {
+                        *tr = *tr * (fn_ + 1) as f64 / tk;
+                        if tr.abs() < tst {
+                            break;
+                        }
+                        s += *tr;
+                        tk += 2.;
+                    }
+                }
+                s = (s + t1) * tss;
+                if xinc != 0.0 {
+                    if fn_ == 0 {
+                        to_l20 = true;
+                        break;
+                    }
+                    let mut xm = xinc - 1.0;
+                    let mut fx = x + xm;
+                    for tr in trmr.iter_mut().skip(1).take(nx as usize) {
+                        *tr *= fx;
+                        s += *tr;
+                        xm -= 1.;
+                        fx = x + xm;
+                    }
+                }
+                ans[(mm - j) as usize] = s;
+                if fn_ == 0 {
+                    break;
+                }
+                j += 1;
+            }
+        }
+    }
+
+    if to_l20 {
+        for i in 1..=nx {
+            s += 1. / (x + (nx - i) as f64); // avoid disastrous cancellation, PR#13714
+        }
+    }
+
+    // L30:
+    if kode != 2 {
+        // always
+        ans[0] = s - xdmln;
+    } else if xdmy != x {
+        let xq = xdmy / x;
+        ans[0] = s - xq.ln();
+    }
+}
+
+/// Calls `dpsifn` for a single derivative `n` as R does.
+fn dpsifn1(x: f64, n: i32) -> Option<f64> {
+    let mut ans = [0.0];
+    let mut nz = 0;
+    let mut ierr = 0;
+    dpsifn(x, n, 1, 1, &mut ans, &mut nz, &mut ierr);
+    if ierr != 0 {
+        None
+    } else {
+        Some(ans[0])
+    }
+}
+
+/// The `deriv`-th derivative of the digamma function `psi(x)`.
+///
+/// For example, `psigamma(x, 0) == digamma(x)` and
+/// `psigamma(x, 1) == trigamma(x)`.
+/// `deriv` is rounded to the nearest integer and must be at most 100,
+/// otherwise an `MlWarningKind::Range` warning is raised and NaN is
+/// returned.
+/// As in R, negative non-integer `x` is only supported up to
+/// `deriv = 3`; larger derivatives give NaN there.
+pub fn psigamma(x: f64, deriv: f64) -> f64 {
+    // n-th derivative of psi(x);  e.g., psigamma(x,0) == digamma(x)
+    if x.is_nan() {
+        return x;
+    }
+    let deriv = r_forceint(deriv);
+    let n = deriv as i32;
+    if n > N_MAX {
+        // deriv = n > n_max
+        ml_warning(MlWarningKind::Range, "psigamma");
+        return ML_NAN;
+    }
+    let mut ans = match dpsifn1(x, n) {
+        Some(ans) => ans,
+        None => return ML_NAN,
+    };
+    // Now, ans ==  A := (-1)^(n+1) / gamma(n+1) * psi(n, x)
+    ans = -ans; // = (-1)^(0+1) * gamma(0+1) * A
+    for k in 1..=n {
+        ans *= -k as f64; // = (-1)^(k+1) * gamma(k+1) * A
+    }
+    ans // = psi(n, x)
+}
+
+/// The digamma function `psi(x)`, the first derivative of `ln(gamma(x))`.
+///
+/// Returns NaN at `x = 0, -1, -2, ...`.
+pub fn digamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    match dpsifn1(x, 0) {
+        Some(ans) => -ans,
+        None => ML_NAN,
+    }
+}
+
+/// The trigamma function, the second derivative of `ln(gamma(x))`.
+pub fn trigamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    dpsifn1(x, 1).unwrap_or(ML_NAN)
+}
+
+/// The tetragamma function, the third derivative of `ln(gamma(x))`.
+pub fn tetragamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    match dpsifn1(x, 2) {
+        Some(ans) => -2.0 * ans,
+        None => ML_NAN,
+    }
+}
+
+/// The pentagamma function, the fourth derivative of `ln(gamma(x))`.
+pub fn pentagamma(x: f64) -> f64 {
+    if x.is_nan() {
+        return x;
+    }
+    match dpsifn1(x, 3) {
+        Some(ans) => 6.0 * ans,
+        None => ML_NAN,
+    }
+}
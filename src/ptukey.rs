@@ -0,0 +1,348 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_val;
+use crate::lgamma::lgammafn;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::LDouble;
+use crate::pnorm::pnorm5;
+use crate::rmath::M_1_SQRT_2PI;
+use crate::rmath::M_LN2;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998       Ross Ihaka
+//  Copyright (C) 2000--2007 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Order of the legendre quadrature in `wprob`.
+const NLEG: usize = 12;
+/// int ((nleg + 1) / 2)
+const IHALF: usize = 6;
+
+/// Legendre 12-point nodes.
+#[allow(clippy::excessive_precision)]
+const XLEG: [f64; IHALF] = [
+    0.981560634246719250690549090149,
+    0.904117256370474856678465866119,
+    0.769902674194304687036893833213,
+    0.587317954286617447296702418941,
+    0.367831498998180193752691536644,
+    0.125233408511468915472441369464,
+];
+
+/// Legendre 12-point coefficients.
+#[allow(clippy::excessive_precision)]
+const ALEG: [f64; IHALF] = [
+    0.047175336386511827194615961485,
+    0.106939325995318430960254718194,
+    0.160078328543346226334652529543,
+    0.203167426723065921749064455810,
+    0.233492536538354808760849898925,
+    0.249147045813402785000562436043,
+];
+
+/// The probability integral of Hartley's form of the range.
+///
+/// w     = value of range
+/// rr    = no. of rows or groups
+/// cc    = no. of columns or treatments
+/// pr_w  = returned probability integral from (0, w)
+///
+/// bb = upper limit of legendre integration
+/// wlar = value of range above which wincr1 intervals are used to
+///        calculate second part of integral,
+///        else wincr2 intervals are used.
+/// C1, C2, C3 = values which are used as cutoffs for terminating
+/// or modifying a calculation.
+///
+/// The interval limits `blb` and `bub` and the quadrature sums are
+/// `LDouble`s, as in the C code.
+fn wprob(w: f64, rr: f64, cc: f64) -> f64 {
+    // looks like this is suboptimal for double precision.
+    // (see how C1-C3 are used) <MM>
+    const C1: f64 = -30.;
+    const C2: f64 = -50.;
+    const C3: f64 = 60.;
+    const BB: f64 = 8.;
+    const WLAR: f64 = 3.;
+    const WINCR1: f64 = 2.;
+    const WINCR2: f64 = 3.;
+
+    let qsqz = w * 0.5;
+
+    // if w >= 16 then the integral lower bound (occurs for c=20)
+    // is 0.99999999999995 so return a value of 1.
+    if qsqz >= BB {
+        return 1.0;
+    }
+
+    // find (f(w/2) - 1) ^ cc
+    // (first term in integral of hartley's form).
+    let mut pr_w = 2. * pnorm5(qsqz, 0., 1., true, false) - 1.; // erf(qsqz / M_SQRT2)
+                                                                // if pr_w ^ cc < 2e-22 then set pr_w = 0
+    if pr_w >= (C2 / cc).exp() {
+        pr_w = pr_w.powf(cc);
+    } else {
+        pr_w = 0.0;
+    }
+
+    // if w is large then the second component of the
+    // integral is small, so fewer intervals are needed.
+    let wincr = if w > WLAR { WINCR1 } else { WINCR2 };
+
+    // find the integral of second term of hartley's form
+    // for the integral of the range for equal-length
+    // intervals using legendre quadrature.  limits of
+    // integration are from (w/2, 8).  two or three
+    // equal-length intervals are used.
+
+    // blb and bub are lower and upper limits of integration.
+    let mut blb = LDouble::new(qsqz);
+    let binc = (BB - qsqz) / wincr;
+    let mut bub = blb;
+    bub += binc;
+    let mut einsum = LDouble::new(0.0);
+
+    // integrate over each interval
+    let cc1 = cc - 1.0;
+    let mut wi = 1.;
+    while wi <= wincr {
+        let mut elsum = LDouble::new(0.0);
+        let mut a = bub;
+        a += blb;
+        let a = (a * 0.5).to_f64();
+
+        // legendre quadrature with order = nleg
+        let mut b = bub;
+        b -= blb;
+        let b = (b * 0.5).to_f64();
+
+        for jj in 1..=NLEG {
+            let (j, xx) = if IHALF < jj {
+                let j = (NLEG - jj) + 1;
+                (j, XLEG[j - 1])
+            } else {
+                (jj, -XLEG[jj - 1])
+            };
+            let c = b * xx;
+            let ac = a + c;
+
+            // if exp(-qexpo/2) < 9e-14,
+            // then doesn't contribute to integral
+            let qexpo = ac * ac;
+            if qexpo > C3 {
+                break;
+            }
+
+            let pplus = 2. * pnorm5(ac, 0., 1., true, false);
+            let pminus = 2. * pnorm5(ac, w, 1., true, false);
+
+            // if rinsum ^ (cc-1) < 9e-14,
+            // then doesn't contribute to integral
+            let mut rinsum = (pplus * 0.5) - (pminus * 0.5);
+            if rinsum >= (C1 / cc1).exp() {
+                rinsum = (ALEG[j - 1] * (-(0.5 * qexpo)).exp()) * rinsum.powf(cc1);
+                elsum += rinsum;
+            }
+        }
+        elsum *= ((2.0 * b) * cc) * M_1_SQRT_2PI;
+        einsum += elsum;
+        blb = bub;
+        bub += binc;
+        wi += 1.;
+    }
+
+    // if pr_w ^ rr < 9e-14, then return 0
+    pr_w += einsum.to_f64();
+    if pr_w <= (C1 / rr).exp() {
+        return 0.;
+    }
+
+    pr_w = pr_w.powf(rr);
+    if pr_w >= 1. {
+        // 1 was iMax was eps
+        return 1.;
+    }
+    pr_w
+}
+
+/// Order of the legendre quadrature in `ptukey`.
+const NLEGQ: usize = 16;
+/// int ((nlegq + 1) / 2)
+const IHALFQ: usize = 8;
+
+/// Legendre 16-point nodes.
+#[allow(clippy::excessive_precision)]
+const XLEGQ: [f64; IHALFQ] = [
+    0.989400934991649932596154173450,
+    0.944575023073232576077988415535,
+    0.865631202387831743880467897712,
+    0.755404408355003033895101194847,
+    0.617876244402643748446671764049,
+    0.458016777657227386342419442984,
+    0.281603550779258913230460501460,
+    0.950125098376374401853193354250e-1,
+];
+
+/// Legendre 16-point coefficients.
+#[allow(clippy::excessive_precision)]
+const ALEGQ: [f64; IHALFQ] = [
+    0.271524594117540948517805724560e-1,
+    0.622535239386478928628438369944e-1,
+    0.951585116824927848099251076022e-1,
+    0.124628971255533872052476282192,
+    0.149595988816576732081501730547,
+    0.169156519395002538189312079030,
+    0.182603415044923588866763667969,
+    0.189450610455068496285396723208,
+];
+
+/// The distribution function of the studentized range distribution.
+///
+/// q = value of studentized range
+/// rr = no. of rows or groups (`nranges` in R)
+/// cc = no. of columns or treatments (`nmeans` in R)
+/// df = degrees of freedom of error term
+///
+/// The integral is computed with legendre quadrature, see
+///
+/// Copenhaver, Margaret Diponzio & Holland, Burt S.
+/// Multiple comparisons of simple effects in
+/// the two-way analysis of variance with fixed effects.
+/// Journal of Statistical Computation and Simulation,
+/// Vol.30, pp.1-15, 1988.
+///
+/// If the integral does not converge, a `MlWarningKind::Precision` warning
+/// is raised; see `take_warnings`.
+pub fn ptukey(q: f64, rr: f64, cc: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
+    // const double eps = 1.0; not used if = 1
+    const EPS1: f64 = -30.0;
+    const EPS2: f64 = 1.0e-14;
+    const DHAF: f64 = 100.0;
+    const DQUAR: f64 = 800.0;
+    const DEIGH: f64 = 5000.0;
+    const DLARG: f64 = 25000.0;
+    const ULEN1: f64 = 1.0;
+    const ULEN2: f64 = 0.5;
+    const ULEN3: f64 = 0.25;
+    const ULEN4: f64 = 0.125;
+
+    if q.is_nan() || rr.is_nan() || cc.is_nan() || df.is_nan() {
+        return ml_warn_return_nan();
+    }
+
+    if q <= 0. {
+        return r_dt_0(lower_tail, log_p);
+    }
+
+    // df must be > 1
+    // there must be at least two values
+    if df < 2. || rr < 1. || cc < 2. {
+        return ml_warn_return_nan();
+    }
+
+    if !r_finite(q) {
+        return r_dt_1(lower_tail, log_p);
+    }
+
+    if df > DLARG {
+        return r_dt_val(wprob(q, rr, cc), lower_tail, log_p);
+    }
+
+    // calculate leading constant
+    let f2 = df * 0.5;
+    // lgammafn(u) = log(gamma(u))
+    let mut f2lf = ((f2 * df.ln()) - (df * M_LN2)) - lgammafn(f2);
+    let f21 = f2 - 1.0;
+
+    // integral is divided into unit, half-unit, quarter-unit, or
+    // eighth-unit length intervals depending on the value of the
+    // degrees of freedom.
+    let ff4 = df * 0.25;
+    let ulen = if df <= DHAF {
+        ULEN1
+    } else if df <= DQUAR {
+        ULEN2
+    } else if df <= DEIGH {
+        ULEN3
+    } else {
+        ULEN4
+    };
+
+    f2lf += ulen.ln();
+
+    // integrate over each subinterval
+    let mut ans = 0.0;
+    let mut otsum = 0.0;
+
+    for i in 1..=50 {
+        otsum = 0.0;
+
+        // legendre quadrature with order = nlegq
+        // nodes (stored in xlegq) are symmetric around zero.
+        let twa1 = (2 * i - 1) as f64 * ulen;
+
+        for jj in 1..=NLEGQ {
+            let j;
+            let t1 = if IHALFQ < jj {
+                j = jj - IHALFQ - 1;
+                (f2lf + (f21 * (twa1 + (XLEGQ[j] * ulen)).ln()))
+                    - (((XLEGQ[j] * ulen) + twa1) * ff4)
+            } else {
+                j = jj - 1;
+                (f2lf + (f21 * (twa1 - (XLEGQ[j] * ulen)).ln()))
+                    + (((XLEGQ[j] * ulen) - twa1) * ff4)
+            };
+
+            // if exp(t1) < 9e-14, then doesn't contribute to integral
+            if t1 >= EPS1 {
+                let qsqz = if IHALFQ < jj {
+                    q * (((XLEGQ[j] * ulen) + twa1) * 0.5).sqrt()
+                } else {
+                    q * (((-(XLEGQ[j] * ulen)) + twa1) * 0.5).sqrt()
+                };
+
+                // call wprob to find integral of range portion
+                let wprb = wprob(qsqz, rr, cc);
+                let rotsum = (wprb * ALEGQ[j]) * t1.exp();
+                otsum += rotsum;
+            }
+            // end legendre integral for interval i
+        }
+
+        // if integral for interval i < 1e-14, then stop.
+        // However, in order to avoid small area under left tail,
+        // at least  1 / ulen  intervals are calculated.
+        if i as f64 * ulen >= 1.0 && otsum <= EPS2 {
+            break;
+        }
+
+        // end of interval i
+        ans += otsum;
+    }
+
+    if otsum > EPS2 {
+        // not converged
+        ml_warning(MlWarningKind::Precision, "ptukey");
+    }
+    if ans > 1. {
+        ans = 1.;
+    }
+    r_dt_val(ans, lower_tail, log_p)
+}
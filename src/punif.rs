@@ -0,0 +1,49 @@
+use crate::dpq::r_d_val;
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2006 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The distribution function of the uniform distribution on `[a, b]`.
+pub fn punif(x: f64, a: f64, b: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || a.is_nan() || b.is_nan() {
+        return x + a + b;
+    }
+    if b < a {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(a) || !r_finite(b) {
+        return ml_warn_return_nan();
+    }
+
+    if x >= b {
+        return r_dt_1(lower_tail, log_p);
+    }
+    if x <= a {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if lower_tail {
+        r_d_val((x - a) / (b - a), log_p)
+    } else {
+        r_d_val((b - x) / (b - a), log_p)
+    }
+}
@@ -0,0 +1,627 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_civ;
+use crate::dpq::r_dt_clog;
+use crate::dpq::r_dt_log;
+use crate::dpq::r_dt_qiv;
+use crate::fmax2::fmax2;
+use crate::fmin2::fmin2;
+use crate::lbeta::lbeta;
+use crate::libc::DBL_MANT_DIG;
+use crate::libc::DBL_MIN;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_log1_exp;
+use crate::nmath::DBL_MIN_EXP;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+use crate::pbeta::pbeta_raw;
+use crate::rmath::M_LN2;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 1998--2018  The R Core Team
+//  Copyright (C) 1995, 1996  Robert Gentleman and Ross Ihaka
+//  based on code (C) 1979 and later Royal Statistical Society
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// based on some testing; had = -10
+const USE_LOG_X_CUTOFF: f64 = -5.0;
+
+/// based on some testing; had = 10
+const N_NEWTON_FREE: i32 = 4;
+
+// CARE: assumes subnormal numbers, i.e., no underflow at DBL_MIN:
+const DBL_VERY_MIN: f64 = DBL_MIN / 4.0;
+const DBL_LOG_V_MIN: f64 = M_LN2 * (DBL_MIN_EXP - 2.0);
+// Too extreme: inaccuracy in pbeta(); e.g for  qbeta(0.95, 1e-9, 20):
+// -> in pbeta() --> bgrat(..... b*z == 0 underflow, hence inaccurate pbeta()
+
+/// = 1 - 2^-53
+const DBL_1__EPS: f64 = 1.0 - f64::EPSILON / 2.0;
+
+// set the exponent of acu to -2r-2 for r digits of accuracy
+//---- NEW ---- -- still fails for p = 1e11, q=.5
+
+const FPU: f64 = 3e-308;
+/// Minimal value for accuracy 'acu' which will depend on (a,p);
+/// acu_min >= fpu !
+const ACU_MIN: f64 = 1e-300;
+const P_LO: f64 = FPU;
+const P_HI: f64 = 1.0 - 2.22e-16;
+
+const CONST1: f64 = 2.30753;
+const CONST2: f64 = 0.27061;
+const CONST3: f64 = 0.99229;
+const CONST4: f64 = 0.04481;
+
+/// Compute the quantile function of the beta distribution.
+///
+/// ## NOTES
+///
+/// Uses the initial approximations of AS 109 (and AS 64 for small p or q)
+/// followed by modified Newton-Raphson steps on `pbeta_raw()`.
+/// When the quantile is very close to 0, the Newton iterations are done on
+/// the `log(x)` scale.
+///
+/// ## REFERENCES
+///
+/// Cran, G. W., K. J. Martin and G. E. Thomas (1977).
+/// Remark AS R19 and Algorithm AS 109,
+/// Applied Statistics, 26(1), 111-114.
+///
+/// Remark AS R83 (v.39, 309-310) and the correction (v.40(1) p.236)
+/// have been incorporated in this version.
+pub fn qbeta(alpha: f64, p: f64, q: f64, lower_tail: bool, log_p: bool) -> f64 {
+    // test for admissibility of parameters
+    if p.is_nan() || q.is_nan() || alpha.is_nan() {
+        return p + q + alpha;
+    }
+    if p < 0.0 || q < 0.0 {
+        return ml_warn_return_nan();
+    }
+    // allowing p==0 and q==0  <==> treat as one- or two-point mass
+
+    let qbet = qbeta_raw(
+        alpha,
+        p,
+        q,
+        lower_tail,
+        log_p,
+        None,
+        USE_LOG_X_CUTOFF,
+        N_NEWTON_FREE,
+    );
+    qbet[0]
+}
+
+/// Returns both `qbeta()` and its "mirror" `1 - qbeta()`.
+/// Useful notably when `qbeta()` ~= 1.
+///
+/// - `swap_01`: if `None`, the algorithm decides whether to swap the tails.
+/// - `log_q_cut`: if `Inf`, return `log(qbeta(..))`; otherwise, the bound
+///   for switching to `log(x)`-scale.
+/// - `n_n`: number of "unconstrained" Newton steps before switching to
+///   constrained.
+#[allow(clippy::too_many_arguments)]
+pub fn qbeta_raw(
+    alpha: f64,
+    p: f64,
+    q: f64,
+    lower_tail: bool,
+    log_p: bool,
+    swap_01: Option<bool>,
+    log_q_cut: f64,
+    n_n: i32,
+) -> [f64; 2] {
+    let swap_choose = swap_01.is_none();
+    let give_log_q = log_q_cut == ML_POSINF;
+    let mut use_log_x = give_log_q; // or u < log_q_cut  below
+    let mut warned = false;
+    let mut add_n_step = true;
+
+    let return_q_0 = if give_log_q {
+        [ML_NEGINF, 0.0]
+    } else {
+        [0.0, 1.0]
+    };
+    let return_q_1 = if give_log_q {
+        [0.0, ML_NEGINF]
+    } else {
+        [1.0, 0.0]
+    };
+    let return_q_half = if give_log_q {
+        [-M_LN2, -M_LN2]
+    } else {
+        [0.5, 0.5]
+    };
+
+    // Assuming p >= 0, q >= 0  here ...
+
+    // Deal with boundary cases here:
+    if alpha == r_dt_0(lower_tail, log_p) {
+        return return_q_0;
+    }
+    if alpha == r_dt_1(lower_tail, log_p) {
+        return return_q_1;
+    }
+
+    // check alpha {*before* transformation which may all accuracy}:
+    if (log_p && alpha > 0.0) || (!log_p && (alpha < 0.0 || alpha > 1.0)) {
+        // alpha is outside
+        ml_warn_return_nan();
+        return [ML_NAN, ML_NAN];
+    }
+
+    //  p==0, q==0, p = Inf, q = Inf  <==> treat as one- or two-point mass
+    if p == 0.0 || q == 0.0 || !r_finite(p) || !r_finite(q) {
+        // We know 0 < T(alpha) < 1 : pbeta() is constant and trivial in {0, 1/2, 1}
+        if p == 0.0 && q == 0.0 {
+            // point mass 1/2 at each of {0,1} :
+            let r_d_half = if log_p { -M_LN2 } else { 0.5 };
+            if alpha < r_d_half {
+                return return_q_0;
+            }
+            if alpha > r_d_half {
+                return return_q_1;
+            }
+            // else:  alpha == "1/2"
+            return return_q_half;
+        } else if p == 0.0 || p / q == 0.0 {
+            // point mass 1 at 0 - "flipped around"
+            return return_q_0;
+        } else if q == 0.0 || q / p == 0.0 {
+            // point mass 1 at 0 - "flipped around"
+            return return_q_1;
+        }
+        // else:  p = q = Inf : point mass 1 at 1/2
+        return return_q_half;
+    }
+
+    // initialize
+    // lower_tail prob (in any case)
+    // Conceptually,  0 < p_ < 1  (but can be 0 or 1 because of cancellation!)
+    let p_ = r_dt_qiv(alpha, lower_tail, log_p);
+    let logbeta = lbeta(p, q);
+
+    let mut swap_tail = match swap_01 {
+        None => p_ > 0.5,
+        Some(swap_01) => swap_01,
+    };
+    // change tail; default (swap_01 = NA): afterwards 0 < a <= 1/2
+    let mut a;
+    let mut la;
+    let mut pp;
+    let mut qq;
+    if swap_tail {
+        // change tail, swap  p <-> q :
+        a = r_dt_civ(alpha, lower_tail, log_p); // = 1 - p_ < 1/2
+
+        // la := log(a), but without numerical cancellation:
+        la = r_dt_clog(alpha, lower_tail, log_p);
+        pp = q;
+        qq = p;
+    } else {
+        a = p_;
+        la = r_dt_log(alpha, lower_tail, log_p);
+        pp = p;
+        qq = q;
+    }
+
+    // calculate the initial approximation
+
+    // Desired accuracy for Newton iterations (below) should depend on  (a,p)
+    // This is from Remark .. on AS 109, adapted.
+    // However, it's not clear if this is "optimal" for IEEE double prec.
+    //
+    // acu = fmax2(acu_min, pow(10., -25. - 5./(pp * pp) - 1./(a * a)));
+    //
+    // NEW: 'acu' accuracy NOT for squared adjustment, but simple;
+    // ---- i.e.,  "new acu" = sqrt(old acu)
+    let acu = fmax2(ACU_MIN, 10f64.powf(-13.0 - 2.5 / (pp * pp) - 0.5 / (a * a)));
+    // try to catch  "extreme left tail" early
+    let mut tx;
+    let u0 = (la + pp.ln() + logbeta) / pp; // = log(x_0)
+    let log_eps_c = M_LN2 * (1.0 - DBL_MANT_DIG as f64); // = log(DBL_EPSILON) = -36.04..
+    let mut r = pp * (1.0 - qq) / (pp + 1.0);
+
+    let mut t = 0.2;
+    // FIXME: Factor 0.2 is a bit arbitrary;  '1' is clearly much too much.
+
+    let mut u;
+    let mut xinbta;
+    let mut u_n = 1.0; // -Wall
+    let mut y = -1.0;
+    let mut w;
+
+    // Whether to skip the Newton iterations, i.e., `goto L_return` in C.
+    let mut skip_newton = false;
+
+    if M_LN2 * DBL_MIN_EXP < u0 // cannot allow exp(u0) = 0 ==> exp(u1) = exp(u0) = 0
+        && u0 < -0.01 // (must: u0 < 0, but too close to 0 <==> x = exp(u0) = 0.99..)
+        // qq <= 2 && // <--- "arbitrary"
+        // u0 <  t*log_eps_c - log(fabs(r)) &&
+        && u0
+            < (t * log_eps_c - (pp * (1.0 - qq) * (2.0 - qq) / (2.0 * (pp + 2.0))).abs().ln())
+                / 2.0
+    {
+        // TODO: maybe jump here from below, when initial u "fails" ?
+        // L_tail_u:
+        // MM's one-step correction (cheaper than 1 Newton!)
+        r *= u0.exp(); // = r*x0
+        if r > -1.0 {
+            u = u0 - r.ln_1p() / pp;
+        } else {
+            u = u0;
+        }
+        xinbta = u.exp();
+        tx = xinbta;
+        use_log_x = true; // or (u < log_q_cut)  ??
+
+    // goto L_Newton;
+    } else {
+        // y := y_\alpha in AS 64 := Hastings(1955) approximation of qnorm(1 - a) :
+        r = (-2.0 * la).sqrt();
+        y = r - (CONST1 + CONST2 * r) / (1.0 + (CONST3 + CONST4 * r) * r);
+
+        if pp > 1.0 && qq > 1.0 {
+            // use  Carter(1947), see AS 109, remark '5.'
+            r = (y * y - 3.0) / 6.0;
+            let s = 1.0 / (pp + pp - 1.0);
+            t = 1.0 / (qq + qq - 1.0);
+            let h = 2.0 / (s + t);
+            w = y * (h + r).sqrt() / h - (t - s) * (r + 5.0 / 6.0 - 2.0 / (3.0 * h));
+            if w > 300.0 {
+                // exp(w+w) is huge or overflows
+                t = w + w + qq.ln() - pp.ln(); // = argument of log1pexp(.)
+
+                // log(xinbta) = - log1p(qq/pp * exp(w+w)) = -log(1 + exp(t))
+                u = if t <= 18.0 {
+                    -t.exp().ln_1p()
+                } else {
+                    -t - (-t).exp()
+                };
+                xinbta = u.exp();
+            } else {
+                xinbta = pp / (pp + qq * (w + w).exp());
+                // log(xinbta)
+                u = -(qq / pp * (w + w).exp()).ln_1p();
+            }
+        } else {
+            // use the original AS 64 proposal, Scheffé-Tukey (1944) and Wilson-Hilferty
+            r = qq + qq;
+            // A slightly more stable version of  t := \chi^2_{alpha} of AS 64
+            // t = 1. / (9. * qq); t = r * R_pow_di(1. - t + y * sqrt(t), 3);
+            t = 1.0 / (3.0 * qq.sqrt());
+            t = r * (1.0 + t * (-t + y)).powi(3); // = \chi^2_{alpha} of AS 64
+            let s = 4.0 * pp + r - 2.0; // 4p + 2q - 2 = numerator of new t = (...) / chi^2
+            if t == 0.0 || (t < 0.0 && s >= t) {
+                // cannot use chisq approx
+                // x0 = 1 - { (1-a)*q*B(p,q) } ^{1/q}    {AS 65}
+                // xinbta = 1. - exp((log(1-a)+ log(qq) + logbeta) / qq);
+                // := log(1-a), directly from alpha (as 'la' above):
+                // FIXME: not worth it? log1p(-a) always the same ??
+                let l1ma = if swap_tail {
+                    r_dt_log(alpha, lower_tail, log_p)
+                } else {
+                    r_dt_clog(alpha, lower_tail, log_p)
+                };
+                let xx = (l1ma + qq.ln() + logbeta) / qq;
+                if xx <= 0.0 {
+                    xinbta = -xx.exp_m1();
+                    u = r_log1_exp(xx); // =  log(xinbta) = log(1 - exp(...A...))
+                } else {
+                    // xx > 0 ==> 1 - e^xx < 0 .. is nonsense
+                    xinbta = 0.0;
+                    u = ML_NEGINF; // FIXME can do better?
+                }
+            } else {
+                t = s / t;
+                if t <= 1.0 {
+                    // cannot use chisq, either
+                    u = (la + pp.ln() + logbeta) / pp;
+                    xinbta = u.exp();
+                } else {
+                    // (1+x0)/(1-x0) = t,  solved for x0 :
+                    xinbta = 1.0 - 2.0 / (t + 1.0);
+                    u = (-2.0 / (t + 1.0)).ln_1p();
+                }
+            }
+        }
+
+        // Problem: If initial u is completely wrong, we make a wrong decision here
+        if swap_choose
+            && ((swap_tail && u >= -log_q_cut.exp()) // ==> "swap back"
+                || (!swap_tail && u >= -(4.0 * log_q_cut).exp() && pp / qq < 1000.0))
+        // ==> "swap now"
+        {
+            // "revert swap" -- and use_log_x
+            swap_tail = !swap_tail;
+            if swap_tail {
+                // "swap now" (much less easily)
+                a = r_dt_civ(alpha, lower_tail, log_p); // needed ?
+                la = r_dt_clog(alpha, lower_tail, log_p);
+                pp = q;
+                qq = p;
+            } else {
+                // swap back :
+                a = p_;
+                la = r_dt_log(alpha, lower_tail, log_p);
+                pp = p;
+                qq = q;
+            }
+            // we could redo computations above, but this should be stable
+            u = r_log1_exp(u);
+            xinbta = u.exp();
+
+            // Careful: "swap now"  should not fail if
+            // 1) the above initial xinbta is "completely wrong"
+            // 2) The correction step can go outside (u_n > 0 ==>  e^u > 1 is illegal)
+            // e.g., for  qbeta(0.2066, 0.143891, 0.05)
+        }
+
+        if !use_log_x {
+            use_log_x = u < log_q_cut; // <==> xinbta = e^u < exp(log_q_cut)
+        }
+        let bad_u = !r_finite(u);
+        let bad_init = bad_u || xinbta > P_HI;
+
+        tx = xinbta; // keeping "original initial x" (for now)
+
+        if bad_u || u < log_q_cut {
+            // e.g.
+            // qbeta(0.21, .001, 0.05)
+            // try "left border" quickly, i.e.,
+            // try at smallest positive number:
+            w = pbeta_raw(DBL_VERY_MIN, pp, qq, true, log_p);
+            if w > (if log_p { la } else { a }) {
+                if log_p || (w - a).abs() < (0.0 - a).abs() {
+                    // DBL_very_MIN is better than 0
+                    tx = DBL_VERY_MIN;
+                    u_n = DBL_LOG_V_MIN; // = log(DBL_very_MIN)
+                } else {
+                    tx = 0.0;
+                    u_n = ML_NEGINF;
+                }
+                use_log_x = log_p;
+                add_n_step = false;
+                skip_newton = true; // goto L_return;
+            } else if u < DBL_LOG_V_MIN {
+                u = DBL_LOG_V_MIN; // = log(DBL_very_MIN)
+                xinbta = DBL_VERY_MIN;
+            }
+        }
+
+        // Sometimes the approximation is negative (and == 0 is also not "ok")
+        if !skip_newton && bad_init && !(use_log_x && tx > 0.0) {
+            if u == ML_NEGINF {
+                u = M_LN2 * DBL_MIN_EXP;
+                xinbta = DBL_MIN;
+            } else {
+                xinbta = if xinbta > 1.1 {
+                    // i.e. "way off"
+                    0.5 // otherwise, keep the respective boundary:
+                } else if xinbta < P_LO {
+                    u.exp()
+                } else {
+                    P_HI
+                };
+                if bad_u {
+                    u = xinbta.ln();
+                }
+                // otherwise: not changing "potentially better" u than the above
+            }
+        }
+    }
+
+    // L_Newton: (also needed for the final Newton step at L_return)
+    r = 1.0 - pp;
+    t = 1.0 - qq;
+
+    if !skip_newton {
+        // L_Newton:
+        // --------------------------------------------------------------------
+        // Solve for x by a modified Newton-Raphson method, using pbeta_raw()
+        let mut wprev = 0.0;
+        let mut prev = 1.0;
+        let mut adj: f64 = 1.0; // -Wall
+        let mut converged = false;
+
+        if use_log_x {
+            // find  log(xinbta) -- work in  u := log(x) scale
+            // if(bad_init && tx > 0) xinbta = tx;// may have been better
+
+            'log_newton: for i_pb in 0..1000 {
+                // using log_p == TRUE  unconditionally here
+                // FIXME: if exp(u) = xinbta underflows to 0,
+                //  want different formula pbeta_log(u, ..)
+                y = pbeta_raw(xinbta, pp, qq, true, true);
+
+                // w := Newton step size for   L(u) = log F(e^u)  =!= 0;   u := log(x)
+                //   =  (L(.) - la) / L'(.);  L'(u)= (F'(e^u) * e^u ) / F(e^u)
+                //   =  (L(.) - la)*F(.) / {F'(e^u) * e^u } =
+                //   =  (L(.) - la) * e^L(.) * e^{-log F'(e^u) - u}
+                //   =  ( y   - la) * e^{ y - u -log F'(e^u)}
+                // and  -log F'(x)= -log f(x) = - -logbeta + (1-p) log(x) + (1-q) log(1-x)
+                //                            = logbeta + (1-p) u + (1-q) log(1-e^u)
+                w = if y == ML_NEGINF {
+                    // y = -Inf  well possible: we are on log scale!
+                    0.0
+                } else {
+                    (y - la) * (y - u + logbeta + r * u + t * r_log1_exp(u)).exp()
+                };
+                if !r_finite(w) {
+                    break;
+                }
+                if i_pb >= n_n && w * wprev <= 0.0 {
+                    prev = fmax2(adj.abs(), FPU);
+                }
+                let mut g = 1.0;
+                for _ in 0..1000 {
+                    adj = g * w;
+                    // safe guard (here, from the very beginning)
+                    if adj.abs() < prev {
+                        u_n = u - adj; // u_{n+1} = u_n - g*w
+                        if u_n <= 0.0 {
+                            // <==> 0 <  xinbta := e^u  <= 1
+                            if prev <= acu || w.abs() <= acu {
+                                converged = true;
+                                break 'log_newton;
+                            }
+                            // if (u_n != ML_NEGINF && u_n != 1)
+                            break;
+                        }
+                    }
+                    g /= 3.0;
+                }
+                // (cancellation in (u_n -u) => may differ from adj:
+                let d = fmin2(adj.abs(), (u_n - u).abs());
+                if d <= 4e-16 * (u_n + u).abs() {
+                    converged = true;
+                    break;
+                }
+                u = u_n;
+                xinbta = u.exp();
+                wprev = w;
+            } // for(i )
+        } else {
+            // "normal scale" Newton
+
+            'newton: for i_pb in 0..1000 {
+                y = pbeta_raw(xinbta, pp, qq, true, log_p);
+                // delta{y} :   d_y = y - (log_p ? la : a);
+                if !(r_finite(y) || (log_p && y == ML_NEGINF)) {
+                    // y = -Inf  is ok if(log_p)
+                    ml_warn_return_nan();
+                    return [ML_NAN, ML_NAN];
+                }
+
+                // w := Newton step size  (F(.) - a) / F'(.)  or,
+                // --   log: (lF - la) / (F' / F) = exp(lF) * (lF - la) / F'
+                w = if log_p {
+                    (y - la) * (y + logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+                } else {
+                    (y - a) * (logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+                };
+                if i_pb >= n_n && w * wprev <= 0.0 {
+                    prev = fmax2(adj.abs(), FPU);
+                }
+                let mut g = 1.0;
+                for _ in 0..1000 {
+                    adj = g * w;
+                    // take full Newton steps at the beginning; only then safe guard:
+                    if i_pb < n_n || adj.abs() < prev {
+                        tx = xinbta - adj; // x_{n+1} = x_n - g*w
+                        if 0.0 <= tx && tx <= 1.0 {
+                            if prev <= acu || w.abs() <= acu {
+                                converged = true;
+                                break 'newton;
+                            }
+                            if tx != 0.0 && tx != 1.0 {
+                                break;
+                            }
+                        }
+                    }
+                    g /= 3.0;
+                }
+                if (tx - xinbta).abs() <= 4e-16 * (tx + xinbta) {
+                    // "<=" : (.) == 0
+                    converged = true;
+                    break;
+                }
+                xinbta = tx;
+                if tx == 0.0 {
+                    // "we have lost"
+                    break;
+                }
+                wprev = w;
+            } // for( i_pb ..)
+        } // end{else : normal scale Newton}
+
+        if !converged {
+            //-- NOT converged: Iteration count --
+            warned = true;
+            ml_warning(MlWarningKind::Precision, "qbeta");
+        }
+
+        // L_converged:
+        let log_ = log_p || use_log_x; // only for printing
+        if (log_ && y == ML_NEGINF) || (!log_ && y == 0.0) {
+            // stuck at left, try if smallest positive number is "better"
+            w = pbeta_raw(DBL_VERY_MIN, pp, qq, true, log_);
+            if log_ || (w - a).abs() <= (y - a).abs() {
+                tx = DBL_VERY_MIN;
+                u_n = DBL_LOG_V_MIN; // = log(DBL_very_MIN)
+            }
+            add_n_step = false; // not trying to do better anymore
+        } else if !warned
+            && (if log_ {
+                (y - la).abs() > 3.0
+            } else {
+                (y - a).abs() > 1e-4
+            })
+            && !(log_
+                && y == ML_NEGINF
+                // e.g. qbeta(-1e-10, .2, .03, log=TRUE) cannot get accurate ==> do NOT warn
+                && pbeta_raw(DBL_1__EPS, pp, qq, true, true) > la + 2.0)
+        {
+            // x0 is not accurate
+            ml_warning(MlWarningKind::Precision, "qbeta");
+        }
+    }
+
+    // L_return:
+    if give_log_q {
+        // ==> use_log_x , too
+        if !use_log_x {
+            // (see if claim above is true)
+            ml_warning(MlWarningKind::Precision, "qbeta");
+        }
+        let r = r_log1_exp(u_n);
+        if swap_tail {
+            [r, u_n]
+        } else {
+            [u_n, r]
+        }
+    } else {
+        if use_log_x {
+            if add_n_step {
+                // add one last Newton step on original x scale, e.g., for
+                // qbeta(2^-98, 0.125, 2^-96)
+                xinbta = u_n.exp();
+                y = pbeta_raw(xinbta, pp, qq, true, log_p);
+                w = if log_p {
+                    (y - la) * (y + logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+                } else {
+                    (y - a) * (logbeta + r * xinbta.ln() + t * (-xinbta).ln_1p()).exp()
+                };
+                tx = xinbta - w;
+            } else if swap_tail {
+                return [-u_n.exp_m1(), u_n.exp()];
+            } else {
+                return [u_n.exp(), -u_n.exp_m1()];
+            }
+        }
+        if swap_tail {
+            [1.0 - tx, tx]
+        } else {
+            [tx, 1.0 - tx]
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::fmax2::fmax2;
+use crate::fmin2::fmin2;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::pbinom::pbinom;
+use crate::qnorm::qnorm5;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2009 The R Core Team
+//  Copyright (C) 2003-2009 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Search from `y` in steps of `incr` for the smallest value whose
+/// distribution function is at least `p`.
+///
+/// On entry `z` holds `pbinom(y, ..)`; on return it holds the value at the
+/// returned quantile.
+fn do_search(y: f64, z: &mut f64, p: f64, n: f64, pr: f64, incr: f64) -> f64 {
+    let mut y = y;
+    if *z >= p {
+        // search to the left
+        loop {
+            if y == 0.0 {
+                return y;
+            }
+            let newz = pbinom(y - incr, n, pr, true, false);
+            if newz < p {
+                return y;
+            }
+            y = fmax2(0.0, y - incr);
+            *z = newz;
+        }
+    } else {
+        // search to the right
+        loop {
+            y = fmin2(y + incr, n);
+            if y == n {
+                return y;
+            }
+            *z = pbinom(y, n, pr, true, false);
+            if *z >= p {
+                return y;
+            }
+        }
+    }
+}
+
+/// The quantile function of the binomial distribution.
+///
+/// Uses the Cornish-Fisher Expansion to include a skewness
+/// correction to a normal approximation.  This gives an
+/// initial value which never seems to be off by more than
+/// 1 or 2.  A search is then conducted of values close to
+/// this initial start point.
+pub fn qbinom(p: f64, n: f64, pr: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || n.is_nan() || pr.is_nan() {
+        return p + n + pr;
+    }
+    if !r_finite(n) || !r_finite(pr) {
+        return ml_warn_return_nan();
+    }
+    // if log_p is true, p = -Inf is a legitimate value
+    if !r_finite(p) && !log_p {
+        return ml_warn_return_nan();
+    }
+
+    if n != (n + 0.5).floor() {
+        return ml_warn_return_nan();
+    }
+    if pr < 0.0 || pr > 1.0 || n < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, n, lower_tail, log_p) {
+        return x;
+    }
+
+    if pr == 0.0 || n == 0.0 {
+        return 0.0;
+    }
+
+    let q = 1.0 - pr;
+    if q == 0.0 {
+        // covers the full range of the distribution
+        return n;
+    }
+    let mu = n * pr;
+    let sigma = (n * pr * q).sqrt();
+    let gamma = (q - pr) / sigma;
+
+    // Note : "same" code in qpois.c, qbinom.c, qnbinom.c --
+    // FIXME: This is far from optimal [cancellation for p ~= 1, etc]:
+    let mut p = p;
+    if !lower_tail || log_p {
+        // need check again (cancellation!):
+        p = r_dt_qiv(p, lower_tail, log_p);
+        if p == 0.0 {
+            return 0.0;
+        }
+        if p == 1.0 {
+            return n;
+        }
+    }
+    // temporary hack --- FIXME ---
+    if p + 1.01 * DBL_EPSILON >= 1.0 {
+        return n;
+    }
+
+    // y := approx.value (Cornish-Fisher expansion) :
+    let mut z = qnorm5(p, 0.0, 1.0, true, false);
+    let mut y = (mu + sigma * (z + gamma * (z * z - 1.0) / 6.0) + 0.5).floor();
+
+    if y > n {
+        // way off
+        y = n;
+    }
+
+    z = pbinom(y, n, pr, true, false);
+
+    // fuzz to ensure left continuity:
+    p *= 1.0 - 64.0 * DBL_EPSILON;
+
+    if n < 1e5 {
+        return do_search(y, &mut z, p, n, pr, 1.0);
+    }
+    // Otherwise be a bit cleverer in the search
+    let mut incr = (n * 0.001).floor();
+    loop {
+        let oldincr = incr;
+        y = do_search(y, &mut z, p, n, pr, incr);
+        incr = fmax2(1.0, (incr / 100.0).floor());
+        if !(oldincr > 1.0 && incr > n * 1e-15) {
+            break;
+        }
+    }
+    y
+}
@@ -0,0 +1,82 @@
+use crate::cospi::tanpi;
+use crate::dpq::r_q_p01_check;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998    Ross Ihaka
+//  Copyright (C) 2000-2013 The R Core Team
+//  Copyright (C) 2005-6  The R Foundation
+//
+//  This version is based on a suggestion by Morten Welinder.
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the Cauchy distribution.
+pub fn qcauchy(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() {
+        return p + location + scale;
+    }
+    if let Some(x) = r_q_p01_check(p, log_p) {
+        return x;
+    }
+    if scale <= 0.0 || !r_finite(scale) {
+        if scale == 0.0 {
+            return location;
+        }
+        return ml_warn_return_nan();
+    }
+
+    let my_inf =
+        |lower_tail: bool| location + (if lower_tail { scale } else { -scale }) * ML_POSINF;
+    let mut p = p;
+    let mut lower_tail = lower_tail;
+    if log_p {
+        if p > -1.0 {
+            // when ep := exp(p),
+            // tan(pi*ep)= -tan(pi*(-ep))= -tan(pi*(-ep)+pi) = -tan(pi*(1-ep)) =
+            //           = -tan(pi*(-expm1(p))
+            // for p ~ 0, exp(p) ~ 1, tan(~0) may be better than tan(~pi).
+            if p == 0.0 {
+                // needed, since 1/tan(-0) = -Inf  for some arch.
+                return my_inf(lower_tail);
+            }
+            lower_tail = !lower_tail;
+            p = -p.exp_m1();
+        } else {
+            p = p.exp();
+        }
+    } else if p > 0.5 {
+        if p == 1.0 {
+            return my_inf(lower_tail);
+        }
+        p = 1.0 - p;
+        lower_tail = !lower_tail;
+    }
+
+    if p == 0.5 {
+        // avoid 1/Inf below
+        return location;
+    }
+    if p == 0.0 {
+        // p = 1. is handled above
+        return location + (if lower_tail { scale } else { -scale }) * ML_NEGINF;
+    }
+    // -1/tan(pi * p) = -cot(pi * p) = tan(pi * (p - 1/2))
+    location + (if lower_tail { -scale } else { scale }) / tanpi(p)
+}
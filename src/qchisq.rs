@@ -0,0 +1,6 @@
+use crate::qgamma::qgamma;
+
+/// The quantile function of the chi-squared distribution.
+pub fn qchisq(p: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
+    qgamma(p, 0.5 * df, 2.0, lower_tail, log_p)
+}
@@ -0,0 +1,42 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_clog;
+use crate::dpq::r_q_p01_check;
+use crate::nmath::ml_warn_return_nan;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the exponential distribution with mean `scale`.
+pub fn qexp(p: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || scale.is_nan() {
+        return p + scale;
+    }
+    if scale < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(x) = r_q_p01_check(p, log_p) {
+        return x;
+    }
+    if p == r_dt_0(lower_tail, log_p) {
+        return 0.0;
+    }
+
+    -scale * r_dt_clog(p, lower_tail, log_p)
+}
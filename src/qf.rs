@@ -0,0 +1,64 @@
+use crate::dpq::r_q_p01_boundaries;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_NAN;
+use crate::nmath::ML_POSINF;
+use crate::qbeta::qbeta;
+use crate::qchisq::qchisq;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2015 The R Core Team
+//  Copyright (C) 2005 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the F distribution.
+pub fn qf(p: f64, df1: f64, df2: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || df1.is_nan() || df2.is_nan() {
+        return p + df1 + df2;
+    }
+    if df1 <= 0.0 || df2 <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    // fudge the extreme DF cases -- qbeta doesn't do this well.
+    // But we still need to fudge the infinite ones.
+
+    if df1 <= df2 && df2 > 4e5 {
+        if !r_finite(df1) {
+            // df1 == df2 == Inf :
+            return 1.0;
+        }
+        return qchisq(p, df1, lower_tail, log_p) / df1;
+    }
+    if df1 > 4e5 {
+        // and so  df2 < df1
+        return df2 / qchisq(p, df2, !lower_tail, log_p);
+    }
+
+    // FIXME: (1/qb - 1) = (1 - qb)/qb; if we know qb ~= 1, should use other tail
+    let p = (1.0 / qbeta(p, df2 / 2.0, df1 / 2.0, !lower_tail, log_p) - 1.0) * (df2 / df1);
+    if !p.is_nan() {
+        p
+    } else {
+        ML_NAN
+    }
+}
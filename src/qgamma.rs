@@ -0,0 +1,269 @@
+use crate::dgamma::dgamma;
+use crate::dpq::r_d__0;
+use crate::dpq::r_dt_clog;
+use crate::dpq::r_dt_log;
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::dpq::r_q_p01_check;
+use crate::lgamma::lgammafn;
+use crate::libc::DBL_MIN;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+use crate::pgamma::lgamma1p;
+use crate::pgamma::pgamma;
+use crate::pgamma::pgamma_raw;
+use crate::qnorm::qnorm5;
+use crate::rmath::M_LN2;
+
+const C7: f64 = 4.67;
+const C8: f64 = 6.66;
+const C9: f64 = 6.73;
+const C10: f64 = 13.32;
+
+/// Starting approximation for the chi-squared quantile, used by `qgamma`.
+///
+/// `g` is `log Gamma(nu/2)` and `tol` the tolerance of the iteration for
+/// "small nu".
+pub fn qchisq_appr(p: f64, nu: f64, g: f64, lower_tail: bool, log_p: bool, tol: f64) -> f64 {
+    if p.is_nan() || nu.is_nan() {
+        return p + nu;
+    }
+    if let Some(x) = r_q_p01_check(p, log_p) {
+        return x;
+    }
+    if nu <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    let alpha = 0.5 * nu; // = [pq]gamma() shape
+    let c = alpha - 1.0;
+
+    let p1 = r_dt_log(p, lower_tail, log_p);
+    let mut ch;
+    if nu < (-1.24) * p1 {
+        // for small chi-squared
+        // log(alpha) + g = log(alpha) + log(gamma(alpha)) =
+        //        = log(alpha*gamma(alpha)) = lgamma(alpha+1) suffers from
+        //  catastrophic cancellation when alpha << 1
+        let lgam1pa = if alpha < 0.5 {
+            lgamma1p(alpha)
+        } else {
+            alpha.ln() + g
+        };
+        ch = ((lgam1pa + p1) / alpha + M_LN2).exp();
+    } else if nu > 0.32 {
+        // using Wilson and Hilferty estimate
+        let x = qnorm5(p, 0.0, 1.0, lower_tail, log_p);
+        let p1 = 2.0 / (9.0 * nu);
+        ch = nu * (x * p1.sqrt() + 1.0 - p1).powi(3);
+
+        // approximation for p tending to 1:
+        if ch > 2.2 * nu + 6.0 {
+            ch = -2.0 * (r_dt_clog(p, lower_tail, log_p) - c * (0.5 * ch).ln() + g);
+        }
+    } else {
+        // "small nu" : 1.24*(-log(p)) <= nu <= 0.32
+        ch = 0.4;
+        let a = r_dt_clog(p, lower_tail, log_p) + g + c * M_LN2;
+        loop {
+            let q = ch;
+            let p1 = 1.0 / (1.0 + ch * (C7 + ch));
+            let p2 = ch * (C9 + ch * (C8 + ch));
+            let t = -0.5 + (C7 + 2.0 * ch) * p1 - (C9 + ch * (C10 + 3.0 * ch)) / p2;
+            ch -= (1.0 - (a + 0.5 * ch).exp() * p2 * p1) / t;
+            if !((q - ch).abs() > tol * ch.abs()) {
+                break;
+            }
+        }
+    }
+
+    ch
+}
+
+/// Compute the quantile function of the gamma distribution.
+///
+/// ## NOTES
+///
+/// This function is based on the Applied Statistics
+/// Algorithm AS 91 ("ppchi2") and via pgamma(.) AS 239.
+///
+/// R core improvements:
+/// - lower_tail, log_p
+/// - non-trivial result for p outside \[0.000002, 0.999998\]
+/// - p ~ 1 no longer gives +Inf; final Newton step(s)
+///
+/// ## REFERENCES
+///
+/// Best, D. J. and D. E. Roberts (1975).
+/// Percentage Points of the Chi-Squared Distribution.
+/// Applied Statistics 24, page 385.
+pub fn qgamma(p: f64, alpha: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const EPS1: f64 = 1e-2;
+    const EPS_N: f64 = 1e-15; // precision of Newton step / iterations
+
+    if p.is_nan() || alpha.is_nan() || scale.is_nan() {
+        return p + alpha + scale;
+    }
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    if alpha < 0.0 || scale <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if alpha == 0.0 {
+        // all mass at 0 :
+        return 0.0;
+    }
+
+    let max_it_newton = if alpha < 1e-10 {
+        7 // may still be increased below
+    } else {
+        1
+    };
+
+    let mut p_ = r_dt_qiv(p, lower_tail, log_p); // lower_tail prob (in any case)
+
+    let mut g = lgammafn(alpha); // log Gamma(v/2)
+
+    // ----- Phase I : Starting Approximation
+    let ch = qchisq_appr(p, 2.0 * alpha, g, lower_tail, log_p, EPS1);
+
+    // The C code uses `goto END` to skip to the final Newton steps.
+    // Here, phase II is a separate function which returns early instead.
+    let (ch, max_it_newton) = qgamma_phase2(ch, p_, alpha, g, max_it_newton);
+
+    // PR# 2214 :	 From: Morten Welinder <terra@diku.dk>, Fri, 25 Oct 2002 16:50
+    // --------	 To: R-bugs@biostat.ku.dk     Subject: qgamma precision
+    //
+    // With a final Newton step, double accuracy, e.g. for (p= 7e-4; nu= 0.9)
+    //
+    // Improved (MM): - only if rel.Err > EPS_N (= 1e-15);
+    //  - also for lower_tail = FALSE	 or log_p = TRUE
+    //  - optionally *iterate* Newton
+    let mut x = 0.5 * scale * ch;
+    if max_it_newton > 0 {
+        // always use log scale
+        let (p, log_p) = if log_p { (p, log_p) } else { (p.ln(), true) };
+        if x == 0.0 {
+            const _1_P: f64 = 1.0 + 1e-7;
+            const _1_M: f64 = 1.0 - 1e-7;
+            x = DBL_MIN;
+            p_ = pgamma(x, alpha, scale, lower_tail, log_p);
+            if (lower_tail && p_ > p * _1_P) || (!lower_tail && p_ < p * _1_M) {
+                return 0.0;
+            }
+            // else:  continue, using x = DBL_MIN instead of  0
+        } else {
+            p_ = pgamma(x, alpha, scale, lower_tail, log_p);
+        }
+        if p_ == ML_NEGINF {
+            return 0.0; // PR#14710
+        }
+        for i in 1..=max_it_newton {
+            let p1 = p_ - p;
+            if p1.abs() < (EPS_N * p).abs() {
+                break;
+            }
+            // else
+            g = dgamma(x, alpha, scale, log_p);
+            if g == r_d__0(log_p) {
+                break;
+            }
+            // else :
+            // delta x = f(x)/f'(x);
+            // if(log_p) f(x) := log P(x) - p; f'(x) = d/dx log P(x) = P' / P
+            // ==> f(x)/f'(x) = f*P / P' = f*exp(p_) / P' (since p_ = log P(x))
+            let t = if log_p { p1 * (p_ - g).exp() } else { p1 / g }; // = "delta x"
+            let t = if lower_tail { x - t } else { x + t };
+            p_ = pgamma(t, alpha, scale, lower_tail, log_p);
+            if (p_ - p).abs() > p1.abs() || (i > 1 && (p_ - p).abs() == p1.abs())
+            // <- against flip-flop
+            {
+                // no improvement
+                break;
+            } // else :
+            x = t;
+        }
+    }
+
+    x
+}
+
+/// Phase II of `qgamma`: the AS 91 iteration using `pgamma_raw` and a seven
+/// term Taylor series.
+///
+/// Returns the (possibly improved) approximation `ch` and the number of
+/// Newton steps which should still be done on it.
+fn qgamma_phase2(mut ch: f64, p_: f64, alpha: f64, g: f64, max_it_newton: i32) -> (f64, i32) {
+    const EPS2: f64 = 5e-7; // final precision of AS 91
+    const MAXIT: i32 = 1000; // was 20
+
+    const P_MIN: f64 = 1e-100; // was 0.000002 = 2e-6
+    const P_MAX: f64 = 1.0 - 1e-14; // was (1-1e-12) and 0.999998 = 1 - 2e-6
+
+    const I420: f64 = 1.0 / 420.0;
+    const I2520: f64 = 1.0 / 2520.0;
+    const I5040: f64 = 1.0 / 5040.0;
+
+    if !r_finite(ch) {
+        // forget about all iterations!
+        return (ch, 0);
+    }
+    if ch < EPS2 {
+        // Corrected according to AS 91; MM, May 25, 1999
+        return (ch, 20); // and do Newton steps
+    }
+
+    // FIXME: This (cutoff to {0, +Inf}) is far from optimal
+    // -----  when log_p or !lower_tail, but NOT doing it can be even worse
+    if p_ > P_MAX || p_ < P_MIN {
+        // did return ML_POSINF or 0.;	much better:
+        return (ch, 20); // and do Newton steps
+    }
+
+    // ----- Phase II: Iteration
+    // Call pgamma() [AS 239] and calculate seven term taylor series
+    let c = alpha - 1.0;
+    let s6 = (120.0 + c * (346.0 + 127.0 * c)) * I5040; // used below, is "const"
+
+    let ch0 = ch; // save initial approx.
+    for _ in 1..=MAXIT {
+        let q = ch;
+        let p1 = 0.5 * ch;
+        let p2 = p_ - pgamma_raw(p1, alpha, true, false);
+        if !r_finite(p2) || ch <= 0.0 {
+            return (ch0, 27);
+        } // was  return ML_NAN;
+
+        let t = p2 * (alpha * M_LN2 + g + p1 - c * ch.ln()).exp();
+        let b = t / ch;
+        let a = 0.5 * t - b * c;
+        let s1 = (210.0 + a * (140.0 + a * (105.0 + a * (84.0 + a * (70.0 + 60.0 * a))))) * I420;
+        let s2 = (420.0 + a * (735.0 + a * (966.0 + a * (1141.0 + 1278.0 * a)))) * I2520;
+        let s3 = (210.0 + a * (462.0 + a * (707.0 + 932.0 * a))) * I2520;
+        let s4 =
+            (252.0 + a * (672.0 + 1182.0 * a) + c * (294.0 + a * (889.0 + 1740.0 * a))) * I5040;
+        let s5 = (84.0 + 2264.0 * a + c * (1175.0 + 606.0 * a)) * I2520;
+
+        ch += t
+            * (1.0 + 0.5 * t * s1
+                - b * c * (s1 - b * (s2 - b * (s3 - b * (s4 - b * (s5 - b * s6))))));
+        if (q - ch).abs() < EPS2 * ch {
+            return (ch, max_it_newton);
+        }
+        if (q - ch).abs() > 0.1 * ch {
+            // diverging? -- also forces ch > 0
+            if ch < q {
+                ch = 0.9 * q;
+            } else {
+                ch = 1.1 * q;
+            }
+        }
+    }
+    // no convergence in MAXIT iterations -- but we add Newton now...
+    (ch, max_it_newton)
+}
@@ -0,0 +1,52 @@
+use crate::dpq::r_dt_clog;
+use crate::dpq::r_q_p01_boundaries;
+use crate::dpq::r_q_p01_check;
+use crate::fmax2::fmax2;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::ML_POSINF;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 	     Ross Ihaka
+//  Copyright (C) 2000--2016 The R Core Team
+//  Copyright (C) 2004--2016 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the geometric distribution.
+pub fn qgeom(p: f64, prob: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || prob.is_nan() {
+        return p + prob;
+    }
+    if prob <= 0.0 || prob > 1.0 {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(x) = r_q_p01_check(p, log_p) {
+        return x;
+    }
+    if prob == 1.0 {
+        return 0.0;
+    }
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    // add a fuzz to ensure left continuity, but value must be >= 0
+    fmax2(
+        0.0,
+        (r_dt_clog(p, lower_tail, log_p) / (-prob).ln_1p() - 1.0 - 1e-12).ceil(),
+    )
+}
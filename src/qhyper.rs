@@ -0,0 +1,95 @@
+use crate::choose::lfastchoose;
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::fmax2::fmax2;
+use crate::fmin2::fmin2;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2014 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the hypergeometric distribution.
+///
+/// This is basically the same code as `phyper` *used* to be.
+pub fn qhyper(p: f64, nr: f64, nb: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || nr.is_nan() || nb.is_nan() || n.is_nan() {
+        return p + nr + nb + n;
+    }
+    if !r_finite(p) || !r_finite(nr) || !r_finite(nb) || !r_finite(n) {
+        return ml_warn_return_nan();
+    }
+
+    let mut nr = r_forceint(nr);
+    let mut nb = r_forceint(nb);
+    let big_n = nr + nb;
+    let n = r_forceint(n);
+    if nr < 0.0 || nb < 0.0 || n < 0.0 || n > big_n {
+        return ml_warn_return_nan();
+    }
+
+    // Goal:  Find  xr (= #{red balls in sample}) such that
+    //   phyper(xr,  NR,NB, n) >= p > phyper(xr - 1,  NR,NB, n)
+
+    let xstart = fmax2(0.0, n - nb);
+    let xend = fmin2(n, nr);
+
+    if let Some(x) = r_q_p01_boundaries(p, xstart, xend, lower_tail, log_p) {
+        return x;
+    }
+
+    let mut xr = xstart;
+    // always ( = #{black balls in sample} )
+    let mut xb = n - xr;
+
+    // won't have underflow in product below
+    let small_n = big_n < 1000.0;
+    // if N is small,  term := product.ratio( bin.coef );
+    // otherwise work with its logarithm to protect against underflow
+    let mut term = lfastchoose(nr, xr) + lfastchoose(nb, xb) - lfastchoose(big_n, n);
+    if small_n {
+        term = term.exp();
+    }
+    nr -= xr;
+    nb -= xb;
+
+    let mut p = p;
+    if !lower_tail || log_p {
+        p = r_dt_qiv(p, lower_tail, log_p);
+    }
+    // was 64, but failed on FreeBSD sometimes
+    p *= 1.0 - 1000.0 * DBL_EPSILON;
+    let mut sum = if small_n { term } else { term.exp() };
+
+    while sum < p && xr < xend {
+        xr += 1.0;
+        nb += 1.0;
+        if small_n {
+            term *= (nr / xr) * (xb / nb);
+        } else {
+            term += ((nr / xr) * (xb / nb)).ln();
+        }
+        sum += if small_n { term } else { term.exp() };
+        xb -= 1.0;
+        nr -= 1.0;
+    }
+    xr
+}
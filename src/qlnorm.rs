@@ -0,0 +1,35 @@
+use crate::dpq::r_q_p01_boundaries;
+use crate::nmath::ML_POSINF;
+use crate::qnorm::qnorm5;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-8 The R Core Team
+//  Copyright (C) 2005 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the lognormal distribution.
+pub fn qlnorm(p: f64, meanlog: f64, sdlog: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || meanlog.is_nan() || sdlog.is_nan() {
+        return p + meanlog + sdlog;
+    }
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    qnorm5(p, meanlog, sdlog, lower_tail, log_p).exp()
+}
@@ -0,0 +1,57 @@
+use crate::dpq::r_q_p01_boundaries;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_log1_exp;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 1995, 1996  Robert Gentleman and Ross Ihaka
+//  Copyright (C) 2000        The R Core Team
+//  Copyright (C) 2005        The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the logistic distribution.
+pub fn qlogis(p: f64, location: f64, scale: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || location.is_nan() || scale.is_nan() {
+        return p + location + scale;
+    }
+    if let Some(x) = r_q_p01_boundaries(p, ML_NEGINF, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    if scale < 0. {
+        return ml_warn_return_nan();
+    }
+    if scale == 0. {
+        return location;
+    }
+
+    // p := logit(p) = log( p / (1-p) )  :
+    let p = if log_p {
+        if lower_tail {
+            p - r_log1_exp(p)
+        } else {
+            r_log1_exp(p) - p
+        }
+    } else if lower_tail {
+        (p / (1. - p)).ln()
+    } else {
+        ((1. - p) / p).ln()
+    };
+
+    location + scale * p
+}
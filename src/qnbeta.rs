@@ -0,0 +1,83 @@
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::fmin2::fmin2;
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MIN;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::pnbeta::pnbeta;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 2006 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the non-central beta distribution.
+///
+/// Inverts `pnbeta` by first finding an upper and lower bound and then
+/// halving the interval.
+pub fn qnbeta(p: f64, a: f64, b: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const ACCU: f64 = 1e-15;
+    const EPS: f64 = 1e-14; // must be > ACCU
+
+    if p.is_nan() || a.is_nan() || b.is_nan() || ncp.is_nan() {
+        return p + a + b + ncp;
+    }
+    if !r_finite(a) {
+        return ml_warn_return_nan();
+    }
+
+    if ncp < 0.0 || a <= 0.0 || b <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, 1.0, lower_tail, log_p) {
+        return x;
+    }
+
+    let p = r_dt_qiv(p, lower_tail, log_p);
+
+    // Invert pnbeta(.) :
+    // 1. finding an upper and lower bound
+    if p > 1.0 - DBL_EPSILON {
+        return 1.0;
+    }
+    let mut pp = fmin2(1.0 - DBL_EPSILON, p * (1.0 + EPS));
+    let mut ux = 0.5;
+    while ux < 1.0 - DBL_EPSILON && pnbeta(ux, a, b, ncp, true, false) < pp {
+        ux = 0.5 * (1.0 + ux);
+    }
+    pp = p * (1.0 - EPS);
+    let mut lx = 0.5;
+    while lx > DBL_MIN && pnbeta(lx, a, b, ncp, true, false) > pp {
+        lx *= 0.5;
+    }
+
+    // 2. interval (lx,ux)  halving :
+    loop {
+        let nx = 0.5 * (lx + ux);
+        if pnbeta(nx, a, b, ncp, true, false) > p {
+            ux = nx;
+        } else {
+            lx = nx;
+        }
+        if !((ux - lx) / nx > ACCU) {
+            break;
+        }
+    }
+
+    0.5 * (ux + lx)
+}
@@ -0,0 +1,154 @@
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::fmax2::fmax2;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_forceint;
+use crate::nmath::ML_POSINF;
+use crate::pnbinom::pnbinom;
+use crate::qnorm::qnorm5;
+use crate::qpois::qpois;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2016 The R Core Team
+//  Copyright (C) 2005-2016 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Search from `y` in steps of `incr` for the smallest value whose
+/// distribution function is at least `p`.
+///
+/// On entry `z` holds `pnbinom(y, ..)`; on return it holds the value at the
+/// returned quantile.
+fn do_search(y: f64, z: &mut f64, p: f64, n: f64, pr: f64, incr: f64) -> f64 {
+    let mut y = y;
+    if *z >= p {
+        // search to the left
+        loop {
+            if y == 0.0 {
+                return y;
+            }
+            *z = pnbinom(y - incr, n, pr, true, false);
+            if *z < p {
+                return y;
+            }
+            y = fmax2(0.0, y - incr);
+        }
+    } else {
+        // search to the right
+        loop {
+            y += incr;
+            *z = pnbinom(y, n, pr, true, false);
+            if *z >= p {
+                return y;
+            }
+        }
+    }
+}
+
+/// The quantile function of the negative binomial distribution.
+///
+/// Uses the Cornish-Fisher Expansion to include a skewness
+/// correction to a normal approximation.  This gives an
+/// initial value which never seems to be off by more than
+/// 1 or 2.  A search is then conducted of values close to
+/// this initial start point.
+pub fn qnbinom(p: f64, size: f64, prob: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || size.is_nan() || prob.is_nan() {
+        return p + size + prob;
+    }
+
+    // this happens if specified via mu, size, since
+    // prob == size/(size+mu)
+    if prob == 0.0 && size == 0.0 {
+        return 0.0;
+    }
+
+    if prob <= 0.0 || prob > 1.0 || size < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if prob == 1.0 || size == 0.0 {
+        return 0.0;
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    let big_q = 1.0 / prob;
+    let big_p = (1.0 - prob) * big_q;
+    let mu = size * big_p;
+    let sigma = (size * big_p * big_q).sqrt();
+    let gamma = (big_q + big_p) / sigma;
+
+    // Note : "same" code in qpois.c, qbinom.c, qnbinom.c --
+    // FIXME: This is far from optimal [cancellation for p ~= 1, etc]:
+    let mut p = p;
+    if !lower_tail || log_p {
+        // need check again (cancellation!):
+        p = r_dt_qiv(p, lower_tail, log_p);
+        if p == r_dt_0(lower_tail, log_p) {
+            return 0.0;
+        }
+        if p == r_dt_1(lower_tail, log_p) {
+            return ML_POSINF;
+        }
+    }
+    // temporary hack --- FIXME ---
+    if p + 1.01 * DBL_EPSILON >= 1.0 {
+        return ML_POSINF;
+    }
+
+    // y := approx.value (Cornish-Fisher expansion) :
+    let mut z = qnorm5(p, 0.0, 1.0, true, false);
+    let mut y = r_forceint(mu + sigma * (z + gamma * (z * z - 1.0) / 6.0));
+
+    z = pnbinom(y, size, prob, true, false);
+
+    // fuzz to ensure left continuity:
+    p *= 1.0 - 64.0 * DBL_EPSILON;
+
+    // If the C-F value is not too large a simple search is OK
+    if y < 1e5 {
+        return do_search(y, &mut z, p, size, prob, 1.0);
+    }
+    // Otherwise be a bit cleverer in the search
+    let mut incr = (y * 0.001).floor();
+    loop {
+        let oldincr = incr;
+        y = do_search(y, &mut z, p, size, prob, incr);
+        incr = fmax2(1.0, (incr / 100.0).floor());
+        if !(oldincr > 1.0 && incr > y * 1e-15) {
+            break;
+        }
+    }
+    y
+}
+
+/// The quantile function of the negative binomial distribution,
+/// parametrized by the mean `mu`.
+pub fn qnbinom_mu(p: f64, size: f64, mu: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if size == ML_POSINF {
+        // limit case: Poisson
+        return qpois(p, mu, lower_tail, log_p);
+    }
+    // FIXME!  Implement properly!! (not losing accuracy for very large size (prob ~= 1)
+    qnbinom(p, size, size / (size + mu), lower_tail, log_p)
+}
@@ -0,0 +1,137 @@
+use crate::dpq::r_d_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::fmin2::fmin2;
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MAX;
+use crate::libc::DBL_MIN;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+use crate::pnchisq::pnchisq_raw;
+use crate::qchisq::qchisq;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 2000--2020  The R Core Team
+//  Copyright (C) 2004	      The R Foundation
+//  Copyright (C) 1995, 1996  Robert Gentleman and Ross Ihaka
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the non-central chi-squared distribution.
+///
+/// Starts from Pearson's (1959) approximation, then brackets the quantile
+/// and halves the interval using `pnchisq_raw`.
+pub fn qnchisq(p: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const ACCU: f64 = 1e-13;
+    const RACC: f64 = 4.0 * DBL_EPSILON;
+    // these two are for the "search" loops, can have less accuracy:
+    const EPS: f64 = 1e-11; // must be > ACCU
+    const R_EPS: f64 = 1e-10; // relative tolerance ...
+
+    if p.is_nan() || df.is_nan() || ncp.is_nan() {
+        return p + df + ncp;
+    }
+    if !r_finite(df) {
+        return ml_warn_return_nan();
+    }
+
+    if df < 0.0 || ncp < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    let mut pp = r_d_qiv(p, log_p); // exp(p) iff log_p
+    if pp > 1.0 - DBL_EPSILON {
+        // early under/over flow  iff log_p (FIXME)
+        return if lower_tail { ML_POSINF } else { 0.0 };
+    }
+
+    // Invert pnchisq(.) :
+    // 1. finding an upper and lower bound
+    let mut ux = {
+        // This is Pearson's (1959) approximation,
+        // which is usually good to 4 figs or so.
+        let b = (ncp * ncp) / (df + 3.0 * ncp);
+        let c = (df + 3.0 * ncp) / (df + 2.0 * ncp);
+        let ff = (df + 2.0 * ncp) / (c * c);
+        let ux = b + c * qchisq(p, ff, lower_tail, log_p);
+        if ux <= 0.0 {
+            1.0
+        } else {
+            ux
+        }
+    };
+    let ux0 = ux;
+
+    let mut lower_tail = lower_tail;
+    let p = if !lower_tail && ncp >= 80.0 {
+        // in this case, pnchisq() works via lower_tail = TRUE
+        if pp < 1e-10 {
+            ml_warning(MlWarningKind::Precision, "qnchisq");
+        }
+        lower_tail = true;
+        // R_DT_qIv(p)
+        if log_p {
+            -p.exp_m1()
+        } else {
+            0.5 - p + 0.5
+        }
+    } else {
+        pp
+    };
+
+    pp = fmin2(1.0 - DBL_EPSILON, p * (1.0 + EPS));
+    let mut lx;
+    if lower_tail {
+        while ux < DBL_MAX && pnchisq_raw(ux, df, ncp, EPS, R_EPS, 10000, true, false) < pp {
+            ux *= 2.0;
+        }
+        pp = p * (1.0 - EPS);
+        lx = fmin2(ux0, DBL_MAX);
+        while lx > DBL_MIN && pnchisq_raw(lx, df, ncp, EPS, R_EPS, 10000, true, false) > pp {
+            lx *= 0.5;
+        }
+    } else {
+        while ux < DBL_MAX && pnchisq_raw(ux, df, ncp, EPS, R_EPS, 10000, false, false) > pp {
+            ux *= 2.0;
+        }
+        pp = p * (1.0 - EPS);
+        lx = fmin2(ux0, DBL_MAX);
+        while lx > DBL_MIN && pnchisq_raw(lx, df, ncp, EPS, R_EPS, 10000, false, false) < pp {
+            lx *= 0.5;
+        }
+    }
+
+    // 2. interval (lx,ux)  halving :
+    loop {
+        let nx = 0.5 * (lx + ux);
+        let pnx = pnchisq_raw(nx, df, ncp, ACCU, RACC, 100000, lower_tail, false);
+        if (lower_tail && pnx > p) || (!lower_tail && pnx < p) {
+            ux = nx;
+        } else {
+            lx = nx;
+        }
+        if !((ux - lx) / nx > ACCU) {
+            break;
+        }
+    }
+    0.5 * (ux + lx)
+}
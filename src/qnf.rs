@@ -0,0 +1,51 @@
+use crate::dpq::r_q_p01_boundaries;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+use crate::qnbeta::qnbeta;
+use crate::qnchisq::qnchisq;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 2006-8 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the non-central F distribution.
+pub fn qnf(p: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || df1.is_nan() || df2.is_nan() || ncp.is_nan() {
+        return p + df1 + df2 + ncp;
+    }
+    if df1 <= 0.0 || df2 <= 0.0 || ncp < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(ncp) {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(df1) && !r_finite(df2) {
+        return ml_warn_return_nan();
+    }
+    if let Some(x) = r_q_p01_boundaries(p, 0.0, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    if df2 > 1e8 {
+        // avoid problems with +Inf and loss of accuracy
+        return qnchisq(p, df1, ncp, lower_tail, log_p) / df1;
+    }
+
+    let y = qnbeta(p, df1 / 2.0, df2 / 2.0, ncp, lower_tail, log_p);
+    y / (1.0 - y) * (df2 / df1)
+}
@@ -27,7 +27,7 @@ pub fn qnorm5(p: f64, mu: f64, sigma: f64, lower_tail: bool, log_p: bool) -> f64
     }
 
     if sigma < 0.0 {
-        ml_warn_return_nan();
+        return ml_warn_return_nan();
     }
     if sigma == 0.0 {
         return mu;
@@ -0,0 +1,79 @@
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::fmax2::fmax2;
+use crate::fmin2::fmin2;
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MAX;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+use crate::pnt::pnt;
+use crate::qnorm::qnorm5;
+use crate::qt::qt;
+
+/// The quantile function of the non-central t-distribution.
+///
+/// Inverts `pnt` by first finding an upper and lower bound and then
+/// halving the interval.
+/// For `df = Inf`, the quantile of the limiting N(ncp, 1) is returned.
+pub fn qnt(p: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const ACCU: f64 = 1e-13;
+    const EPS: f64 = 1e-11; // must be > ACCU
+
+    if p.is_nan() || df.is_nan() || ncp.is_nan() {
+        return p + df + ncp;
+    }
+    // Was
+    // df = floor(df + 0.5);
+    // if (df < 1 || ncp < 0) ML_WARN_return_NAN;
+    if df <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if ncp == 0.0 && df >= 1.0 {
+        return qt(p, df, lower_tail, log_p);
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, ML_NEGINF, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    if !r_finite(df) {
+        // df = Inf ==> limit N(ncp,1)
+        return qnorm5(p, ncp, 1.0, lower_tail, log_p);
+    }
+
+    let p = r_dt_qiv(p, lower_tail, log_p);
+
+    // Invert pnt(.) :
+    // 1. finding an upper and lower bound
+    if p > 1.0 - DBL_EPSILON {
+        return ML_POSINF;
+    }
+    let mut pp = fmin2(1.0 - DBL_EPSILON, p * (1.0 + EPS));
+    let mut ux = fmax2(1.0, ncp);
+    while ux < DBL_MAX && pnt(ux, df, ncp, true, false) < pp {
+        ux *= 2.0;
+    }
+    pp = p * (1.0 - EPS);
+    let mut lx = fmin2(-1.0, -ncp);
+    while lx > -DBL_MAX && pnt(lx, df, ncp, true, false) > pp {
+        lx *= 2.0;
+    }
+
+    // 2. interval (lx,ux)  halving :
+    loop {
+        let nx = 0.5 * (lx + ux); // could be zero
+        if pnt(nx, df, ncp, true, false) > p {
+            ux = nx;
+        } else {
+            lx = nx;
+        }
+        if !((ux - lx) > ACCU * fmax2(lx.abs(), ux.abs())) {
+            break;
+        }
+    }
+
+    0.5 * (lx + ux)
+}
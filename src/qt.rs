@@ -0,0 +1,299 @@
+use crate::cospi::tanpi;
+use crate::dpq::r_d_cval;
+use crate::dpq::r_d_lexp;
+use crate::dpq::r_d_log;
+use crate::dpq::r_d_lval;
+use crate::dpq::r_d_qiv;
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::dt::dt;
+use crate::fmin2::fmin2;
+use crate::libc::DBL_EPSILON;
+use crate::libc::DBL_MANT_DIG;
+use crate::libc::DBL_MAX;
+use crate::libc::DBL_MIN;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_NEGINF;
+use crate::nmath::ML_POSINF;
+use crate::pt::pt;
+use crate::qnorm::qnorm5;
+use crate::rmath::M_1_PI;
+use crate::rmath::M_LN2;
+use crate::rmath::M_PI;
+use crate::rmath::M_PI_2;
+use crate::rmath::M_SQRT2;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+/// The quantile function of the Student's t-distribution.
+///
+/// ## NOTES
+///
+/// For `ndf < 1`, `pt` is inverted by interval halving.
+/// Otherwise, Hill's algorithm is used with exact formulas for `ndf` ~= 1
+/// (Cauchy) and `ndf` ~= 2, followed by at most 10 steps of a 2-term Taylor
+/// expansion improvement.
+/// For `ndf > 1e20`, the normal quantile is returned.
+///
+/// ## REFERENCES
+///
+/// Hill, G.W (1970) "Algorithm 396: Student's t-quantiles"
+/// CACM 13(10), 619-620.
+///
+/// Hill, G.W (1981) "Remark on Algorithm 396", ACM TOMS 7, 250-1.
+pub fn qt(p: f64, ndf: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const EPS: f64 = 1.0e-12;
+
+    if p.is_nan() || ndf.is_nan() {
+        return p + ndf;
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, ML_NEGINF, ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    if ndf <= 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if ndf < 1.0 {
+        // based on qnt
+        const ACCU: f64 = 1e-13;
+        const EPS_BOUND: f64 = 1e-11; // must be > ACCU
+
+        let mut iter = 0;
+
+        let p = r_dt_qiv(p, lower_tail, log_p);
+
+        // Invert pt(.) :
+        // 1. finding an upper and lower bound
+        if p > 1.0 - DBL_EPSILON {
+            return ML_POSINF;
+        }
+        let mut pp = fmin2(1.0 - DBL_EPSILON, p * (1.0 + EPS_BOUND));
+        let mut ux = 1.0;
+        while ux < DBL_MAX && pt(ux, ndf, true, false) < pp {
+            ux *= 2.0;
+        }
+        pp = p * (1.0 - EPS_BOUND);
+        let mut lx = -1.0;
+        while lx > -DBL_MAX && pt(lx, ndf, true, false) > pp {
+            lx *= 2.0;
+        }
+
+        // 2. interval (lx,ux)  halving
+        //    regula falsi failed on qt(0.1, 0.1)
+        loop {
+            let nx = 0.5 * (lx + ux);
+            if pt(nx, ndf, true, false) > p {
+                ux = nx;
+            } else {
+                lx = nx;
+            }
+            iter += 1;
+            if !((ux - lx) / nx.abs() > ACCU && iter < 1000) {
+                break;
+            }
+        }
+
+        if iter >= 1000 {
+            ml_warning(MlWarningKind::Precision, "qt");
+        }
+
+        return 0.5 * (lx + ux);
+    }
+
+    // Old comment:
+    // FIXME: "This test should depend on  ndf  AND p  !!
+    // -----  and in fact should be replaced by
+    // something like Abramowitz & Stegun 26.7.5 (p.949)"
+    //
+    // That would say that if the qnorm value is x then
+    // the result is about x + (x^3+x)/4df + (5x^5+16x^3+3x)/96df^2
+    // The differences are tiny even if x ~ 1e5, and qnorm is not
+    // that accurate in the extreme tails.
+    if ndf > 1e20 {
+        return qnorm5(p, 0.0, 1.0, lower_tail, log_p);
+    }
+
+    let mut big_p = r_d_qiv(p, log_p); // if exp(p) underflows, we fix below
+
+    let neg = (!lower_tail || big_p < 0.5) && (lower_tail || big_p > 0.5);
+    let is_neg_lower = lower_tail == neg; // both TRUE or FALSE == !xor
+    if neg {
+        big_p = 2.0
+            * if log_p {
+                if lower_tail {
+                    big_p
+                } else {
+                    -p.exp_m1()
+                }
+            } else {
+                r_d_lval(p, lower_tail)
+            };
+    } else {
+        big_p = 2.0
+            * if log_p {
+                if lower_tail {
+                    -p.exp_m1()
+                } else {
+                    big_p
+                }
+            } else {
+                r_d_cval(p, lower_tail)
+            };
+    }
+    // 0 <= P <= 1 ; P = 2*min(P', 1 - P')  in all cases
+
+    let mut q;
+    if (ndf - 2.0).abs() < EPS {
+        // df ~= 2
+        if big_p > DBL_MIN {
+            if 3.0 * big_p < DBL_EPSILON {
+                // P ~= 0
+                q = 1.0 / big_p.sqrt();
+            } else if big_p > 0.9 {
+                // P ~= 1
+                q = (1.0 - big_p) * (2.0 / (big_p * (2.0 - big_p))).sqrt();
+            } else {
+                // eps/3 <= P <= 0.9
+                q = (2.0 / (big_p * (2.0 - big_p)) - 2.0).sqrt();
+            }
+        } else {
+            // P << 1, q = 1/sqrt(P) = ...
+            if log_p {
+                q = if is_neg_lower {
+                    (-p / 2.0).exp() / M_SQRT2
+                } else {
+                    1.0 / (-p.exp_m1()).sqrt()
+                };
+            } else {
+                q = ML_POSINF;
+            }
+        }
+    } else if ndf < 1.0 + EPS {
+        // df ~= 1  (df < 1 excluded above): Cauchy
+        if big_p == 1.0 {
+            q = 0.0; // some versions of tanpi give Inf, some NaN
+        } else if big_p > 0.0 {
+            q = 1.0 / tanpi(big_p / 2.0); // == - tan((P+1) * M_PI_2) -- suffers for P ~= 0
+        } else {
+            // P = 0, but maybe = 2*exp(p) !
+            if log_p {
+                // 1/tan(e) ~ 1/e
+                q = if is_neg_lower {
+                    M_1_PI * (-p).exp()
+                } else {
+                    -1.0 / (M_PI * p.exp_m1())
+                };
+            } else {
+                q = ML_POSINF;
+            }
+        }
+    } else {
+        //-- usual case;  including, e.g.,  df = 1.1
+        let mut x = 0.0;
+        let mut y = 0.0;
+        let mut log_p2 = 0.0;
+        let a = 1.0 / (ndf - 0.5);
+        let b = 48.0 / (a * a);
+        let mut c = ((20700.0 * a / b - 98.0) * a - 16.0) * a + 96.36;
+        let d = ((94.5 / (b + c) - 3.0) / b + 1.0) * (a * M_PI_2).sqrt() * ndf;
+
+        let p_ok1 = big_p > DBL_MIN || !log_p;
+        // when true (after check below), use "normal scale": log_p=FALSE
+        let mut p_ok = p_ok1;
+        if p_ok1 {
+            y = (d * big_p).powf(2.0 / ndf);
+            p_ok = y >= DBL_EPSILON;
+        }
+        if !p_ok {
+            // log.p && P very.small  ||  (d*P)^(2/df) =: y < eps_c
+            log_p2 = if is_neg_lower {
+                r_d_log(p, log_p)
+            } else {
+                r_d_lexp(p, log_p)
+            }; // == log(P / 2)
+            x = (d.ln() + M_LN2 + log_p2) / ndf;
+            y = (2.0 * x).exp();
+        }
+
+        if (ndf < 2.1 && big_p > 0.5) || y > 0.05 + a {
+            // P > P0(df)
+            // Asymptotic inverse expansion about normal
+            if p_ok {
+                x = qnorm5(0.5 * big_p, 0.0, 1.0, true, false);
+            } else {
+                // log_p && P underflowed
+                x = qnorm5(log_p2, 0.0, 1.0, lower_tail, true);
+            }
+
+            y = x * x;
+            if ndf < 5.0 {
+                c += 0.3 * (ndf - 4.5) * (x + 0.6);
+            }
+            c += (((0.05 * d * x - 5.0) * x - 7.0) * x - 2.0) * x + b;
+            y = (((((0.4 * y + 6.3) * y + 36.0) * y + 94.5) / c - y - 3.0) / b + 1.0) * x;
+            y = (a * y * y).exp_m1();
+            q = (ndf * y).sqrt();
+        } else if !p_ok && x < -M_LN2 * DBL_MANT_DIG as f64 {
+            // 0.5* log(DBL_EPSILON)
+            // y above might have underflown
+            q = ndf.sqrt() * (-x).exp();
+        } else {
+            // re-use 'y' from above
+            y = ((1.0 / (((ndf + 6.0) / (ndf * y) - 0.089 * d - 0.822) * (ndf + 2.0) * 3.0)
+                + 0.5 / (ndf + 4.0))
+                * y
+                - 1.0)
+                * (ndf + 1.0)
+                / (ndf + 2.0)
+                + 1.0 / y;
+            q = (ndf * y).sqrt();
+        }
+
+        // Now apply 2-term Taylor expansion improvement (1-term = Newton):
+        // as by Hill (1981) [ref.above]
+
+        // FIXME: This can be far from optimal when log_p = TRUE
+        //      but is still needed, e.g. for qt(-2, df=1.01, log=TRUE).
+        //	Probably also improvable when  lower_tail = FALSE
+
+        if p_ok1 {
+            let m = ((DBL_MAX / 2.0).sqrt() - ndf).abs();
+            for _ in 0..10 {
+                y = dt(q, ndf, false);
+                if !(y > 0.0) {
+                    break;
+                }
+                x = (pt(q, ndf, false, false) - big_p / 2.0) / y;
+                if !r_finite(x) || !(x.abs() > 1e-14 * q.abs()) {
+                    break;
+                }
+                // Newton (=Taylor 1 term):
+                //  q += x;
+                // Taylor 2-term :
+                let f = if q.abs() < m {
+                    q * (ndf + 1.0) / (2.0 * (q * q + ndf))
+                } else {
+                    (ndf + 1.0) / (2.0 * (q + ndf / q))
+                };
+                let del_q = x * (1.0 + x * f);
+                if r_finite(del_q) && r_finite(q + del_q) {
+                    q += del_q;
+                } else if r_finite(x) && r_finite(q + x) {
+                    q += x;
+                } else {
+                    // FIXME??  if  q+x = +/-Inf is *better* than q should use it
+                    break; // cannot improve  q  with a Newton/Taylor step
+                }
+            }
+        }
+    }
+    if neg {
+        -q
+    } else {
+        q
+    }
+}
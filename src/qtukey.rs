@@ -0,0 +1,160 @@
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_boundaries;
+use crate::fmax2::fmax2;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::ML_POSINF;
+use crate::ptukey::ptukey;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 	     Ross Ihaka
+//  Copyright (C) 2000--2005 The R Core Team
+//  based in part on AS70 (C) 1974 Royal Statistical Society
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Finds the percentage point of the studentized range which is used as
+/// initial estimate for the secant method.
+///
+/// This function is adapted from portion of algorithm as 70
+/// from applied statistics (1974) ,vol. 23, no. 1
+/// by odeh, r. e. and evans, j. o.
+///
+/// p = percentage point
+/// c = no. of columns or treatments
+/// v = degrees of freedom
+///
+/// vmax is cutoff above which degrees of freedom
+/// is treated as infinity.
+#[allow(clippy::approx_constant)]
+#[allow(clippy::excessive_precision)]
+fn qinv(p: f64, c: f64, v: f64) -> f64 {
+    const P0: f64 = 0.322232421088;
+    const Q0: f64 = 0.993484626060e-01;
+    const P1: f64 = -1.0;
+    const Q1: f64 = 0.588581570495;
+    const P2: f64 = -0.342242088547;
+    const Q2: f64 = 0.531103462366;
+    const P3: f64 = -0.204231210125;
+    const Q3: f64 = 0.103537752850;
+    const P4: f64 = -0.453642210148e-04;
+    const Q4: f64 = 0.38560700634e-02;
+    const C1: f64 = 0.8832;
+    const C2: f64 = 0.2368;
+    const C3: f64 = 1.214;
+    const C4: f64 = 1.208;
+    const C5: f64 = 1.4142;
+    const VMAX: f64 = 120.0;
+
+    let ps = 0.5 - 0.5 * p;
+    let yi = (1.0 / (ps * ps)).ln().sqrt();
+    let mut t = yi
+        + ((((yi * P4 + P3) * yi + P2) * yi + P1) * yi + P0)
+            / ((((yi * Q4 + Q3) * yi + Q2) * yi + Q1) * yi + Q0);
+    if v < VMAX {
+        t += (t * t * t + t) / v / 4.0;
+    }
+    let mut q = C1 - C2 * t;
+    if v < VMAX {
+        q += -C3 / v + C4 * t / v;
+    }
+    t * (q * (c - 1.0).ln() + C5)
+}
+
+/// The quantile function of the studentized range distribution.
+///
+/// Uses the secant method to find critical values, see
+///
+/// Copenhaver, Margaret Diponzio & Holland, Burt S.
+/// Multiple comparisons of simple effects in
+/// the two-way analysis of variance with fixed effects.
+/// Journal of Statistical Computation and Simulation,
+/// Vol.30, pp.1-15, 1988.
+///
+/// p = confidence level (1 - alpha)
+/// rr = no. of rows or groups (`nranges` in R)
+/// cc = no. of columns or treatments (`nmeans` in R)
+/// df = degrees of freedom of error term
+///
+/// If the difference between successive iterates is less than eps,
+/// the search is terminated.
+/// If this does not happen in 50 iterations, a `MlWarningKind::NoConv`
+/// warning is raised; see `take_warnings`.
+pub fn qtukey(p: f64, rr: f64, cc: f64, df: f64, lower_tail: bool, log_p: bool) -> f64 {
+    const EPS: f64 = 0.0001;
+    const MAXITER: i32 = 50;
+
+    if p.is_nan() || rr.is_nan() || cc.is_nan() || df.is_nan() {
+        // ML_WARNING(ME_DOMAIN, "qtukey") is not reported, like in R.
+        return p + rr + cc + df;
+    }
+
+    // df must be > 1 ; there must be at least two values
+    if df < 2. || rr < 1. || cc < 2. {
+        return ml_warn_return_nan();
+    }
+
+    if let Some(x) = r_q_p01_boundaries(p, 0., ML_POSINF, lower_tail, log_p) {
+        return x;
+    }
+
+    let p = r_dt_qiv(p, lower_tail, log_p); // lower_tail,non-log "p"
+
+    // Initial value
+    let mut x0 = qinv(p, cc, df);
+
+    // Find prob(value < x0)
+    let mut valx0 = ptukey(x0, rr, cc, df, true, false) - p;
+
+    // Find the second iterate and prob(value < x1).
+    // If the first iterate has probability value
+    // exceeding p then second iterate is 1 less than
+    // first iterate; otherwise it is 1 greater.
+    let mut x1 = if valx0 > 0.0 {
+        fmax2(0.0, x0 - 1.0)
+    } else {
+        x0 + 1.0
+    };
+    let mut valx1 = ptukey(x1, rr, cc, df, true, false) - p;
+
+    // Find new iterate
+    let mut ans = 0.0;
+    for _ in 1..MAXITER {
+        ans = x1 - ((valx1 * (x1 - x0)) / (valx1 - valx0));
+        valx0 = valx1;
+
+        // New iterate must be >= 0
+        x0 = x1;
+        if ans < 0.0 {
+            ans = 0.0;
+        }
+        // Find prob(value < new iterate)
+        valx1 = ptukey(ans, rr, cc, df, true, false) - p;
+        x1 = ans;
+
+        // If the difference between two successive
+        // iterates is less than eps, stop
+        let xabs = (x1 - x0).abs();
+        if xabs < EPS {
+            return ans;
+        }
+    }
+
+    // The process did not converge in 'maxiter' iterations
+    ml_warning(MlWarningKind::NoConv, "qtukey");
+    ans
+}
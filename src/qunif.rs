@@ -0,0 +1,44 @@
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_q_p01_check;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2006 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The quantile function of the uniform distribution on `[a, b]`.
+pub fn qunif(p: f64, a: f64, b: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if p.is_nan() || a.is_nan() || b.is_nan() {
+        return p + a + b;
+    }
+    if let Some(x) = r_q_p01_check(p, log_p) {
+        return x;
+    }
+    if !r_finite(a) || !r_finite(b) {
+        return ml_warn_return_nan();
+    }
+    if b < a {
+        return ml_warn_return_nan();
+    }
+    if b == a {
+        return a;
+    }
+
+    a + r_dt_qiv(p, lower_tail, log_p) * (b - a)
+}
@@ -0,0 +1,138 @@
+use crate::fmax2::fmax2;
+use crate::fmin2::fmin2;
+use crate::libc::DBL_MAX;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rmath::M_LN2;
+use crate::rng::Rng;
+
+/// = log(DBL_MAX)
+const EXPMAX: f64 = f64::MAX_EXP as f64 * M_LN2;
+
+/// Random variates from the beta distribution.
+///
+/// ## NOTES
+///
+/// The C code keeps the set-up values of the last `(aa, bb)` in static
+/// variables; here they are recomputed on each call.
+///
+/// ## REFERENCES
+///
+/// R. C. H. Cheng (1978).
+/// Generating beta variates with nonintegral shape parameters.
+/// Communications of the ACM 21, 317-322.
+/// (Algorithms BB and BC)
+pub fn rbeta<R: Rng + ?Sized>(aa: f64, bb: f64, rng: &mut R) -> f64 {
+    if aa.is_nan() || bb.is_nan() || aa < 0.0 || bb < 0.0 {
+        return ml_warn_return_nan();
+    }
+    if !r_finite(aa) && !r_finite(bb) {
+        // a = b = Inf : all mass at 1/2
+        return 0.5;
+    }
+    if aa == 0.0 && bb == 0.0 {
+        // point mass 1/2 at each of {0,1} :
+        return if rng.unif_rand() < 0.5 { 0.0 } else { 1.0 };
+    }
+    // now, at least one of a, b is finite and positive
+    if !r_finite(aa) || bb == 0.0 {
+        return 1.0;
+    }
+    if !r_finite(bb) || aa == 0.0 {
+        return 0.0;
+    }
+
+    let a = fmin2(aa, bb);
+    let b = fmax2(aa, bb); // a <= b
+    let alpha = a + b;
+
+    let v_w_from_u1_bet = |aa: f64, beta: f64, u1: f64| -> (f64, f64) {
+        let v = beta * (u1 / (1.0 - u1)).ln();
+        let w = if v <= EXPMAX {
+            let w = aa * v.exp();
+            if r_finite(w) {
+                w
+            } else {
+                DBL_MAX
+            }
+        } else {
+            DBL_MAX
+        };
+        (v, w)
+    };
+
+    if a <= 1.0 {
+        // --- Algorithm BC ---
+
+        // changed notation, now also a <= b (was reversed)
+
+        let beta = 1.0 / a;
+        let delta = 1.0 + b - a;
+        let k1 = delta * (0.0138889 + 0.0416667 * a) / (b * beta - 0.777778);
+        let k2 = 0.25 + (0.5 + 0.25 / delta) * a;
+
+        let w = loop {
+            let u1 = rng.unif_rand();
+            let u2 = rng.unif_rand();
+            let z;
+            if u1 < 0.5 {
+                let y = u1 * u2;
+                z = u1 * y;
+                if 0.25 * u2 + z - y >= k1 {
+                    continue;
+                }
+            } else {
+                z = u1 * u1 * u2;
+                if z <= 0.25 {
+                    let (_, w) = v_w_from_u1_bet(b, beta, u1);
+                    break w;
+                }
+                if z >= k2 {
+                    continue;
+                }
+            }
+
+            let (v, w) = v_w_from_u1_bet(b, beta, u1);
+
+            if alpha * ((alpha / (a + w)).ln() + v) - 1.3862944 >= z.ln() {
+                break w;
+            }
+        };
+        if aa == a {
+            a / (a + w)
+        } else {
+            w / (a + w)
+        }
+    } else {
+        // Algorithm BB
+
+        let beta = ((alpha - 2.0) / (2.0 * a * b - alpha)).sqrt();
+        let gamma = a + 1.0 / beta;
+
+        let w = loop {
+            let u1 = rng.unif_rand();
+            let u2 = rng.unif_rand();
+
+            let (v, w) = v_w_from_u1_bet(a, beta, u1);
+
+            let z = u1 * u1 * u2;
+            let r = gamma * v - 1.3862944;
+            let s = a + r - w;
+            if s + 2.609438 >= 5.0 * z {
+                break w;
+            }
+            let t = z.ln();
+            if s > t {
+                break w;
+            }
+            if !(r + alpha * (alpha / (b + w)).ln() < t) {
+                break w;
+            }
+        };
+        if aa != a {
+            b / (b + w)
+        } else {
+            w / (b + w)
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::fmin2::fmin2;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+use crate::qbinom::qbinom;
+use crate::rng::Rng;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2014 The R Core Team
+//  Copyright (C) 2007 The R Foundation
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Random variates from the binomial distribution.
+///
+/// # Reference
+///
+/// Kachitvichyanukul, V. and Schmeiser, B. W. (1988).
+/// Binomial random variate generation.
+/// Communications of the ACM 31, 216-222.
+/// (Algorithm BTPEC).
+///
+/// The C code caches the setup in static variables between calls with the
+/// same parameters; here the setup is recomputed on every call.
+pub fn rbinom<R: Rng + ?Sized>(nin: f64, pp: f64, rng: &mut R) -> f64 {
+    if !r_finite(nin) {
+        return ml_warn_return_nan();
+    }
+    let r = r_forceint(nin);
+    if r != nin {
+        return ml_warn_return_nan();
+    }
+    // n=0, p=0, p=1 are not errors <TSL>
+    if !r_finite(pp) || r < 0.0 || pp < 0.0 || pp > 1.0 {
+        return ml_warn_return_nan();
+    }
+
+    if r == 0.0 || pp == 0.0 {
+        return 0.0;
+    }
+    if pp == 1.0 {
+        return r;
+    }
+
+    if r >= i32::MAX as f64 {
+        // evade integer overflow, and r == INT_MAX gave only even values
+        return qbinom(rng.unif_rand(), r, pp, false, false);
+    }
+    let n = r as i32;
+
+    let p = fmin2(pp, 1.0 - pp);
+    let q = 1.0 - p;
+    let np = n as f64 * p;
+    let r = p / q;
+    let g = r * (n + 1) as f64;
+
+    let ix = if np < 30.0 {
+        // inverse cdf logic for mean less than 30
+        let qn = q.powi(n);
+        'outer: loop {
+            let mut ix = 0;
+            let mut f = qn;
+            let mut u = rng.unif_rand();
+            loop {
+                if u < f {
+                    break 'outer ix;
+                }
+                if ix > 110 {
+                    break;
+                }
+                u -= f;
+                ix += 1;
+                f *= g / ix as f64 - r;
+            }
+        }
+    } else {
+        let ffm = np + p;
+        let m = ffm as i32;
+        let fm = m as f64;
+        let npq = np * q;
+        let p1 = (2.195 * npq.sqrt() - 4.6 * q) as i32 as f64 + 0.5;
+        let xm = fm + 0.5;
+        let xl = xm - p1;
+        let xr = xm + p1;
+        let c = 0.134 + 20.5 / (15.3 + fm);
+        let al = (ffm - xl) / (ffm - xl * p);
+        let xll = al * (1.0 + 0.5 * al);
+        let al = (xr - ffm) / (xr * q);
+        let xlr = al * (1.0 + 0.5 * al);
+        let p2 = p1 * (1.0 + c + c);
+        let p3 = p2 + c / xll;
+        let p4 = p3 + c / xlr;
+
+        // np = n*p >= 30 :
+        loop {
+            let u = rng.unif_rand() * p4;
+            let mut v = rng.unif_rand();
+            let ix;
+            // triangular region
+            if u <= p1 {
+                break (xm - p1 * v + u) as i32;
+            }
+            // parallelogram region
+            if u <= p2 {
+                let x = xl + (u - p1) / c;
+                v = v * c + 1.0 - (xm - x).abs() / p1;
+                if v > 1.0 || v <= 0.0 {
+                    continue;
+                }
+                ix = x as i32;
+            } else if u > p3 {
+                // right tail
+                ix = (xr - v.ln() / xlr) as i32;
+                if ix > n {
+                    continue;
+                }
+                v *= (u - p3) * xlr;
+            } else {
+                // left tail
+                ix = (xl + v.ln() / xll) as i32;
+                if ix < 0 {
+                    continue;
+                }
+                v *= (u - p2) * xll;
+            }
+            // determine appropriate way to perform accept/reject test
+            let k = (ix - m).abs();
+            if k <= 20 || k as f64 >= npq / 2.0 - 1.0 {
+                // explicit evaluation
+                let mut f = 1.0;
+                if m < ix {
+                    for i in (m + 1)..=ix {
+                        f *= g / i as f64 - r;
+                    }
+                } else if m != ix {
+                    for i in (ix + 1)..=m {
+                        f /= g / i as f64 - r;
+                    }
+                }
+                if v <= f {
+                    break ix;
+                }
+            } else {
+                // squeezing using upper and lower bounds on log(f(x))
+                let k = k as f64;
+                let amaxp = (k / npq) * ((k * (k / 3. + 0.625) + 0.1666666666666) / npq + 0.5);
+                let ynorm = -k * k / (2.0 * npq);
+                let alv = v.ln();
+                if alv < ynorm - amaxp {
+                    break ix;
+                }
+                if alv <= ynorm + amaxp {
+                    // stirling's formula to machine accuracy
+                    // for the final acceptance/rejection test
+                    let x1 = (ix + 1) as f64;
+                    let f1 = fm + 1.0;
+                    let z = (n + 1) as f64 - fm;
+                    let w = (n - ix) as f64 + 1.0;
+                    let z2 = z * z;
+                    let x2 = x1 * x1;
+                    let f2 = f1 * f1;
+                    let w2 = w * w;
+                    if alv
+                        <= xm * (f1 / x1).ln()
+                            + ((n - m) as f64 + 0.5) * (z / w).ln()
+                            + (ix - m) as f64 * (w * p / (x1 * q)).ln()
+                            + (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / f2) / f2) / f2) / f2)
+                                / f1
+                                / 166320.0
+                            + (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / z2) / z2) / z2) / z2)
+                                / z
+                                / 166320.0
+                            + (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / x2) / x2) / x2) / x2)
+                                / x1
+                                / 166320.0
+                            + (13860.0 - (462.0 - (132.0 - (99.0 - 140.0 / w2) / w2) / w2) / w2)
+                                / w
+                                / 166320.
+                    {
+                        break ix;
+                    }
+                }
+            }
+        }
+    };
+
+    let ix = if pp > 0.5 { n - ix } else { ix };
+    ix as f64
+}
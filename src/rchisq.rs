@@ -0,0 +1,12 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rgamma::rgamma;
+use crate::rng::Rng;
+
+/// Random variates from the chi-squared distribution.
+pub fn rchisq<R: Rng + ?Sized>(df: f64, rng: &mut R) -> f64 {
+    if !r_finite(df) || df < 0.0 {
+        return ml_warn_return_nan();
+    }
+    rgamma(df / 2.0, 2.0, rng)
+}
@@ -0,0 +1,184 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::ML_POSINF;
+use crate::rng::Rng;
+use crate::sexp::exp_rand;
+
+/// Random variates from the gamma distribution.
+///
+/// ## REFERENCES
+///
+/// [1] Shape parameter a >= 1.  Algorithm GD in:
+///
+/// Ahrens, J.H. and Dieter, U. (1982).
+/// Generating gamma variates by a modified
+/// rejection technique.
+/// Comm. ACM, 25, 47-54.
+///
+///
+/// [2] Shape parameter 0 < a < 1. Algorithm GS in:
+///
+/// Ahrens, J.H. and Dieter, U. (1974).
+/// Computer methods for sampling from gamma, beta,
+/// poisson and binomial distributions.
+/// Computing, 12, 223-246.
+///
+/// Input: a = parameter (mean) of the standard gamma distribution.
+/// Output: a variate from the gamma(a)-distribution
+pub fn rgamma<R: Rng + ?Sized>(a: f64, scale: f64, rng: &mut R) -> f64 {
+    // Constants :
+    const SQRT32: f64 = 5.656854;
+    const EXP_M1: f64 = 0.367_879_441_171_442_33; // exp(-1) = 1/e
+
+    // Coefficients q[k] - for q0 = sum(q[k]*a^(-k))
+    // Coefficients a[k] - for q = q0+(t*t/2)*sum(a[k]*v^k)
+    // Coefficients e[k] - for exp(q)-1 = sum(e[k]*q^k)
+    const Q1: f64 = 0.04166669;
+    const Q2: f64 = 0.02083148;
+    const Q3: f64 = 0.00801191;
+    const Q4: f64 = 0.00144121;
+    const Q5: f64 = -7.388e-5;
+    const Q6: f64 = 2.4511e-4;
+    const Q7: f64 = 2.424e-4;
+
+    const A1: f64 = 0.3333333;
+    const A2: f64 = -0.250003;
+    const A3: f64 = 0.2000062;
+    const A4: f64 = -0.1662921;
+    const A5: f64 = 0.1423657;
+    const A6: f64 = -0.1367177;
+    const A7: f64 = 0.1233795;
+
+    if a.is_nan() || scale.is_nan() {
+        return ml_warn_return_nan();
+    }
+    if a <= 0.0 || scale <= 0.0 {
+        if scale == 0.0 || a == 0.0 {
+            return 0.0;
+        }
+        return ml_warn_return_nan();
+    }
+    if !r_finite(a) || !r_finite(scale) {
+        return ML_POSINF;
+    }
+
+    if a < 1.0 {
+        // GS algorithm for parameters a < 1
+        let e = 1.0 + EXP_M1 * a;
+        let x = loop {
+            let p = e * rng.unif_rand();
+            if p >= 1.0 {
+                let x = -((e - p) / a).ln();
+                if exp_rand(rng) >= (1.0 - a) * x.ln() {
+                    break x;
+                }
+            } else {
+                let x = (p.ln() / a).exp();
+                if exp_rand(rng) >= x {
+                    break x;
+                }
+            }
+        };
+        return scale * x;
+    }
+
+    // --- a >= 1 : GD algorithm ---
+
+    // Step 1: Calculations of s2, s, d.
+    // (The C code caches these in static variables for the last `a`.)
+    let s2 = a - 0.5;
+    let s = s2.sqrt();
+    let d = SQRT32 - s * 12.0;
+
+    // Step 2: t = standard normal deviate,
+    //         x = (s,1/2) -normal deviate.
+
+    // immediate acceptance (i)
+    let mut t = rng.norm_rand();
+    let x = s + 0.5 * t;
+    let ret_val = x * x;
+    if t >= 0.0 {
+        return scale * ret_val;
+    }
+
+    // Step 3: u = 0,1 - uniform sample. squeeze acceptance (s)
+    let mut u = rng.unif_rand();
+    if d * u <= t * t * t {
+        return scale * ret_val;
+    }
+
+    // Step 4: calculations of q0, b, si, c
+    let r = 1.0 / a;
+    let q0 = ((((((Q7 * r + Q6) * r + Q5) * r + Q4) * r + Q3) * r + Q2) * r + Q1) * r;
+
+    // Approximation depending on size of parameter a
+    // The constants in the expressions for b, si and c
+    // were established by numerical experiments
+    let (b, si, c) = if a <= 3.686 {
+        (0.463 + s + 0.178 * s2, 1.235, 0.195 / s - 0.079 + 0.16 * s)
+    } else if a <= 13.022 {
+        (1.654 + 0.0076 * s2, 1.68 / s + 0.275, 0.062 / s + 0.024)
+    } else {
+        (1.77, 0.75, 0.1515 / s)
+    };
+
+    // Step 5: no quotient test if x not positive
+    if x > 0.0 {
+        // Step 6: calculation of v and quotient q
+        let v = t / (s + s);
+        let q = if v.abs() <= 0.25 {
+            q0 + 0.5
+                * t
+                * t
+                * ((((((A7 * v + A6) * v + A5) * v + A4) * v + A3) * v + A2) * v + A1)
+                * v
+        } else {
+            q0 - s * t + 0.25 * t * t + (s2 + s2) * (1.0 + v).ln()
+        };
+
+        // Step 7: quotient acceptance (q)
+        if (1.0 - u).ln() <= q {
+            return scale * ret_val;
+        }
+    }
+
+    loop {
+        // Step 8: e = standard exponential deviate
+        //         u =  0,1 -uniform deviate
+        //         t = (b,si)-double exponential (laplace) sample
+        let e = exp_rand(rng);
+        u = rng.unif_rand();
+        u = u + u - 1.0;
+        if u < 0.0 {
+            t = b - si * e;
+        } else {
+            t = b + si * e;
+        }
+        // Step 9:  rejection if t < tau(1) = -0.71874483771719
+        if t >= -0.71874483771719 {
+            // Step 10:  calculation of v and quotient q
+            let v = t / (s + s);
+            let q = if v.abs() <= 0.25 {
+                q0 + 0.5
+                    * t
+                    * t
+                    * ((((((A7 * v + A6) * v + A5) * v + A4) * v + A3) * v + A2) * v + A1)
+                    * v
+            } else {
+                q0 - s * t + 0.25 * t * t + (s2 + s2) * (1.0 + v).ln()
+            };
+            // Step 11:  hat acceptance (h)
+            // (if q not positive go to step 8)
+            if q > 0.0 {
+                let w = q.exp_m1();
+                //  ^^^^^ original code had approximation with rel.err < 2e-7
+                // if t is rejected sample again at step 8
+                if c * u.abs() <= w * (e - 0.5 * t * t).exp() {
+                    break;
+                }
+            }
+        }
+    } // repeat .. until  `t' is accepted
+    let x = s + 0.5 * t;
+    scale * x * x
+}
@@ -0,0 +1,334 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+use crate::nmath::M_LN_SQRT_2PI;
+use crate::qhyper::qhyper;
+use crate::rbinom::rbinom;
+use crate::rng::Rng;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 2000-2020 The R Core Team
+//  Copyright (C) 2005-2020 The R Foundation
+//  Copyright (C) 1998 Ross Ihaka
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// afc(i) :=  ln( i! )  [logarithm of the factorial i]
+fn afc(i: i32) -> f64 {
+    // If (i > 7), use Stirling's approximation, otherwise use table lookup.
+    #[allow(clippy::approx_constant)]
+    #[allow(clippy::excessive_precision)]
+    const AL: [f64; 8] = [
+        0.0,                                // ln(0!)=ln(1)
+        0.0,                                // ln(1!)=ln(1)
+        0.69314718055994530941723212145817, // ln(2)
+        1.79175946922805500081247735838070, // ln(6)
+        3.17805383034794561964694160129705, // ln(24)
+        4.78749174278204599424770093452324,
+        6.57925121201010099506017829290394,
+        8.52516136106541430016553103634712,
+        // 10.60460290274525022841722740072165, approx. value below =
+        // 10.6046028788027; rel.error = 2.26 10^{-9}
+        //
+        // FIXME: Use constants and if(n > ..) decisions from ./stirlerr.c
+        // -----  will be even *faster* for n > 500 (or so)
+    ];
+
+    if i < 0 {
+        ml_warning(MlWarningKind::Range, "rhyper");
+        return -1.0; // unreached
+    }
+    if i <= 7 {
+        return AL[i as usize];
+    }
+    // else i >= 8 :
+    let di = i as f64;
+    let i2 = di * di;
+    (di + 0.5) * di.ln() - di + M_LN_SQRT_2PI + (0.0833333333333333 - 0.00277777777777778 / i2) / di
+}
+
+/// Random variates from the hypergeometric distribution.
+///
+/// Returns the number of red balls drawn when `kkin` balls are drawn at
+/// random from an urn containing `nn1in` red and `nn2in` black balls.
+///
+/// # Reference
+///
+/// V. Kachitvichyanukul and B. Schmeiser (1985).
+/// ``Computer generation of hypergeometric random variates,''
+/// Journal of Statistical Computation and Simulation 22, 127-145.
+///
+/// The original algorithm had a bug -- R bug report PR#7314 --
+/// giving numbers slightly too small in case III h2pe
+/// where (m < 100 || ix <= 50), see below.
+///
+/// The C code caches the setup for the last parameters in static variables;
+/// here it is recomputed on every call.
+pub fn rhyper<R: Rng + ?Sized>(nn1in: f64, nn2in: f64, kkin: f64, rng: &mut R) -> f64 {
+    // check parameter validity
+
+    if !r_finite(nn1in) || !r_finite(nn2in) || !r_finite(kkin) {
+        return ml_warn_return_nan();
+    }
+
+    let nn1in = r_forceint(nn1in);
+    let nn2in = r_forceint(nn2in);
+    let kkin = r_forceint(kkin);
+
+    if nn1in < 0.0 || nn2in < 0.0 || kkin < 0.0 || kkin > nn1in + nn2in {
+        return ml_warn_return_nan();
+    }
+    let int_max = i32::MAX as f64;
+    if nn1in >= int_max || nn2in >= int_max || kkin >= int_max {
+        // large n -- evade integer overflow (and inappropriate algorithms)
+        // FIXME: Much faster to give rbinom() approx when appropriate; -> see Kuensch(1989)
+        // Johnson, Kotz,.. p.258 (top) mention the *four* different binomial approximations
+        if kkin == 1.0 {
+            // Bernoulli
+            return rbinom(kkin, nn1in / (nn1in + nn2in), rng);
+        }
+        // Slow, but safe: return  F^{-1}(U)  where F(.) = phyper(.) and  U ~ U[0,1]
+        // lower_tail=FALSE: a thinko, is still "correct" as equiv. to  U <--> 1-U
+        return qhyper(rng.unif_rand(), nn1in, nn2in, kkin, false, false);
+    }
+    let nn1 = nn1in as i32;
+    let nn2 = nn2in as i32;
+    let kk = kkin as i32;
+
+    // avoid int overflow
+    let big_n = nn1 as f64 + nn2 as f64;
+    // now have n1 <= n2
+    let (n1, n2) = if nn1 <= nn2 { (nn1, nn2) } else { (nn2, nn1) };
+    let k = if kk as f64 + kk as f64 >= big_n {
+        // this could overflow
+        (big_n - kk as f64) as i32
+    } else {
+        kk
+    };
+
+    // m := floor(adjusted mean E[.])
+    let m = ((k as f64 + 1.) * (n1 as f64 + 1.) / (big_n + 2.)) as i32;
+    let minjx = std::cmp::max(0, k - n2);
+    let maxjx = std::cmp::min(n1, k);
+
+    // generate random variate --- Three basic cases
+
+    let ix = if minjx == maxjx {
+        // I: degenerate distribution
+        maxjx
+    } else if m - minjx < 10 {
+        // II: (Scaled) algorithm HIN (inverse transformation)
+
+        // scaling factor against (early) underflow
+        const SCALE: f64 = 1e25;
+        // 25*log(10) = log(scale) { <==> exp(con) == scale }
+        #[allow(clippy::excessive_precision)]
+        const CON: f64 = 57.5646273248511421;
+
+        // log(w);  w = exp(lw) * scale = exp(lw + log(scale)) = exp(lw + con)
+        let lw = if k < n2 {
+            afc(n2) + afc(n1 + n2 - k) - afc(n2 - k) - afc(n1 + n2)
+        } else {
+            afc(n1) + afc(k) - afc(k - n2) - afc(n1 + n2)
+        };
+        let w = (lw + CON).exp();
+
+        'l10: loop {
+            let mut p = w;
+            let mut ix = minjx;
+            let mut u = rng.unif_rand() * SCALE;
+            while u > p {
+                u -= p;
+                p *= (n1 as f64 - ix as f64) * (k - ix) as f64;
+                ix += 1;
+                p = p / ix as f64 / (n2 - k + ix) as f64;
+                if ix > maxjx {
+                    continue 'l10;
+                }
+                // FIXME  if(p == 0.)  we also "have lost"  => goto L10
+            }
+            break ix;
+        }
+    } else {
+        // III : H2PE Algorithm
+
+        let s =
+            ((big_n - k as f64) * k as f64 * n1 as f64 * n2 as f64 / (big_n - 1.) / big_n / big_n)
+                .sqrt();
+
+        // remark: d is defined in reference without int.
+        // the truncation centers the cell boundaries at 0.5
+
+        let d = (1.5 * s) as i32 as f64 + 0.5;
+        let xl = m as f64 - d + 0.5;
+        let xr = m as f64 + d + 0.5;
+        let a = afc(m) + afc(n1 - m) + afc(k - m) + afc(n2 - k + m);
+        let kl = (a
+            - afc(xl as i32)
+            - afc((n1 as f64 - xl) as i32)
+            - afc((k as f64 - xl) as i32)
+            - afc(((n2 - k) as f64 + xl) as i32))
+        .exp();
+        let kr = (a
+            - afc((xr - 1.) as i32)
+            - afc((n1 as f64 - xr + 1.) as i32)
+            - afc((k as f64 - xr + 1.) as i32)
+            - afc(((n2 - k) as f64 + xr - 1.) as i32))
+        .exp();
+        let lamdl =
+            -(xl * ((n2 - k) as f64 + xl) / (n1 as f64 - xl + 1.) / (k as f64 - xl + 1.)).ln();
+        let lamdr =
+            -((n1 as f64 - xr + 1.) * (k as f64 - xr + 1.) / xr / ((n2 - k) as f64 + xr)).ln();
+        let p1 = d + d;
+        let p2 = p1 + kl / lamdl;
+        let p3 = p2 + kr / lamdr;
+
+        let mut n_uv = 0;
+        loop {
+            let u = rng.unif_rand() * p3;
+            let mut v = rng.unif_rand();
+            n_uv += 1;
+            if n_uv >= 10000 {
+                ml_warning(MlWarningKind::NoConv, "rhyper");
+                return ml_warn_return_nan();
+            }
+
+            let ix;
+            if u < p1 {
+                // rectangular region
+                ix = (xl + u) as i32;
+            } else if u <= p2 {
+                // left tail
+                ix = (xl + v.ln() / lamdl) as i32;
+                if ix < minjx {
+                    continue;
+                }
+                v = v * (u - p1) * lamdl;
+            } else {
+                // right tail
+                ix = (xr - v.ln() / lamdr) as i32;
+                if ix > maxjx {
+                    continue;
+                }
+                v = v * (u - p2) * lamdr;
+            }
+
+            // acceptance/rejection test
+            let reject;
+
+            if m < 100 || ix <= 50 {
+                // explicit evaluation
+                // The original algorithm (and TOMS 668) have
+                //     f = f * i * (n2 - k + i) / (n1 - i) / (k - i);
+                // in the (m > ix) case, but the definition of the
+                // recurrence relation on p134 shows that the +1 is
+                // needed.
+                let mut f = 1.0;
+                if m < ix {
+                    for i in (m + 1)..=ix {
+                        f = f * (n1 - i + 1) as f64 * (k - i + 1) as f64
+                            / (n2 - k + i) as f64
+                            / i as f64;
+                    }
+                } else if m > ix {
+                    for i in (ix + 1)..=m {
+                        f = f * i as f64 * (n2 - k + i) as f64
+                            / (n1 - i + 1) as f64
+                            / (k - i + 1) as f64;
+                    }
+                }
+                reject = v > f;
+            } else {
+                const DELTAL: f64 = 0.0078;
+                const DELTAU: f64 = 0.0034;
+
+                // squeeze using upper and lower bounds
+                let y = ix as f64;
+                let y1 = y + 1.0;
+                let ym = y - m as f64;
+                let yn = n1 as f64 - y + 1.0;
+                let yk = k as f64 - y + 1.0;
+                let nk = (n2 - k) as f64 + y1;
+                let r = -ym / y1;
+                let s = ym / yn;
+                let t = ym / yk;
+                let e = -ym / nk;
+                let g = yn * yk / (y1 * nk) - 1.0;
+                let dg = if g < 0.0 { 1.0 + g } else { 1.0 };
+                let gu = g * (1.0 + g * (-0.5 + g / 3.0));
+                let gl = gu - 0.25 * (g * g * g * g) / dg;
+                let xm = m as f64 + 0.5;
+                let xn = (n1 - m) as f64 + 0.5;
+                let xk = (k - m) as f64 + 0.5;
+                let nm = (n2 - k) as f64 + xm;
+                let ub = y * gu - m as f64 * gl
+                    + DELTAU
+                    + xm * r * (1. + r * (-0.5 + r / 3.0))
+                    + xn * s * (1. + s * (-0.5 + s / 3.0))
+                    + xk * t * (1. + t * (-0.5 + t / 3.0))
+                    + nm * e * (1. + e * (-0.5 + e / 3.0));
+                // test against upper bound
+                let alv = v.ln();
+                if alv > ub {
+                    reject = true;
+                } else {
+                    // test against lower bound
+                    let mut dr = xm * (r * r * r * r);
+                    if r < 0.0 {
+                        dr /= 1.0 + r;
+                    }
+                    let mut ds = xn * (s * s * s * s);
+                    if s < 0.0 {
+                        ds /= 1.0 + s;
+                    }
+                    let mut dt = xk * (t * t * t * t);
+                    if t < 0.0 {
+                        dt /= 1.0 + t;
+                    }
+                    let mut de = nm * (e * e * e * e);
+                    if e < 0.0 {
+                        de /= 1.0 + e;
+                    }
+                    if alv < ub - 0.25 * (dr + ds + dt + de) + (y + m as f64) * (gl - gu) - DELTAL {
+                        reject = false;
+                    } else {
+                        // Stirling's formula to machine accuracy
+                        reject = alv > a - afc(ix) - afc(n1 - ix) - afc(k - ix) - afc(n2 - k + ix);
+                    }
+                }
+            }
+            if !reject {
+                break ix;
+            }
+        }
+    };
+
+    // return appropriate variate
+    let ix = if kk as f64 + kk as f64 >= big_n {
+        if nn1 > nn2 {
+            kk - nn2 + ix
+        } else {
+            nn1 - ix
+        }
+    } else if nn1 > nn2 {
+        kk - ix
+    } else {
+        ix
+    };
+    ix as f64
+}
@@ -0,0 +1,36 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rng::Rng;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 1995, 1996  Robert Gentleman and Ross Ihaka
+//  Copyright (C) 2000--2008 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Random variates from the logistic distribution.
+pub fn rlogis<R: Rng + ?Sized>(location: f64, scale: f64, rng: &mut R) -> f64 {
+    if location.is_nan() || !r_finite(scale) {
+        return ml_warn_return_nan();
+    }
+
+    if scale == 0. || !r_finite(location) {
+        location
+    } else {
+        let u = rng.unif_rand();
+        location + scale * (u / (1. - u)).ln()
+    }
+}
@@ -0,0 +1,56 @@
+use crate::libc::DBL_MAX;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rgamma::rgamma;
+use crate::rng::Rng;
+use crate::rpois::rpois;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000--2016  The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Random variates from the negative binomial distribution.
+///
+/// Uses the gamma-Poisson mixture.
+pub fn rnbinom<R: Rng + ?Sized>(size: f64, prob: f64, rng: &mut R) -> f64 {
+    // prob = 1 is ok, PR#1218
+    if !r_finite(prob) || size.is_nan() || size <= 0.0 || prob <= 0.0 || prob > 1.0 {
+        return ml_warn_return_nan();
+    }
+    // '/2' to prevent rgamma() returning Inf
+    let size = if r_finite(size) { size } else { DBL_MAX / 2.0 };
+    if prob == 1.0 {
+        0.0
+    } else {
+        rpois(rgamma(size, (1.0 - prob) / prob, rng), rng)
+    }
+}
+
+/// Random variates from the negative binomial distribution, parametrized by
+/// the mean `mu`.
+pub fn rnbinom_mu<R: Rng + ?Sized>(size: f64, mu: f64, rng: &mut R) -> f64 {
+    if !r_finite(mu) || size.is_nan() || size <= 0.0 || mu < 0.0 {
+        return ml_warn_return_nan();
+    }
+    let size = if r_finite(size) { size } else { DBL_MAX / 2.0 };
+    if mu == 0.0 {
+        0.0
+    } else {
+        rpois(rgamma(size, mu / size, rng), rng)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::snorm::norm_rand_inversion;
+use crate::sunif::unif_index_rejection;
+
+/// A source of random numbers for the random variate generators in this
+/// crate, e.g., `runif`, `rchisq` and `rgamma`.
+///
+/// This is the Rust counterpart of R's `unif_rand()`, `norm_rand()` and
+/// `R_unif_index()`.
+/// Instead of using a global generator, each random variate generator takes
+/// the source as its last argument.
+/// This allows the same uniform stream to be shared by all generators while
+/// keeping the state owned by the caller.
+pub trait Rng {
+    /// Returns a random number from the uniform distribution on (0, 1).
+    fn unif_rand(&mut self) -> f64;
+
+    /// Returns a random number from the standard normal distribution.
+    ///
+    /// Defaults to R's default normal kind, "Inversion".
+    fn norm_rand(&mut self) -> f64 {
+        norm_rand_inversion(self)
+    }
+
+    /// Returns a random integer in `0..dn`, as `f64`.
+    ///
+    /// Defaults to R's default sample kind, "Rejection".
+    fn unif_index(&mut self, dn: f64) -> f64 {
+        unif_index_rejection(dn, self)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rng::Rng;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Random variates from the normal distribution.
+pub fn rnorm<R: Rng + ?Sized>(mu: f64, sigma: f64, rng: &mut R) -> f64 {
+    if mu.is_nan() || !r_finite(sigma) || sigma < 0. {
+        return ml_warn_return_nan();
+    }
+    if sigma == 0. || !r_finite(mu) {
+        mu // includes mu = +/- Inf with finite sigma
+    } else {
+        mu + sigma * rng.norm_rand()
+    }
+}
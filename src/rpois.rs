@@ -0,0 +1,215 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rmath::M_1_SQRT_2PI;
+use crate::rng::Rng;
+use crate::sexp::exp_rand;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000-2011 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+const A0: f64 = -0.5;
+const A1: f64 = 0.3333333;
+const A2: f64 = -0.2500068;
+const A3: f64 = 0.2000118;
+const A4: f64 = -0.1661269;
+const A5: f64 = 0.1421878;
+const A6: f64 = -0.1384794;
+const A7: f64 = 0.1250060;
+
+#[allow(clippy::excessive_precision)]
+const ONE_7: f64 = 0.1428571428571428571;
+#[allow(clippy::excessive_precision)]
+const ONE_12: f64 = 0.0833333333333333333;
+#[allow(clippy::excessive_precision)]
+const ONE_24: f64 = 0.0416666666666666667;
+
+/// Factorial Table (0:9)!
+const FACT: [f64; 10] = [1., 1., 2., 6., 24., 120., 720., 5040., 40320., 362880.];
+
+/// Random variates from the Poisson distribution.
+///
+/// # Reference
+///
+/// Ahrens, J.H. and Dieter, U. (1982).
+/// Computer generation of Poisson deviates
+/// from modified normal distributions.
+/// ACM Trans. Math. Software 8, 163-179.
+///
+/// The C code keeps the table and the setup for the last `mu` in static
+/// variables; here they are recomputed on every call.
+pub fn rpois<R: Rng + ?Sized>(mu: f64, rng: &mut R) -> f64 {
+    if !r_finite(mu) || mu < 0.0 {
+        return ml_warn_return_nan();
+    }
+
+    if mu <= 0.0 {
+        return 0.0;
+    }
+
+    if mu < 10.0 {
+        // Small mu ( < 10) -- not using normal approx.
+
+        // Case B. (start new table and calculate p0 if necessary)
+        let m = std::cmp::max(1, mu as i32) as usize;
+        let mut l = 0; // pp[] is already ok up to pp[l]
+        let p0 = (-mu).exp();
+        let mut p = p0;
+        let mut q = p0;
+        let mut pp = [0.0; 36];
+
+        loop {
+            // Step U. uniform sample for inversion method
+            let u = rng.unif_rand();
+            if u <= p0 {
+                return 0.0;
+            }
+
+            // Step T. table comparison until the end pp[l] of the
+            // pp-table of cumulative poisson probabilities
+            // (0.458 > ~= pp[9](= 0.45792971447) for mu=10 )
+            if l != 0 {
+                let start = if u <= 0.458 { 1 } else { std::cmp::min(l, m) };
+                for (k, &ppk) in pp.iter().enumerate().take(l + 1).skip(start) {
+                    if u <= ppk {
+                        return k as f64;
+                    }
+                }
+                if l == 35 {
+                    // u > pp[35]
+                    continue;
+                }
+            }
+            // Step C. creation of new poisson
+            // probabilities p[l..] and their cumulatives q =: pp[k]
+            l += 1;
+            for (k, ppk) in pp.iter_mut().enumerate().skip(l) {
+                p *= mu / k as f64;
+                q += p;
+                *ppk = q;
+                if u <= q {
+                    return k as f64;
+                }
+            }
+            l = 35;
+        }
+    }
+
+    // Only if mu >= 10 :
+
+    // Case A. The poisson probabilities pk exceed the discrete normal
+    // probabilities fk whenever k >= m(mu).
+    let s = mu.sqrt();
+    let d = 6. * mu * mu;
+    // = an upper bound to m(mu) for all mu >= 10.
+    let big_l = (mu - 1.1484).floor();
+
+    let mut pois = -1.0;
+    let mut fk = 0.0;
+    let mut difmuk = 0.0;
+    let mut u = 0.0;
+
+    // Step N. normal sample
+    let g = mu + s * rng.norm_rand();
+
+    if g >= 0.0 {
+        pois = g.floor();
+        // Step I. immediate acceptance if pois is large enough
+        if pois >= big_l {
+            return pois;
+        }
+        // Step S. squeeze acceptance
+        fk = pois;
+        difmuk = mu - fk;
+        u = rng.unif_rand(); // ~ U(0,1) - sample
+        if d * u >= difmuk * difmuk * difmuk {
+            return pois;
+        }
+    }
+
+    // Step P. preparations for steps Q and H.
+    let omega = M_1_SQRT_2PI / s;
+    // The quantities b1, b2, c3, c2, c1, c0 are for the Hermite
+    // approximations to the discrete normal probabilities fk.
+    let b1 = ONE_24 / mu;
+    let b2 = 0.3 * b1 * b1;
+    let c3 = ONE_7 * b1 * b2;
+    let c2 = b2 - 15. * c3;
+    let c1 = b1 - 6. * b2 + 45. * c3;
+    let c0 = 1. - b1 + 3. * b2 - 15. * c3;
+    let c = 0.1069 / mu; // guarantees majorization by the 'hat'-function.
+
+    // 'Subroutine' F : calculation of px,py,fx,fy.
+    let step_f = |pois: f64, fk: f64, difmuk: f64| -> (f64, f64, f64, f64) {
+        let (px, py) = if pois < 10.0 {
+            // use factorials from table fact[]
+            (-mu, mu.powf(pois) / FACT[pois as usize])
+        } else {
+            // Case pois >= 10 uses polynomial approximation
+            // a0-a7 for accuracy when advisable
+            let mut del = ONE_12 / fk;
+            del *= 1. - 4.8 * del * del;
+            let v = difmuk / fk;
+            let px = if v.abs() <= 0.25 {
+                fk * v
+                    * v
+                    * (((((((A7 * v + A6) * v + A5) * v + A4) * v + A3) * v + A2) * v + A1) * v
+                        + A0)
+                    - del
+            } else {
+                // |v| > 1/4
+                fk * (1. + v).ln() - difmuk - del
+            };
+            (px, M_1_SQRT_2PI / fk.sqrt())
+        };
+        let mut x = (0.5 - difmuk) / s;
+        x *= x; // x^2
+        let fx = -0.5 * x;
+        let fy = omega * (((c3 * x + c2) * x + c1) * x + c0);
+        (px, py, fx, fy)
+    };
+
+    if g >= 0.0 {
+        let (px, py, fx, fy) = step_f(pois, fk, difmuk);
+        // Step Q. Quotient acceptance (rare case)
+        if fy - u * fy <= py * (px - fx).exp() {
+            return pois;
+        }
+    }
+
+    loop {
+        // Step E. Exponential Sample
+        let e = exp_rand(rng); // ~ Exp(1) (standard exponential)
+
+        // sample t from the laplace 'hat'
+        // (if t <= -0.6744 then pk < fk for all mu >= 10.)
+        u = 2. * rng.unif_rand() - 1.;
+        let t = 1.8 + if u >= 0.0 { e.abs() } else { -e.abs() };
+        if t > -0.6744 {
+            pois = (mu + s * t).floor();
+            fk = pois;
+            difmuk = mu - fk;
+
+            let (px, py, fx, fy) = step_f(pois, fk, difmuk);
+            // Step H. Hat acceptance (E is repeated on rejection)
+            if c * u.abs() <= py * (px + e).exp() - fy * (fx + e).exp() {
+                return pois;
+            }
+        }
+    }
+}
@@ -0,0 +1,924 @@
+use crate::rng::Rng;
+use crate::snorm::norm_rand_kind;
+use crate::snorm::NormKind;
+use crate::sunif::unif_index_kind;
+use crate::sunif::SampleKind;
+use std::error::Error;
+use std::fmt;
+
+//
+//  R : A Computer Language for Statistical Data Analysis
+//  Copyright (C) 1997--2018  The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+// From src/main/RNG.c of R.
+
+/// = 1/(2^32 - 1)
+const I2_32M1: f64 = 2.328306437080797e-10;
+/// = 2^-30
+const KT: f64 = 9.31322574615479e-10;
+
+/// The moduli of the two components of L'Ecuyer-CMRG.
+const M1: i64 = 4294967087;
+const M2: i64 = 4294944443;
+
+// The matrices to jump ahead L'Ecuyer-CMRG by 2^76 and 2^127 steps, from
+// src/library/parallel/src/rngstream.c of R.
+const A1P76: [[u64; 3]; 3] = [
+    [82758667, 1871391091, 4127413238],
+    [3672831523, 69195019, 1871391091],
+    [3672091415, 3528743235, 69195019],
+];
+const A2P76: [[u64; 3]; 3] = [
+    [1511326704, 3759209742, 1610795712],
+    [4292754251, 1511326704, 3889917532],
+    [3859662829, 4292754251, 3708466080],
+];
+const A1P127: [[u64; 3]; 3] = [
+    [2427906178, 3580155704, 949770784],
+    [226153695, 1230515664, 3580155704],
+    [1988835001, 986791581, 1230515664],
+];
+const A2P127: [[u64; 3]; 3] = [
+    [1464411153, 277697599, 1610723613],
+    [32183930, 1464411153, 1022607788],
+    [2824425944, 32183930, 2093834863],
+];
+
+/// The uniform random number generators of R, as chosen by `RNGkind()`.
+///
+/// R's "User-supplied" kind is left out: implement `Rng` instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RngKind {
+    /// "Wichmann-Hill", period about 7e12.
+    WichmannHill,
+    /// "Marsaglia-Multicarry", a multiply-with-carry generator with period
+    /// more than 2^60.
+    MarsagliaMulticarry,
+    /// "Super-Duper", Marsaglia's generator with period about 4.6e18, as
+    /// implemented by Reeds et al (1984).
+    SuperDuper,
+    /// "Mersenne-Twister", Matsumoto and Nishimura (1998), with period
+    /// 2^19937 - 1.
+    /// This is R's default.
+    MersenneTwister,
+    /// "Knuth-TAOCP", the 1997 version of Knuth's lagged Fibonacci
+    /// generator.
+    KnuthTaocp,
+    /// "Knuth-TAOCP-2002", the 2002 version of Knuth's generator, which
+    /// differs only in its initialization.
+    KnuthTaocp2002,
+    /// "L'Ecuyer-CMRG", L'Ecuyer's (1999) combined multiple-recursive
+    /// generator with period about 2^191.
+    LecuyerCmrg,
+}
+
+impl Default for RngKind {
+    fn default() -> Self {
+        RngKind::MersenneTwister
+    }
+}
+
+impl RngKind {
+    /// The name of this kind in R, e.g., "Mersenne-Twister".
+    pub fn name(self) -> &'static str {
+        match self {
+            RngKind::WichmannHill => "Wichmann-Hill",
+            RngKind::MarsagliaMulticarry => "Marsaglia-Multicarry",
+            RngKind::SuperDuper => "Super-Duper",
+            RngKind::MersenneTwister => "Mersenne-Twister",
+            RngKind::KnuthTaocp => "Knuth-TAOCP",
+            RngKind::KnuthTaocp2002 => "Knuth-TAOCP-2002",
+            RngKind::LecuyerCmrg => "L'Ecuyer-CMRG",
+        }
+    }
+
+    /// The code of this kind in `.Random.seed`, see `RNGtype` in R.
+    fn code(self) -> i32 {
+        match self {
+            RngKind::WichmannHill => 0,
+            RngKind::MarsagliaMulticarry => 1,
+            RngKind::SuperDuper => 2,
+            RngKind::MersenneTwister => 3,
+            RngKind::KnuthTaocp => 4,
+            // 5 is USER_UNIF
+            RngKind::KnuthTaocp2002 => 6,
+            RngKind::LecuyerCmrg => 7,
+        }
+    }
+
+    fn from_code(code: i32) -> Option<Self> {
+        match code {
+            0 => Some(RngKind::WichmannHill),
+            1 => Some(RngKind::MarsagliaMulticarry),
+            2 => Some(RngKind::SuperDuper),
+            3 => Some(RngKind::MersenneTwister),
+            4 => Some(RngKind::KnuthTaocp),
+            6 => Some(RngKind::KnuthTaocp2002),
+            7 => Some(RngKind::LecuyerCmrg),
+            _ => None,
+        }
+    }
+
+    /// The number of seeds used by this kind.
+    fn n_seed(self) -> usize {
+        match self {
+            RngKind::WichmannHill => 3,
+            RngKind::MarsagliaMulticarry => 2,
+            RngKind::SuperDuper => 2,
+            // mti and the 624 words of the state
+            RngKind::MersenneTwister => 1 + 624,
+            // the state and KT_pos
+            RngKind::KnuthTaocp | RngKind::KnuthTaocp2002 => 1 + 100,
+            RngKind::LecuyerCmrg => 6,
+        }
+    }
+}
+
+/// The code of a normal kind in `.Random.seed`, see `N01type` in R.
+fn norm_kind_code(kind: NormKind) -> i32 {
+    match kind {
+        NormKind::BuggyKindermanRamage => 0,
+        NormKind::AhrensDieter => 1,
+        NormKind::BoxMuller => 2,
+        // 3 is USER_NORM
+        NormKind::Inversion => 4,
+        NormKind::KindermanRamage => 5,
+    }
+}
+
+fn norm_kind_from_code(code: i32) -> Option<NormKind> {
+    match code {
+        0 => Some(NormKind::BuggyKindermanRamage),
+        1 => Some(NormKind::AhrensDieter),
+        2 => Some(NormKind::BoxMuller),
+        4 => Some(NormKind::Inversion),
+        5 => Some(NormKind::KindermanRamage),
+        _ => None,
+    }
+}
+
+/// The code of a sample kind in `.Random.seed`, see `Sampletype` in R.
+fn sample_kind_code(kind: SampleKind) -> i32 {
+    match kind {
+        SampleKind::Rounding => 0,
+        SampleKind::Rejection => 1,
+    }
+}
+
+fn sample_kind_from_code(code: i32) -> Option<SampleKind> {
+    match code {
+        0 => Some(SampleKind::Rounding),
+        1 => Some(SampleKind::Rejection),
+        _ => None,
+    }
+}
+
+/// The error returned when a `.Random.seed` vector cannot be restored by
+/// `RRng::from_random_seed`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RandomSeedError {
+    /// The vector is empty.
+    Empty,
+    /// Element 0 is not the code of a kind supported by `RRng`, e.g., it
+    /// uses a user-supplied generator.
+    InvalidKind(i32),
+    /// The vector has fewer seeds than the kind needs.
+    WrongLength {
+        /// The kind given by element 0.
+        kind: RngKind,
+        /// The length of the vector.
+        len: usize,
+    },
+    /// The seeds are not valid for the kind, e.g., all zero.
+    InvalidSeeds(RngKind),
+}
+
+impl fmt::Display for RandomSeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RandomSeedError::Empty => write!(f, "'.Random.seed' is empty"),
+            RandomSeedError::InvalidKind(code) => {
+                write!(f, "'.Random.seed[1]' = {} is not a valid kind code", code)
+            }
+            RandomSeedError::WrongLength { kind, len } => write!(
+                f,
+                "'.Random.seed' has wrong length: {} for {}, which needs {}",
+                len,
+                kind.name(),
+                kind.n_seed() + 1
+            ),
+            RandomSeedError::InvalidSeeds(kind) => {
+                write!(
+                    f,
+                    "'.Random.seed' is not a valid set of seeds for {}",
+                    kind.name()
+                )
+            }
+        }
+    }
+}
+
+impl Error for RandomSeedError {}
+
+/// R's uniform random number generators with R's seeding, so that
+/// `set.seed()` streams are reproduced exactly.
+///
+/// As in R, all kinds share one seed array, and `set_kind` seeds the new
+/// kind from the current one like `RNGkind()` does.
+/// Normal random numbers are generated by the kind set by `set_norm_kind`,
+/// which defaults to "Inversion", and random indices by the kind set by
+/// `set_sample_kind`, which defaults to "Rejection".
+///
+/// The whole state can be saved and restored in the format of R's
+/// `.Random.seed` with `random_seed` and `from_random_seed`.
+/// The generator is owned by the caller and passed to the random variate
+/// generators, e.g., `runif`, via the `Rng` trait.
+///
+/// ```
+/// use rmathlib::*;
+///
+/// // set.seed(1); runif(1)
+/// let mut rng = RRng::new(1, RngKind::MersenneTwister);
+/// let u = runif(0.0, 1.0, &mut rng);
+/// assert!((u - 0.2655087).abs() < 1e-7);
+///
+/// // set.seed(1); rnorm(1)
+/// rng.set_seed(1);
+/// let x = rnorm(0.0, 1.0, &mut rng);
+/// assert!((x - -0.6264538).abs() < 1e-7);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RRng {
+    kind: RngKind,
+    norm_kind: NormKind,
+    sample_kind: SampleKind,
+    /// `dummy` in R; the first `kind.n_seed()` values are the seeds.
+    seed: [u32; 625],
+    /// The second normal of a Box-Muller pair, or 0.0 if there is none.
+    bm_norm_keep: f64,
+}
+
+impl RRng {
+    /// Creates a generator of the given kind, seeded like
+    /// `set.seed(seed, kind)`.
+    pub fn new(seed: i32, kind: RngKind) -> Self {
+        let mut rng = RRng {
+            kind,
+            norm_kind: NormKind::default(),
+            sample_kind: SampleKind::default(),
+            seed: [0; 625],
+            bm_norm_keep: 0.0,
+        };
+        rng.set_seed(seed);
+        rng
+    }
+
+    /// The kind of this generator.
+    pub fn kind(&self) -> RngKind {
+        self.kind
+    }
+
+    /// The normal kind of this generator.
+    pub fn norm_kind(&self) -> NormKind {
+        self.norm_kind
+    }
+
+    /// The sample kind of this generator.
+    pub fn sample_kind(&self) -> SampleKind {
+        self.sample_kind
+    }
+
+    /// The state of this generator in the format of R's `.Random.seed`.
+    ///
+    /// Element 0 is the code of the kinds,
+    /// `kind + 100 * norm_kind + 10000 * sample_kind`, followed by the seeds
+    /// of the kind.
+    /// For "Mersenne-Twister", the first seed is the position `mti` in the
+    /// state, and for the Knuth kinds, the last seed is the position in the
+    /// buffer.
+    ///
+    /// R does not save the second normal of a Box-Muller pair.
+    /// If there is one, it is appended as two more elements, the low and
+    /// high 32 bits of the `f64`, which R ignores when restoring the vector.
+    pub fn random_seed(&self) -> Vec<i32> {
+        let n_seed = self.kind.n_seed();
+        let mut seed = Vec::with_capacity(n_seed + 3);
+        seed.push(
+            self.kind.code()
+                + 100 * norm_kind_code(self.norm_kind)
+                + 10000 * sample_kind_code(self.sample_kind),
+        );
+        seed.extend(self.seed[..n_seed].iter().map(|&s| s as i32));
+        if self.norm_kind == NormKind::BoxMuller && self.bm_norm_keep != 0.0 {
+            let bits = self.bm_norm_keep.to_bits();
+            seed.push(bits as u32 as i32);
+            seed.push((bits >> 32) as u32 as i32);
+        }
+        seed
+    }
+
+    /// Restores a generator from the format of R's `.Random.seed`, see
+    /// `random_seed`, so that it continues the stream where it was saved.
+    ///
+    /// As in R, the seeds are fixed up for the kind, e.g., taken modulo the
+    /// moduli for "Wichmann-Hill".
+    /// Elements after the seeds are ignored, except for the second normal of
+    /// a Box-Muller pair as appended by `random_seed`.
+    pub fn from_random_seed(random_seed: &[i32]) -> Result<RRng, RandomSeedError> {
+        let code = *random_seed.first().ok_or(RandomSeedError::Empty)?;
+        if code < 0 {
+            return Err(RandomSeedError::InvalidKind(code));
+        }
+        let kinds = (
+            RngKind::from_code(code % 100),
+            norm_kind_from_code(code % 10000 / 100),
+            sample_kind_from_code(code / 10000),
+        );
+        let (kind, norm_kind, sample_kind) = match kinds {
+            (Some(kind), Some(norm_kind), Some(sample_kind)) => (kind, norm_kind, sample_kind),
+            _ => return Err(RandomSeedError::InvalidKind(code)),
+        };
+        let n_seed = kind.n_seed();
+        if random_seed.len() < n_seed + 1 {
+            return Err(RandomSeedError::WrongLength {
+                kind,
+                len: random_seed.len(),
+            });
+        }
+        let mut rng = RRng {
+            kind,
+            norm_kind,
+            sample_kind,
+            seed: [0; 625],
+            bm_norm_keep: 0.0,
+        };
+        for (s, &x) in rng.seed.iter_mut().zip(&random_seed[1..=n_seed]) {
+            *s = x as u32;
+        }
+        if !rng.fixup_seeds(false) {
+            return Err(RandomSeedError::InvalidSeeds(kind));
+        }
+        if norm_kind == NormKind::BoxMuller && random_seed.len() >= n_seed + 3 {
+            let lo = random_seed[n_seed + 1] as u32 as u64;
+            let hi = random_seed[n_seed + 2] as u32 as u64;
+            rng.bm_norm_keep = f64::from_bits(hi << 32 | lo);
+        }
+        Ok(rng)
+    }
+
+    /// Seeds the generator like `set.seed(seed)`.
+    ///
+    /// The seed is scrambled by 50 steps of the linear congruential
+    /// generator `seed = 69069 * seed + 1`, which then fills the seeds of
+    /// the current kind.
+    pub fn set_seed(&mut self, seed: i32) {
+        self.rng_init(seed as u32);
+    }
+
+    /// Switches to another kind like `RNGkind(kind)`.
+    ///
+    /// The new kind is seeded from a uniform draw of the current one.
+    pub fn set_kind(&mut self, kind: RngKind) {
+        let u = self.unif_rand();
+        self.kind = kind;
+        self.rng_init((u * u32::MAX as f64) as u32);
+    }
+
+    /// Switches to another normal kind like `RNGkind(normal.kind = kind)`.
+    ///
+    /// The uniform stream is not affected.
+    pub fn set_norm_kind(&mut self, kind: NormKind) {
+        if kind == NormKind::BoxMuller {
+            self.bm_norm_keep = 0.0; // zap Box-Muller history
+        }
+        self.norm_kind = kind;
+    }
+
+    /// Switches to another sample kind like `RNGkind(sample.kind = kind)`.
+    ///
+    /// The uniform stream is not affected.
+    pub fn set_sample_kind(&mut self, kind: SampleKind) {
+        self.sample_kind = kind;
+    }
+
+    /// The start of the next stream like `nextRNGStream()` of R's
+    /// `parallel` package, i.e., this generator advanced by 2^127 steps.
+    ///
+    /// Streams are independent for all practical purposes, so each thread
+    /// of a simulation can own one, and the results do not depend on the
+    /// scheduling:
+    ///
+    /// ```
+    /// use rmathlib::*;
+    ///
+    /// // RNGkind("L'Ecuyer-CMRG"); set.seed(1)
+    /// let mut stream = RRng::new(1, RngKind::LecuyerCmrg);
+    /// let mut handles = Vec::new();
+    /// for _ in 0..4 {
+    ///     stream = stream.next_rng_stream().unwrap();
+    ///     let mut rng = stream.clone();
+    ///     handles.push(std::thread::spawn(move || rnorm(0.0, 1.0, &mut rng)));
+    /// }
+    /// let x: Vec<f64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    /// assert_eq!(x.len(), 4);
+    /// ```
+    ///
+    /// The normal and sample kinds are kept, but a pending Box-Muller
+    /// normal is not, as in R.
+    /// Returns `None` unless the kind is "L'Ecuyer-CMRG", where R signals
+    /// an error.
+    pub fn next_rng_stream(&self) -> Option<RRng> {
+        self.jump(&A1P127, &A2P127)
+    }
+
+    /// The start of the next substream like `nextRNGSubStream()` of R's
+    /// `parallel` package, i.e., this generator advanced by 2^76 steps.
+    ///
+    /// Each stream holds 2^51 substreams.
+    /// Returns `None` unless the kind is "L'Ecuyer-CMRG".
+    pub fn next_rng_substream(&self) -> Option<RRng> {
+        self.jump(&A1P76, &A2P76)
+    }
+
+    fn jump(&self, a1: &[[u64; 3]; 3], a2: &[[u64; 3]; 3]) -> Option<RRng> {
+        if self.kind != RngKind::LecuyerCmrg {
+            return None;
+        }
+        let mut rng = self.clone();
+        mat_vec_mod_m(a1, &mut rng.seed[..3], M1 as u64);
+        mat_vec_mod_m(a2, &mut rng.seed[3..6], M2 as u64);
+        rng.bm_norm_keep = 0.0;
+        Some(rng)
+    }
+
+    fn rng_init(&mut self, seed: u32) {
+        let mut seed = seed;
+        self.bm_norm_keep = 0.0; // zap Box-Muller history
+                                 // Initial scrambling
+        for _ in 0..50 {
+            seed = lcg(seed);
+        }
+        match self.kind {
+            RngKind::WichmannHill
+            | RngKind::MarsagliaMulticarry
+            | RngKind::SuperDuper
+            | RngKind::MersenneTwister => {
+                // i_seed[0] is mti, *but* this is needed for historical consistency
+                for s in self.seed.iter_mut().take(self.kind.n_seed()) {
+                    seed = lcg(seed);
+                    *s = seed;
+                }
+                self.fixup_seeds(true);
+            }
+            RngKind::KnuthTaocp => {
+                ran_start_1997(seed % 1073741821, &mut self.seed[..100]);
+                self.seed[100] = 100; // KT_pos
+            }
+            RngKind::KnuthTaocp2002 => {
+                ran_start(seed % 1073741821, &mut self.seed[..100]);
+                self.seed[100] = 100; // KT_pos
+            }
+            RngKind::LecuyerCmrg => {
+                for s in self.seed.iter_mut().take(self.kind.n_seed()) {
+                    seed = lcg(seed);
+                    while seed as i64 >= M2 {
+                        seed = lcg(seed);
+                    }
+                    *s = seed;
+                }
+            }
+        }
+    }
+
+    /// Depending on the kind, set 0 values to non-0, etc.
+    ///
+    /// Returns false for seeds that R would replace by a time-based seed,
+    /// i.e., when all seeds are zero or, for L'Ecuyer-CMRG, out of range.
+    /// That cannot happen for seeds set by `set_seed`.
+    fn fixup_seeds(&mut self, initial: bool) -> bool {
+        let s = &mut self.seed;
+        match self.kind {
+            RngKind::WichmannHill => {
+                s[0] %= 30269;
+                s[1] %= 30307;
+                s[2] %= 30323;
+                // map values equal to 0 mod modulus to 1.
+                for x in s.iter_mut().take(3) {
+                    if *x == 0 {
+                        *x = 1;
+                    }
+                }
+                true
+            }
+            RngKind::SuperDuper => {
+                if s[0] == 0 {
+                    s[0] = 1;
+                }
+                // I2 = Congruential: must be ODD
+                s[1] |= 1;
+                true
+            }
+            RngKind::MarsagliaMulticarry => {
+                for x in s.iter_mut().take(2) {
+                    if *x == 0 {
+                        *x = 1;
+                    }
+                }
+                true
+            }
+            RngKind::MersenneTwister => {
+                if initial {
+                    s[0] = 624;
+                }
+                // No action unless user has corrupted .Random.seed
+                if s[0] as i32 <= 0 {
+                    s[0] = 624;
+                }
+                // check for all zeroes
+                s[1..625].iter().any(|&x| x != 0)
+            }
+            RngKind::KnuthTaocp | RngKind::KnuthTaocp2002 => {
+                if s[100] as i32 <= 0 {
+                    s[100] = 100;
+                }
+                // check for all zeroes
+                s[..100].iter().any(|&x| x != 0)
+            }
+            RngKind::LecuyerCmrg => {
+                // first set: not all zero, in [0, m1)
+                // second set: not all zero, in [0, m2)
+                s[..3].iter().any(|&x| x != 0)
+                    && s[..3].iter().all(|&x| (x as i64) < M1)
+                    && s[3..6].iter().any(|&x| x != 0)
+                    && s[3..6].iter().all(|&x| (x as i64) < M2)
+            }
+        }
+    }
+
+    fn mt_genrand(&mut self) -> f64 {
+        const N: usize = 624;
+        const M: usize = 397;
+        const MATRIX_A: u32 = 0x9908b0df; // constant vector a
+        const UPPER_MASK: u32 = 0x80000000; // most significant w-r bits
+        const LOWER_MASK: u32 = 0x7fffffff; // least significant r bits
+        const TEMPERING_MASK_B: u32 = 0x9d2c5680;
+        const TEMPERING_MASK_C: u32 = 0xefc60000;
+        const MAG01: [u32; 2] = [0x0, MATRIX_A];
+
+        let mut mti = self.seed[0] as i32 as usize;
+        let mt = &mut self.seed[1..];
+
+        if mti >= N {
+            // generate N words at one time
+            if mti == N + 1 {
+                // if sgenrand() has not been called, a default initial seed is used
+                mt_sgenrand(4357, mt);
+            }
+            for kk in 0..N - M {
+                let y = (mt[kk] & UPPER_MASK) | (mt[kk + 1] & LOWER_MASK);
+                mt[kk] = mt[kk + M] ^ (y >> 1) ^ MAG01[(y & 0x1) as usize];
+            }
+            for kk in N - M..N - 1 {
+                let y = (mt[kk] & UPPER_MASK) | (mt[kk + 1] & LOWER_MASK);
+                mt[kk] = mt[kk + M - N] ^ (y >> 1) ^ MAG01[(y & 0x1) as usize];
+            }
+            let y = (mt[N - 1] & UPPER_MASK) | (mt[0] & LOWER_MASK);
+            mt[N - 1] = mt[M - 1] ^ (y >> 1) ^ MAG01[(y & 0x1) as usize];
+            mti = 0;
+        }
+
+        let mut y = mt[mti];
+        mti += 1;
+        y ^= y >> 11;
+        y ^= (y << 7) & TEMPERING_MASK_B;
+        y ^= (y << 15) & TEMPERING_MASK_C;
+        y ^= y >> 18;
+        self.seed[0] = mti as u32;
+
+        y as f64 * 2.3283064365386963e-10 // reals: [0,1)-interval
+    }
+
+    fn kt_next(&mut self) -> u32 {
+        if self.seed[100] >= 100 {
+            ran_arr_cycle(&mut self.seed[..100]);
+            self.seed[100] = 0;
+        }
+        let pos = self.seed[100] as usize;
+        self.seed[100] += 1;
+        self.seed[pos]
+    }
+}
+
+impl Rng for RRng {
+    fn unif_rand(&mut self) -> f64 {
+        let s = &mut self.seed;
+        match self.kind {
+            RngKind::WichmannHill => {
+                s[0] = s[0] * 171 % 30269;
+                s[1] = s[1] * 172 % 30307;
+                s[2] = s[2] * 170 % 30323;
+                let value = s[0] as f64 / 30269.0 + s[1] as f64 / 30307.0 + s[2] as f64 / 30323.0;
+                fixup(value - value.trunc()) // in [0,1)
+            }
+            RngKind::MarsagliaMulticarry => {
+                // 0177777(octal) == 65535(decimal)
+                s[0] = 36969u32
+                    .wrapping_mul(s[0] & 0o177777)
+                    .wrapping_add(s[0] >> 16);
+                s[1] = 18000u32
+                    .wrapping_mul(s[1] & 0o177777)
+                    .wrapping_add(s[1] >> 16);
+                fixup(((s[0] << 16) ^ (s[1] & 0o177777)) as f64 * I2_32M1) // in [0,1)
+            }
+            RngKind::SuperDuper => {
+                // This is Reeds et al (1984) implementation;
+                // modified using __unsigned__	seeds instead of signed ones
+                s[0] ^= (s[0] >> 15) & 0o377777; // Tausworthe
+                s[0] ^= s[0] << 17;
+                s[1] = s[1].wrapping_mul(69069); // Congruential
+                fixup((s[0] ^ s[1]) as f64 * I2_32M1) // in [0,1)
+            }
+            RngKind::MersenneTwister => fixup(self.mt_genrand()),
+            RngKind::KnuthTaocp | RngKind::KnuthTaocp2002 => fixup(self.kt_next() as f64 * KT),
+            RngKind::LecuyerCmrg => {
+                // Based loosely on the GPL-ed version of
+                // http://www.iro.umontreal.ca/~lecuyer/myftp/streams00/c2010/RngStream.c
+                // but using int_least64_t, which C99 guarantees.
+                #[allow(clippy::excessive_precision)]
+                const NORMC: f64 = 2.328306549295727688e-10;
+                const A12: i64 = 1403580;
+                const A13N: i64 = 810728;
+                const A21: i64 = 527612;
+                const A23N: i64 = 1370589;
+
+                let mut p1 = A12 * s[1] as i64 - A13N * s[0] as i64;
+                // p1 % m1 would surely do
+                let k = p1 / M1;
+                p1 -= k * M1;
+                if p1 < 0 {
+                    p1 += M1;
+                }
+                s[0] = s[1];
+                s[1] = s[2];
+                s[2] = p1 as u32;
+
+                let mut p2 = A21 * s[5] as i64 - A23N * s[3] as i64;
+                let k = p2 / M2;
+                p2 -= k * M2;
+                if p2 < 0 {
+                    p2 += M2;
+                }
+                s[3] = s[4];
+                s[4] = s[5];
+                s[5] = p2 as u32;
+
+                (if p1 > p2 { p1 - p2 } else { p1 - p2 + M1 }) as f64 * NORMC
+            }
+        }
+    }
+
+    fn norm_rand(&mut self) -> f64 {
+        let mut bm_norm_keep = self.bm_norm_keep;
+        let x = norm_rand_kind(self.norm_kind, &mut bm_norm_keep, self);
+        self.bm_norm_keep = bm_norm_keep;
+        x
+    }
+
+    fn unif_index(&mut self, dn: f64) -> f64 {
+        unif_index_kind(self.sample_kind, dn, self)
+    }
+}
+
+/// Computes `s = a * s mod m`.
+fn mat_vec_mod_m(a: &[[u64; 3]; 3], s: &mut [u32], m: u64) {
+    let mut x = [0u64; 3];
+    for (xi, row) in x.iter_mut().zip(a) {
+        for (&aij, &sj) in row.iter().zip(s.iter()) {
+            // both are below 2^32, so neither the product nor the sum overflows
+            *xi = (*xi + aij * sj as u64 % m) % m;
+        }
+    }
+    for (si, xi) in s.iter_mut().zip(&x) {
+        *si = *xi as u32;
+    }
+}
+
+/// The linear congruential generator used for seeding.
+fn lcg(seed: u32) -> u32 {
+    seed.wrapping_mul(69069).wrapping_add(1)
+}
+
+/// Ensure 0 and 1 are never returned.
+fn fixup(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.5 * I2_32M1;
+    }
+    if (1.0 - x) <= 0.0 {
+        return 1.0 - 0.5 * I2_32M1;
+    }
+    x
+}
+
+// ===================  Mersenne Twister ==========================
+//
+// A C-program for MT19937: Real number version([0,1)-interval)
+// (1999/10/28)
+//   genrand() generates one pseudorandom real number (double)
+// which is uniformly distributed on [0,1)-interval, for each
+// call. sgenrand(seed) sets initial values to the working area
+// of 624 words.
+//
+// Copyright (C) 1997, 1999 Makoto Matsumoto and Takuji Nishimura.
+// When you use this, send an email to: matumoto@math.keio.ac.jp
+// with an appropriate reference to your work.
+//
+// REFERENCE
+// M. Matsumoto and T. Nishimura,
+// "Mersenne Twister: A 623-Dimensionally Equidistributed Uniform
+// Pseudo-Random Number Generator",
+// ACM Transactions on Modeling and Computer Simulation,
+// Vol. 8, No. 1, January 1998, pp 3--30.
+
+/// Initializing the array with a seed.
+///
+/// Only used when the state was never initialized, i.e., mti is N+1.
+fn mt_sgenrand(seed: u32, mt: &mut [u32]) {
+    let mut seed = seed;
+    for m in mt.iter_mut().take(624) {
+        *m = seed & 0xffff0000;
+        seed = lcg(seed);
+        *m |= (seed & 0xffff0000) >> 16;
+        seed = lcg(seed);
+    }
+}
+
+// ===================  Knuth TAOCP  2002 ==========================
+//
+// This program by D E Knuth is in the public domain and freely copyable.
+// It is explained in Seminumerical Algorithms, 3rd edition, Section 3.6
+// (or in the errata to the 2nd edition --- see
+//     http://www-cs-faculty.stanford.edu/~knuth/taocp.html
+// in the changes to Volume 2 on pages 171 and following).
+
+/// the long lag
+const KK: usize = 100;
+/// the short lag
+const LL: usize = 37;
+/// the modulus
+const MM: u32 = 1 << 30;
+/// guaranteed separation between streams
+const TT: i32 = 70;
+/// recommended quality level for high-res use
+const QUALITY: usize = 1009;
+
+/// subtraction mod MM
+fn mod_diff(x: u32, y: u32) -> u32 {
+    x.wrapping_sub(y) & (MM - 1)
+}
+
+/// Put n new random numbers in aa and advance the state ran_x.
+fn ran_array(aa: &mut [u32], n: usize, ran_x: &mut [u32]) {
+    let mut j = 0;
+    while j < KK {
+        aa[j] = ran_x[j];
+        j += 1;
+    }
+    while j < n {
+        aa[j] = mod_diff(aa[j - KK], aa[j - LL]);
+        j += 1;
+    }
+    let mut i = 0;
+    while i < LL {
+        ran_x[i] = mod_diff(aa[j - KK], aa[j - LL]);
+        i += 1;
+        j += 1;
+    }
+    while i < KK {
+        ran_x[i] = mod_diff(aa[j - KK], ran_x[i - LL]);
+        i += 1;
+        j += 1;
+    }
+}
+
+/// R uses the state itself, not the buffer, as the next 100 numbers.
+fn ran_arr_cycle(ran_x: &mut [u32]) {
+    let mut ran_arr_buf = [0; QUALITY];
+    ran_array(&mut ran_arr_buf, QUALITY, ran_x);
+}
+
+/// Initialize the state ran_x from the seed, 2002 version.
+fn ran_start(seed: u32, ran_x: &mut [u32]) {
+    // the preparation buffer
+    let mut x = [0u32; KK + KK - 1];
+    let mut ss = seed.wrapping_add(2) & (MM - 2);
+    for xj in x.iter_mut().take(KK) {
+        *xj = ss; // bootstrap the buffer
+        ss <<= 1;
+        if ss >= MM {
+            ss -= MM - 2; // cyclic shift 29 bits
+        }
+    }
+    x[1] += 1; // make x[1] (and only x[1]) odd
+    ss = seed & (MM - 1);
+    let mut t = TT - 1;
+    while t != 0 {
+        for j in (1..KK).rev() {
+            // "square"
+            x[j + j] = x[j];
+            x[j + j - 1] = 0;
+        }
+        for j in (KK..=KK + KK - 2).rev() {
+            x[j - (KK - LL)] = mod_diff(x[j - (KK - LL)], x[j]);
+            x[j - KK] = mod_diff(x[j - KK], x[j]);
+        }
+        if ss & 1 == 1 {
+            // "multiply by z"
+            for j in (1..=KK).rev() {
+                x[j] = x[j - 1];
+            }
+            x[0] = x[KK]; // shift the buffer cyclically
+            x[LL] = mod_diff(x[LL], x[KK]);
+        }
+        if ss != 0 {
+            ss >>= 1;
+        } else {
+            t -= 1;
+        }
+    }
+    ran_x[KK - LL..KK].copy_from_slice(&x[..LL]);
+    ran_x[..KK - LL].copy_from_slice(&x[LL..KK]);
+    // warm things up
+    let mut buf = [0; KK + KK - 1];
+    for _ in 0..10 {
+        ran_array(&mut buf, KK + KK - 1, ran_x);
+    }
+}
+
+/// Initialize the state ran_x from the seed, 1997 version.
+///
+/// R does this with `.TAOCP1997init()`, an R translation of the original
+/// code.
+fn ran_start_1997(seed: u32, ran_x: &mut [u32]) {
+    fn evenize(x: u32) -> u32 {
+        x & (MM - 2)
+    }
+    // the preparation buffer
+    let mut x = [0u32; KK + KK - 1];
+    let mut ss = evenize(seed.wrapping_add(2));
+    for xj in x.iter_mut().take(KK) {
+        *xj = ss; // bootstrap the buffer
+        ss <<= 1;
+        if ss >= MM {
+            ss -= MM - 2; // cyclic shift 29 bits
+        }
+    }
+    x[1] += 1; // make x[1] (and only x[1]) odd
+    ss = seed & (MM - 1);
+    let mut t = TT - 1;
+    while t != 0 {
+        for j in (1..KK).rev() {
+            // "square"
+            x[j + j] = x[j];
+        }
+        for j in (KK - LL + 1..=KK + KK - 2).rev().step_by(2) {
+            x[KK + KK - 1 - j] = evenize(x[j]);
+        }
+        for j in (KK..=KK + KK - 2).rev() {
+            if x[j] & 1 == 1 {
+                x[j - (KK - LL)] = mod_diff(x[j - (KK - LL)], x[j]);
+                x[j - KK] = mod_diff(x[j - KK], x[j]);
+            }
+        }
+        if ss & 1 == 1 {
+            // "multiply by z"
+            for j in (1..=KK).rev() {
+                x[j] = x[j - 1];
+            }
+            x[0] = x[KK]; // shift the buffer cyclically
+            if x[KK] & 1 == 1 {
+                x[LL] = mod_diff(x[LL], x[KK]);
+            }
+        }
+        if ss != 0 {
+            ss >>= 1;
+        } else {
+            t -= 1;
+        }
+    }
+    ran_x[KK - LL..KK].copy_from_slice(&x[..LL]);
+    ran_x[..KK - LL].copy_from_slice(&x[LL..KK]);
+}
@@ -0,0 +1,46 @@
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::rng::Rng;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1998 Ross Ihaka
+//  Copyright (C) 2000 The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// Random variates from the uniform distribution on `[a, b]`.
+///
+/// The variates are `a + (b - a) * u`, where `u` is drawn from
+/// `rng.unif_rand()`.
+pub fn runif<R: Rng + ?Sized>(a: f64, b: f64, rng: &mut R) -> f64 {
+    if !r_finite(a) || !r_finite(b) || b < a {
+        return ml_warn_return_nan();
+    }
+
+    if a == b {
+        a
+    } else {
+        // This is true of all builtin generators, but protect against
+        // user-supplied ones
+        let u = loop {
+            let u = rng.unif_rand();
+            if !(u <= 0. || u >= 1.) {
+                break u;
+            }
+        };
+        a + (b - a) * u
+    }
+}
@@ -0,0 +1,68 @@
+use crate::rng::Rng;
+
+/// Random variates from the standard exponential distribution.
+///
+/// ## REFERENCE
+///
+/// Ahrens, J.H. and Dieter, U. (1972).
+/// Computer methods for sampling from the exponential and
+/// normal distributions.
+/// Comm. ACM, 15, 873-882.
+pub fn exp_rand<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    // q[k-1] = sum(log(2)^k / k!)  k=1,..,n,
+    // The highest n (here 16) is determined by q[n-1] = 1.0
+    // within standard precision
+    #[allow(clippy::approx_constant)]
+    #[allow(clippy::excessive_precision)]
+    const Q: [f64; 16] = [
+        0.6931471805599453,
+        0.9333736875190459,
+        0.9888777961838675,
+        0.9984959252914960040,
+        0.9998292811061389,
+        0.9999833164100727,
+        0.9999985691438767,
+        0.9999998906925558,
+        0.9999999924734159,
+        0.9999999995283275,
+        0.9999999999728814,
+        0.9999999999985598,
+        0.9999999999999289,
+        0.9999999999999968,
+        0.9999999999999999,
+        1.0000000000000000,
+    ];
+
+    let mut a = 0.0;
+    let mut u = rng.unif_rand(); // precaution if u = 0 is ever returned
+    while u <= 0.0 || u >= 1.0 {
+        u = rng.unif_rand();
+    }
+    loop {
+        u += u;
+        if u > 1.0 {
+            break;
+        }
+        a += Q[0];
+    }
+    u -= 1.0;
+
+    if u <= Q[0] {
+        return a + u;
+    }
+
+    let mut i = 0;
+    let mut ustar = rng.unif_rand();
+    let mut umin = ustar;
+    loop {
+        ustar = rng.unif_rand();
+        if umin > ustar {
+            umin = ustar;
+        }
+        i += 1;
+        if u <= Q[i] {
+            break;
+        }
+    }
+    a + umin * Q[0]
+}
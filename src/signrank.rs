@@ -0,0 +1,272 @@
+use crate::dpq::r_d__0;
+use crate::dpq::r_d_exp;
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_dt_val;
+use crate::dpq::r_q_p01_check;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+use crate::nmath::ML_NAN;
+use crate::rmath::M_LN2;
+use crate::rng::Rng;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1999-2014  The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The largest value `n * (n + 1) / 2` of the signed rank statistic, or
+/// `None` if it does not fit in an `i32`.
+///
+/// The C code computes it in `int` arithmetic, which overflows for large
+/// `n`.
+fn signrank_max(n: f64) -> Option<i32> {
+    let n = n as i64;
+    let u = n.checked_add(1).and_then(|n1| n.checked_mul(n1))? / 2;
+    if u > i64::from(i32::MAX) {
+        None
+    } else {
+        Some(u as i32)
+    }
+}
+
+/// The counts of the signed rank statistic for sample size `n`, whose
+/// largest value is `u`.
+///
+/// `w[k]` is the number of subsets of `1..=n` summing to `k`, for `k` up to
+/// `kmax` or half of `u`, whichever is smaller.
+/// The C code keeps the full table in a static variable; here it is
+/// recomputed on every call, only as far as needed.
+fn w_init(n: i32, u: i32, kmax: i32) -> Vec<f64> {
+    let c = (u / 2).min(kmax.max(0)) as usize;
+
+    let mut w = vec![0.; c + 1];
+    if n == 1 {
+        // csignrank() does not use the table
+        return w;
+    }
+    w[0] = 1.;
+    if c >= 1 {
+        w[1] = 1.;
+    }
+    for j in 2..(n as usize + 1) {
+        let end = (j * (j + 1) / 2).min(c);
+        for i in (j..=end).rev() {
+            w[i] += w[i - j];
+        }
+    }
+    w
+}
+
+fn csignrank(k: i32, n: i32, u: i32, w: &[f64]) -> f64 {
+    let c = u / 2;
+
+    if k < 0 || k > u {
+        return 0.;
+    }
+    let k = if k > c { u - k } else { k };
+
+    if n == 1 {
+        return 1.;
+    }
+    w[k as usize]
+}
+
+/// The density of the Wilcoxon signed rank distribution.
+///
+/// NaN is returned with an `MlWarningKind::Range` warning when
+/// `n * (n + 1) / 2` does not fit in an `i32`.
+pub fn dsignrank(x: f64, n: f64, give_log: bool) -> f64 {
+    // NaNs propagated correctly
+    if x.is_nan() || n.is_nan() {
+        return x + n;
+    }
+    let n = r_forceint(n);
+    if n <= 0. {
+        return ml_warn_return_nan();
+    }
+
+    if (x - r_forceint(x)).abs() > 1e-7 {
+        return r_d__0(give_log);
+    }
+    let x = r_forceint(x);
+    if x < 0. || x > (n * (n + 1.) / 2.) {
+        return r_d__0(give_log);
+    }
+
+    let u = match signrank_max(n) {
+        Some(u) => u,
+        None => {
+            ml_warning(MlWarningKind::Range, "dsignrank");
+            return ML_NAN;
+        }
+    };
+    let nn = n as i32;
+    let xx = x as i32;
+    let w = w_init(nn, u, xx.min(u - xx));
+    r_d_exp(csignrank(xx, nn, u, &w).ln() - n * M_LN2, give_log)
+}
+
+/// The distribution function of the Wilcoxon signed rank distribution.
+///
+/// NaN is returned with an `MlWarningKind::Range` warning when
+/// `n * (n + 1) / 2` does not fit in an `i32`.
+pub fn psignrank(x: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || n.is_nan() {
+        return x + n;
+    }
+    if !r_finite(n) {
+        return ml_warn_return_nan();
+    }
+    let n = r_forceint(n);
+    if n <= 0. {
+        return ml_warn_return_nan();
+    }
+
+    let mut x = r_forceint(x + 1e-7);
+    if x < 0.0 {
+        return r_dt_0(lower_tail, log_p);
+    }
+    if x >= n * (n + 1.) / 2. {
+        return r_dt_1(lower_tail, log_p);
+    }
+
+    let u = match signrank_max(n) {
+        Some(u) => u,
+        None => {
+            ml_warning(MlWarningKind::Range, "psignrank");
+            return ML_NAN;
+        }
+    };
+    let nn = n as i32;
+    let f = (-n * M_LN2).exp();
+    let mut p = 0.;
+    let mut lower_tail = lower_tail;
+    if x <= (n * (n + 1.) / 4.) {
+        let w = w_init(nn, u, x as i32);
+        let mut i = 0;
+        while i as f64 <= x {
+            p += csignrank(i, nn, u, &w) * f;
+            i += 1;
+        }
+    } else {
+        x = n * (n + 1.) / 2. - x;
+        let w = w_init(nn, u, x as i32 - 1);
+        let mut i = 0;
+        while (i as f64) < x {
+            p += csignrank(i, nn, u, &w) * f;
+            i += 1;
+        }
+        lower_tail = !lower_tail; // p = 1 - p;
+    }
+
+    r_dt_val(p, lower_tail, log_p)
+}
+
+/// The quantile function of the Wilcoxon signed rank distribution.
+///
+/// NaN is returned with an `MlWarningKind::Range` warning when
+/// `n * (n + 1) / 2` does not fit in an `i32`.
+pub fn qsignrank(x: f64, n: f64, lower_tail: bool, log_p: bool) -> f64 {
+    if x.is_nan() || n.is_nan() {
+        return x + n;
+    }
+    if !r_finite(x) || !r_finite(n) {
+        return ml_warn_return_nan();
+    }
+    if let Some(x) = r_q_p01_check(x, log_p) {
+        return x;
+    }
+
+    let n = r_forceint(n);
+    if n <= 0. {
+        return ml_warn_return_nan();
+    }
+
+    if x == r_dt_0(lower_tail, log_p) {
+        return 0.;
+    }
+    if x == r_dt_1(lower_tail, log_p) {
+        return n * (n + 1.) / 2.;
+    }
+
+    let mut x = x;
+    if log_p || !lower_tail {
+        x = r_dt_qiv(x, lower_tail, log_p); // lower_tail,non-log "p"
+    }
+
+    let u = match signrank_max(n) {
+        Some(u) => u,
+        None => {
+            ml_warning(MlWarningKind::Range, "qsignrank");
+            return ML_NAN;
+        }
+    };
+    let nn = n as i32;
+    let w = w_init(nn, u, i32::MAX);
+    let f = (-n * M_LN2).exp();
+    let mut p = 0.;
+    let mut q = 0;
+    if x <= 0.5 {
+        x -= 10. * DBL_EPSILON;
+        loop {
+            p += csignrank(q, nn, u, &w) * f;
+            if p >= x {
+                break;
+            }
+            q += 1;
+        }
+    } else {
+        x = 1. - x + 10. * DBL_EPSILON;
+        loop {
+            p += csignrank(q, nn, u, &w) * f;
+            if p > x {
+                q = (n * (n + 1.) / 2. - q as f64) as i32;
+                break;
+            }
+            q += 1;
+        }
+    }
+
+    q as f64
+}
+
+/// Random variates from the Wilcoxon signed rank distribution.
+pub fn rsignrank<R: Rng + ?Sized>(n: f64, rng: &mut R) -> f64 {
+    // NaNs propagated correctly
+    if n.is_nan() {
+        return n;
+    }
+    let n = r_forceint(n);
+    if n < 0. {
+        return ml_warn_return_nan();
+    }
+
+    if n == 0. {
+        return 0.;
+    }
+    let mut r = 0.0;
+    let k = n as i32;
+    for i in 1..=k {
+        r += i as f64 * (rng.unif_rand() + 0.5).floor();
+    }
+    r
+}
@@ -0,0 +1,335 @@
+use crate::fmax2::fmax2;
+use crate::fmin2::fmin2;
+use crate::qnorm::qnorm5;
+use crate::rng::Rng;
+
+/// 2^27
+const BIG: f64 = 134217728.0;
+
+// Constants for Kinderman-Ramage.
+const C1: f64 = 0.398942280401433;
+const C2: f64 = 0.180025191068563;
+const A: f64 = 2.216035867166471;
+
+/// The normal random number generators of R, as chosen by
+/// `RNGkind(normal.kind = )`.
+///
+/// R's "user-supplied" kind is left out: implement `Rng::norm_rand` instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NormKind {
+    /// "Buggy Kinderman-Ramage", the Kinderman-Ramage generator as used in
+    /// R before 1.7.0, retained for reproducibility.
+    BuggyKindermanRamage,
+    /// "Ahrens-Dieter", Ahrens and Dieter (1973).
+    AhrensDieter,
+    /// "Box-Muller", which generates pairs of normals and keeps the second
+    /// one for the next call.
+    BoxMuller,
+    /// "Inversion", `qnorm5` of a uniform refined by 27 more bits.
+    /// This is R's default.
+    Inversion,
+    /// "Kinderman-Ramage", Kinderman and Ramage (1976), as corrected by
+    /// Josef Leydold.
+    KindermanRamage,
+}
+
+impl Default for NormKind {
+    fn default() -> Self {
+        NormKind::Inversion
+    }
+}
+
+impl NormKind {
+    /// The name of this kind in R, e.g., "Inversion".
+    pub fn name(self) -> &'static str {
+        match self {
+            NormKind::BuggyKindermanRamage => "Buggy Kinderman-Ramage",
+            NormKind::AhrensDieter => "Ahrens-Dieter",
+            NormKind::BoxMuller => "Box-Muller",
+            NormKind::Inversion => "Inversion",
+            NormKind::KindermanRamage => "Kinderman-Ramage",
+        }
+    }
+}
+
+/// Random variates from the standard normal distribution of the given kind.
+///
+/// `bm_norm_keep` holds the second normal of a Box-Muller pair, and is `0.0`
+/// when there is none.
+pub fn norm_rand_kind<R: Rng + ?Sized>(kind: NormKind, bm_norm_keep: &mut f64, rng: &mut R) -> f64 {
+    match kind {
+        NormKind::BuggyKindermanRamage => kinderman_ramage(true, rng),
+        NormKind::AhrensDieter => norm_rand_ahrens_dieter(rng),
+        NormKind::BoxMuller => norm_rand_box_muller(bm_norm_keep, rng),
+        NormKind::Inversion => norm_rand_inversion(rng),
+        NormKind::KindermanRamage => kinderman_ramage(false, rng),
+    }
+}
+
+/// Random variates from the standard normal distribution via inversion.
+///
+/// This is R's default `normal.kind`.
+/// `unif_rand()` alone is not of high enough precision, so two uniforms are
+/// combined into one with 27 more bits.
+pub fn norm_rand_inversion<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let mut u1 = rng.unif_rand();
+    u1 = (BIG * u1).trunc() + rng.unif_rand();
+    qnorm5(u1 / BIG, 0.0, 1.0, true, false)
+}
+
+/// Random variates from the standard normal distribution via Box-Muller.
+///
+/// Each pair of uniforms gives two normals; the second one is returned by
+/// the next call via `bm_norm_keep`.
+fn norm_rand_box_muller<R: Rng + ?Sized>(bm_norm_keep: &mut f64, rng: &mut R) -> f64 {
+    if *bm_norm_keep != 0.0 {
+        // An exact test is intentional
+        let s = *bm_norm_keep;
+        *bm_norm_keep = 0.0;
+        s
+    } else {
+        let theta = 2.0 * std::f64::consts::PI * rng.unif_rand();
+        // ensure non-zero
+        let r = (-2.0 * rng.unif_rand().ln()).sqrt() + 10.0 * f64::MIN_POSITIVE;
+        *bm_norm_keep = r * theta.sin();
+        r * theta.cos()
+    }
+}
+
+/// Random variates from the standard normal distribution via Ahrens-Dieter.
+///
+/// ## REFERENCE
+///
+/// Ahrens, J.H. and Dieter, U.
+/// Extensions of Forsythe's method for random sampling from
+/// the normal distribution.
+/// Math. Comput. 27, 927-937.
+///
+/// The definitions of the constants a[k], d[k], t[k] and
+/// h[k] are according to the abovementioned article
+fn norm_rand_ahrens_dieter<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    const A: [f64; 32] = [
+        0.0000000, 0.03917609, 0.07841241, 0.1177699, 0.1573107, 0.19709910, 0.23720210, 0.2776904,
+        0.3186394, 0.36012990, 0.40225010, 0.4450965, 0.4887764, 0.53340970, 0.57913220, 0.6260990,
+        0.6744898, 0.72451440, 0.77642180, 0.8305109, 0.8871466, 0.94678180, 1.00999000, 1.0775160,
+        1.1503490, 1.22985900, 1.31801100, 1.4177970, 1.5341210, 1.67594000, 1.86273200, 2.1538750,
+    ];
+    const D: [f64; 31] = [
+        0.0000000, 0.0000000, 0.0000000, 0.0000000, 0.0000000, 0.2636843, 0.2425085, 0.2255674,
+        0.2116342, 0.1999243, 0.1899108, 0.1812252, 0.1736014, 0.1668419, 0.1607967, 0.1553497,
+        0.1504094, 0.1459026, 0.1417700, 0.1379632, 0.1344418, 0.1311722, 0.1281260, 0.1252791,
+        0.1226109, 0.1201036, 0.1177417, 0.1155119, 0.1134023, 0.1114027, 0.1095039,
+    ];
+    const T: [f64; 31] = [
+        7.673828e-4,
+        0.002306870,
+        0.003860618,
+        0.005438454,
+        0.007050699,
+        0.008708396,
+        0.010423570,
+        0.012209530,
+        0.014081250,
+        0.016055790,
+        0.018152900,
+        0.020395730,
+        0.022811770,
+        0.025434070,
+        0.028302960,
+        0.031468220,
+        0.034992330,
+        0.038954830,
+        0.043458780,
+        0.048640350,
+        0.054683340,
+        0.061842220,
+        0.070479830,
+        0.081131950,
+        0.094624440,
+        0.112300100,
+        0.136498000,
+        0.171688600,
+        0.227624100,
+        0.330498000,
+        0.584703100,
+    ];
+    const H: [f64; 31] = [
+        0.03920617, 0.03932705, 0.03950999, 0.03975703, 0.04007093, 0.04045533, 0.04091481,
+        0.04145507, 0.04208311, 0.04280748, 0.04363863, 0.04458932, 0.04567523, 0.04691571,
+        0.04833487, 0.04996298, 0.05183859, 0.05401138, 0.05654656, 0.05953130, 0.06308489,
+        0.06737503, 0.07264544, 0.07926471, 0.08781922, 0.09930398, 0.11555990, 0.14043440,
+        0.18361420, 0.27900160, 0.70104740,
+    ];
+
+    let mut u1 = rng.unif_rand();
+    let s = if u1 > 0.5 { 1.0 } else { 0.0 };
+    u1 = u1 + u1 - s;
+    u1 *= 32.0;
+    let mut i = (u1 as usize).min(31);
+    let aa;
+    let w;
+    if i != 0 {
+        let mut u2 = u1 - i as f64;
+        aa = A[i - 1];
+        w = loop {
+            if u2 > T[i - 1] {
+                break (u2 - T[i - 1]) * H[i - 1];
+            }
+            u1 = rng.unif_rand();
+            let w = u1 * (A[i] - aa);
+            let mut tt = (w * 0.5 + aa) * w;
+            let deliver = loop {
+                if u2 > tt {
+                    break true;
+                }
+                u1 = rng.unif_rand();
+                if u2 < u1 {
+                    break false;
+                }
+                tt = u1;
+                u2 = rng.unif_rand();
+            };
+            if deliver {
+                break w;
+            }
+            u2 = rng.unif_rand();
+        };
+    } else {
+        i = 6;
+        let mut a = A[31];
+        loop {
+            u1 = u1 + u1;
+            if u1 >= 1.0 {
+                break;
+            }
+            a += D[i - 1];
+            i += 1;
+        }
+        aa = a;
+        u1 -= 1.0;
+        w = loop {
+            let w = u1 * D[i - 1];
+            let mut tt = (w * 0.5 + aa) * w;
+            let jump = loop {
+                let u2 = rng.unif_rand();
+                if u2 > tt {
+                    break true;
+                }
+                u1 = rng.unif_rand();
+                if u2 < u1 {
+                    break false;
+                }
+                tt = u1;
+            };
+            if jump {
+                break w;
+            }
+            u1 = rng.unif_rand();
+        };
+    }
+
+    let y = aa + w;
+    if s == 1.0 {
+        -y
+    } else {
+        y
+    }
+}
+
+fn g(x: f64) -> f64 {
+    C1 * (-x * x / 2.0).exp() - C2 * (A - x)
+}
+
+/// Random variates from the standard normal distribution via
+/// Kinderman-Ramage.
+///
+/// With `buggy`, this is the version used before R 1.7.0, which has
+/// problems, but is retained for reproducibility of older codes.
+/// Otherwise, it is the version corrected by Josef Leydold.
+///
+/// ## REFERENCE
+///
+/// Kinderman A. J. and Ramage J. G. (1976).
+/// Computer generation of normal random variables.
+/// JASA 71, 893-896.
+#[allow(clippy::excessive_precision)]
+fn kinderman_ramage<R: Rng + ?Sized>(buggy: bool, rng: &mut R) -> f64 {
+    let u1 = rng.unif_rand();
+    if u1 < 0.884070402298758 {
+        let u2 = rng.unif_rand();
+        let c = if buggy {
+            1.13113163544180
+        } else {
+            1.131131635444180
+        };
+        return A * (c * u1 + u2 - 1.0);
+    }
+
+    if u1 >= 0.973310954173898 {
+        // tail:
+        loop {
+            let u2 = rng.unif_rand();
+            let u3 = rng.unif_rand();
+            let tt = A * A - 2.0 * u3.ln();
+            if u2 * u2 < (A * A) / tt {
+                return if u1 < 0.986655477086949 {
+                    tt.sqrt()
+                } else {
+                    -tt.sqrt()
+                };
+            }
+        }
+    }
+
+    if u1 >= 0.958720824790463 {
+        // region3:
+        loop {
+            let u2 = rng.unif_rand();
+            let u3 = rng.unif_rand();
+            let tt = A - 0.630834801921960 * fmin2(u2, u3);
+            if fmax2(u2, u3) <= 0.755591531667601 {
+                return if u2 < u3 { tt } else { -tt };
+            }
+            if 0.034240503750111 * (u2 - u3).abs() <= g(tt) {
+                return if u2 < u3 { tt } else { -tt };
+            }
+        }
+    }
+
+    if u1 >= 0.911312780288703 {
+        // region2:
+        loop {
+            let u2 = rng.unif_rand();
+            let u3 = rng.unif_rand();
+            let tt = 0.479727404222441 + 1.105473661022070 * fmin2(u2, u3);
+            if fmax2(u2, u3) <= 0.872834976671790 {
+                return if u2 < u3 { tt } else { -tt };
+            }
+            if 0.049264496373128 * (u2 - u3).abs() <= g(tt) {
+                return if u2 < u3 { tt } else { -tt };
+            }
+        }
+    }
+
+    // ELSE	 region1:
+    loop {
+        let u2 = rng.unif_rand();
+        let u3 = rng.unif_rand();
+        let tt = 0.479727404222441 - 0.595507138015940 * fmin2(u2, u3);
+        if buggy {
+            if fmax2(u2, u3) <= 0.805577924423817 {
+                return if u2 < u3 { tt } else { -tt };
+            }
+            continue;
+        }
+        if tt < 0. {
+            continue;
+        }
+        if fmax2(u2, u3) <= 0.805577924423817 {
+            return if u2 < u3 { tt } else { -tt };
+        }
+        if 0.053377549506886 * (u2 - u3).abs() <= g(tt) {
+            return if u2 < u3 { tt } else { -tt };
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::rng::Rng;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 2000, 2003  The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The ways of R to sample a random index, as chosen by
+/// `RNGkind(sample.kind = )`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleKind {
+    /// "Rounding", `floor(dn * unif_rand())`, which is non-uniform for
+    /// large `dn`.
+    /// This was R's default before R 3.6.0.
+    Rounding,
+    /// "Rejection", see `unif_index_rejection`.
+    /// This is R's default.
+    Rejection,
+}
+
+impl Default for SampleKind {
+    fn default() -> Self {
+        SampleKind::Rejection
+    }
+}
+
+impl SampleKind {
+    /// The name of this kind in R, e.g., "Rejection".
+    pub fn name(self) -> &'static str {
+        match self {
+            SampleKind::Rounding => "Rounding",
+            SampleKind::Rejection => "Rejection",
+        }
+    }
+}
+
+/// Generate a random non-negative integer < 2 ^ bits in 16 bit chunks.
+///
+/// As in C, the high chunks of `v` wrap around for more than 48 bits; only
+/// the low `bits` bits are kept.
+fn rbits<R: Rng + ?Sized>(bits: i32, rng: &mut R) -> f64 {
+    let mut v: u64 = 0;
+    let mut n = 0;
+    while n <= bits {
+        let v1 = (rng.unif_rand() * 65536.).floor() as u64;
+        v = v.wrapping_mul(65536).wrapping_add(v1);
+        n += 16;
+    }
+    // mask out the bits in the result that are not needed
+    let mask = 1u64.checked_shl(bits as u32).map_or(u64::MAX, |b| b - 1);
+    (v & mask) as f64
+}
+
+/// A random integer in `0..dn`, as `f64`, via rejection sampling.
+///
+/// This is R's default `sample.kind`, "Rejection".
+/// It draws from the integers below the next larger power of two and
+/// rejects values that are not below `dn`.
+pub fn unif_index_rejection<R: Rng + ?Sized>(dn: f64, rng: &mut R) -> f64 {
+    if dn < 1. {
+        return 0.0;
+    }
+    let bits = dn.log2().ceil() as i32;
+    loop {
+        let dv = rbits(bits, rng);
+        if !(dn <= dv) {
+            return dv;
+        }
+    }
+}
+
+/// A random integer in `0..dn`, as `f64`, of the given kind.
+pub fn unif_index_kind<R: Rng + ?Sized>(kind: SampleKind, dn: f64, rng: &mut R) -> f64 {
+    match kind {
+        SampleKind::Rounding => (dn * rng.unif_rand()).floor(),
+        SampleKind::Rejection => unif_index_rejection(dn, rng),
+    }
+}
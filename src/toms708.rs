@@ -2,19 +2,29 @@
 #![allow(clippy::manual_range_contains)]
 
 use crate::debug::debug_print;
+use crate::debug::DEBUG_BRATIO;
 use crate::dpq::r_d__0;
 use crate::dpq::r_d__1;
+use crate::fmax2::fmax2;
 use crate::i1mach::i1mach;
 use libm::cos;
-use libm::exp;
 use libm::expm1;
 use libm::fabs;
-use libm::log;
 use libm::log1p;
-use libm::pow;
 use libm::sin;
 use libm::sqrt;
 
+// `exp`, `log` and `pow` go through the platform's math library, as in the C
+// code: the `libm` versions can be off in the last bit, which cancellation in
+// `pnbeta` turns into errors in the leading digits.
+fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+fn log(x: f64) -> f64 {
+    x.ln()
+}
+
 const ML_NEGINF: f64 = f64::NEG_INFINITY;
 const M_LN_SQRT_2PI: f64 = 0.918_938_533_204_672_8;
 #[allow(clippy::approx_constant)]
@@ -23,7 +33,7 @@ const M_LN2: f64 = 0.693_147_180_559_945_3;
 const M_LOG10_2: f64 = 0.301_029_995_663_981_2;
 const M_SQRT_PI: f64 = 1.772_453_850_905_516;
 
-const DBL_MIN: f64 = f64::MIN;
+const DBL_MIN: f64 = f64::MIN_POSITIVE;
 const DBL_MAX: f64 = f64::MAX;
 const DBL_EPSILON: f64 = f64::EPSILON;
 const INT_MAX: i32 = i32::MAX;
@@ -76,16 +86,6 @@ fn r_d_exp(log_p: bool, x: f64) -> f64 {
     }
 }
 
-fn fmax2(x: f64, y: f64) -> f64 {
-    if x.is_nan() || y.is_nan() {
-        x + y
-    } else if x < y {
-        y
-    } else {
-        x
-    }
-}
-
 fn logspace_add(logx: f64, logy: f64) -> f64 {
     fmax2(logx, logy) + log1p(exp(-fabs(logx - logy)))
 }
@@ -163,7 +163,7 @@ fn bpser(a: f64, b: f64, x: f64, eps: f64, log_p: bool) -> f64 {
                 if log_p {
                     ans = a * log(x);
                 } else {
-                    ans = pow(x, a);
+                    ans = x.powf(a);
                     if ans == 0.0 {
                         /* once underflow, always underflow .. */
                         return ans;
@@ -432,9 +432,13 @@ fn l131(
     do_swap: bool,
     log_p: bool,
 ) {
-    debug_print(&format!(" L131: bgrat(*, w1={}) ", w1));
+    if DEBUG_BRATIO {
+        debug_print(&format!(" L131: bgrat(*, w1={}) ", w1));
+    }
     bgrat(b0, a0, y0, x0, w1, 15.0 * eps, &mut ierr1, false);
-    debug_print(&format!(" ==> new w1={}", *w1));
+    if DEBUG_BRATIO {
+        debug_print(&format!(" ==> new w1={}", *w1));
+    }
     //   if (ierr1) {
     //     REprintf(" ERROR(code=%d)\n", ierr1);
     //   } else {
@@ -472,7 +476,7 @@ fn l131(
     if ierr1 != 0 {
         *ierr = 10 + ierr1;
     }
-    if *w1 < 0.0 {
+    if DEBUG_BRATIO && *w1 < 0.0 {
         debug_print(&format!(
             "bratio(a={}, b={}, x={}): bgrat() -> w1 = {}",
             a, b, x, *w1
@@ -627,8 +631,6 @@ pub fn bratio(
     *w = r_d__0(log_p);
     *w1 = r_d__0(log_p);
 
-    println!("w1: {}", w1);
-
     // safeguard, preventing infinite loops further down
     if x.is_nan() || y.is_nan() || a.is_nan() || b.is_nan() {
         *ierr = 9;
@@ -660,10 +662,12 @@ pub fn bratio(
         return;
     }
 
-    debug_print(&format!(
-        "bratio(a={}, b={}, x={}, y={}, .., log_p={}): ",
-        a, b, x, y, log_p
-    ));
+    if DEBUG_BRATIO {
+        debug_print(&format!(
+            "bratio(a={}, b={}, x={}, y={}, .., log_p={}): ",
+            a, b, x, y, log_p
+        ));
+    }
     *ierr = 0;
     if x == 0.0 {
         if a == 0.0 {
@@ -765,7 +769,9 @@ pub fn bratio(
         let mut did_bup = false;
         if max(a0, b0) > 1.0 {
             /* L20:  min(a,b) <= 1 < max(a,b)  */
-            debug_print("L20:  min(a,b) <= 1 < max(a,b); ");
+            if DEBUG_BRATIO {
+                debug_print("L20:  min(a,b) <= 1 < max(a,b); ");
+            }
             if b0 <= 1.0 {
                 return l_w_bpser(a0, b0, x0, w, w1, eps, do_swap, log_p);
             }
@@ -775,7 +781,7 @@ pub fn bratio(
                 return l_w1_bpser(a0, b0, y0, w, w1, eps, do_swap, log_p);
             }
 
-            if x0 < 0.1 && pow(x0 * b0, a0) <= 0.7 {
+            if x0 < 0.1 && (x0 * b0).powf(a0) <= 0.7 {
                 return l_w_bpser(a0, b0, x0, w, w1, eps, do_swap, log_p);
             }
 
@@ -791,7 +797,7 @@ pub fn bratio(
             if a0 >= min(0.2, b0) {
                 return l_w_bpser(a0, b0, x0, w, w1, eps, do_swap, log_p);
             }
-            if pow(x0, a0) <= 0.9 {
+            if x0.powf(a0) <= 0.9 {
                 return l_w_bpser(a0, b0, x0, w, w1, eps, do_swap, log_p);
             }
             if x0 >= 0.3 {
@@ -803,7 +809,6 @@ pub fn bratio(
         did_bup = true;
         // R_ifDEBUG_printf("  ... n=20 and *w1 := bup(*) = %.15g; ", *w1);
         b0 += n as f64;
-        println!("here w1: {}", w1);
         l131(
             a, b, x, n, a0, b0, x0, y0, w, w1, eps, ierr, ierr1, did_bup, do_swap, log_p,
         )
@@ -1273,7 +1278,9 @@ fn brcmp1(mu: i32, a: f64, b: f64, x: f64, y: f64, give_log: bool) -> f64 {
         if b0 >= 8.0 {
             /* L80:                  ALGORITHM FOR b0 >= 8 */
             u = gamln1(a0) + algdiv(a0, b0);
-            debug_print(&format!(" brcmp1(mu, a, b, *): a0 < 1, b0 >= 8; z={}", z));
+            if DEBUG_BRATIO {
+                debug_print(&format!(" brcmp1(mu, a, b, *): a0 < 1, b0 >= 8; z={}", z));
+            }
             return if give_log {
                 log(a0) + esum(mu, z - u, true)
             } else {
@@ -1420,10 +1427,12 @@ pub fn bgrat(a: f64, b: f64, x: f64, y: f64, w: &mut f64, eps: f64, ierr: &mut i
         // should not happen, but does, e.g.,
         // for  pbeta(1e-320, 1e-5, 0.5)  i.e., _subnormal_ x,
         // Warning ... bgrat(a=20.5, b=1e-05, x=1, y=9.99989e-321): ..
-        debug_print(&format!(
-            "bgrat(a={}, b={}, x={}, y={}): z={}, b*z == 0 underflow, hence inaccurate pbeta()",
-            a, b, x, y, z
-        ));
+        if DEBUG_BRATIO {
+            debug_print(&format!(
+                "bgrat(a={}, b={}, x={}, y={}): z={}, b*z == 0 underflow, hence inaccurate pbeta()",
+                a, b, x, y, z
+            ));
+        }
         /* L_Error:    THE EXPANSION CANNOT BE COMPUTED */
         *ierr = 1;
         return;
@@ -1465,10 +1474,12 @@ pub fn bgrat(a: f64, b: f64, x: f64, y: f64, w: &mut f64, eps: f64, ierr: &mut i
      if log_w { if *w == ML_NEGINF { 0.0 } else { exp(*w - log_u) }
      } else if *w == 0.0 { 0.0 } else { exp(log(*w) - log_u) };
 
-    debug_print(&format!(
-        "bgrat(a={}, b={}, x={}, *) -> u={}, l='w/u'={}, ",
-        a, b, x, u, l
-    ));
+    if DEBUG_BRATIO {
+        debug_print(&format!(
+            "bgrat(a={}, b={}, x={}, *) -> u={}, l='w/u'={}, ",
+            a, b, x, u, l
+        ));
+    }
     let q_r = grat_r(b, z, log_r, eps); // = q/r of former grat1(b,z, r, &p, &q)
     let v = 0.25 / (nu * nu);
     let t2 = lnx * 0.25 * lnx;
@@ -1551,7 +1562,7 @@ fn grat_r(a: f64, x: f64, log_r: f64, eps: f64) -> f64 {
             let p: f64 = erf_(sqrt(x));
             // R_ifDEBUG_printf(" grat_r(a=%g, x=%g ..)): a=1/2 --> p=erf__(.)= %g\n", a,
             //                 x, p);
-            return (0.5 - p + 0.5) * exp(-log_r);
+            (0.5 - p + 0.5) * exp(-log_r)
         } else {
             // 2013-02-27: improvement for "large" x: direct computation of
             // q/r:
@@ -1560,7 +1571,7 @@ fn grat_r(a: f64, x: f64, log_r: f64, eps: f64) -> f64 {
             // R_ifDEBUG_printf(
             //    " grat_r(a=%g, x=%g ..)): a=1/2 --> q_r=erfc1(..)/r= %g\n", a, x,
             //    q_r);
-            return q_r;
+            q_r
         }
     } else if x < 1.1 {
         /* L10:  Taylor series for  P(a,x)/x^a */
@@ -1595,15 +1606,15 @@ fn grat_r(a: f64, x: f64, log_r: f64, eps: f64) -> f64 {
             if q <= 0.0 {
                 // R_ifDEBUG_printf(" => q_r= 0.\n");
                 /* L110:*/
-                return 0.0;
+                0.0
             } else {
                 // R_ifDEBUG_printf(" => q_r=%.15g\n", q * exp(-log_r));
-                return q * exp(-log_r);
+                q * exp(-log_r)
             }
         } else {
             let p = exp(z) * g * (0.5 - j + 0.5);
             // R_ifDEBUG_printf(" => q_r=%.15g\n", (0.5 - p + 0.5) * exp(-log_r));
-            return /* q/r = */ (0.5 - p + 0.5) * exp(-log_r);
+            (0.5 - p + 0.5) * exp(-log_r)
         }
     } else {
         /* L50: ----  (x >= 1.1)  ---- Continued Fraction Expansion */
@@ -1630,14 +1641,16 @@ fn grat_r(a: f64, x: f64, log_r: f64, eps: f64) -> f64 {
             }
         }
 
-        debug_print(&format!(
-            " grat_r(a={}, x={}, log_r={}): Cont.frac. {} terms => q_r={}",
-            a,
-            x,
-            log_r,
-            c - 1.,
-            an0
-        ));
+        if DEBUG_BRATIO {
+            debug_print(&format!(
+                " grat_r(a={}, x={}, log_r={}): Cont.frac. {} terms => q_r={}",
+                a,
+                x,
+                log_r,
+                c - 1.,
+                an0
+            ));
+        }
         /* q/r = (r * an0)/r = */
         an0
     }
@@ -1730,7 +1743,7 @@ fn basym(a: f64, b: f64, lambda: f64, eps: f64, log_p: bool) -> f64 {
             c[i - 1] = b0[i - 1] / (i as f64 + 1.0);
 
             let mut dsum = 0.0;
-            for j in 1..=i {
+            for j in 1..i {
                 dsum += d[i - j - 1] * c[j - 1];
             }
             d[i - 1] = -(dsum + c[i - 1]);
@@ -2138,7 +2151,9 @@ fn gam1(a: f64) -> f64 {
             + R[0];
         bot = (s2 * t + s1) * t + 1.;
         w = top / bot;
-        debug_print(&format!("  gam1(a = {}): t < 0: w={}\n", a, w));
+        if DEBUG_BRATIO {
+            debug_print(&format!("  gam1(a = {}): t < 0: w={}\n", a, w));
+        }
         if d > 0.0 {
             t * w / a
         } else {
@@ -2146,7 +2161,7 @@ fn gam1(a: f64) -> f64 {
         }
     } else if t == 0.0 {
         // L10: a in {0, 1}
-        return 0.0;
+        0.0
     } else {
         /* t > 0;  L20: */
         const P: [f64; 7] = [
@@ -2169,10 +2184,12 @@ fn gam1(a: f64) -> f64 {
         top = (((((P[6] * t + P[5]) * t + P[4]) * t + P[3]) * t + P[2]) * t + P[1]) * t + P[0];
         bot = (((Q[4] * t + Q[3]) * t + Q[2]) * t + Q[1]) * t + 1.;
         w = top / bot;
-        debug_print(&format!(
-            "  gam1(a = {}): t > 0: (is a < 1.5 ?)  w={}\n",
-            a, w
-        ));
+        if DEBUG_BRATIO {
+            debug_print(&format!(
+                "  gam1(a = {}): t > 0: (is a < 1.5 ?)  w={}\n",
+                a, w
+            ));
+        }
         if d > 0.0 {
             /* L21: */
             t / a * (w - 0.5 - 0.5)
@@ -2686,7 +2703,7 @@ fn gamln(a: f64) -> f64 {
     if a <= 0.8 {
         gamln1(a) - log(a) /* ln(G(a+1)) - ln(a) == ln(G(a+1)/a) = ln(G(a)) */
     } else if a <= 2.25 {
-        return gamln1(a - 0.5 - 0.5);
+        gamln1(a - 0.5 - 0.5)
     } else if a < 10. {
         let n = (a - 1.25) as i32;
         let mut t = a;
@@ -2695,11 +2712,11 @@ fn gamln(a: f64) -> f64 {
             t += -1.0;
             w *= t;
         }
-        return gamln1(t - 1.0) + log(w);
+        gamln1(t - 1.0) + log(w)
     } else {
         /* a >= 10 */
         let t = 1.0 / (a * a);
         let w = (((((c5 * t + c4) * t + c3) * t + c2) * t + c1) * t + c0) / a;
-        return d + w + (a - 0.5) * (log(a) - 1.);
+        d + w + (a - 0.5) * (log(a) - 1.)
     }
 }
@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+use std::fmt;
+
+/// The kinds of warnings raised by the functions in this crate.
+///
+/// These are R's `ME_*` codes from `nmath.h`, plus `NonInteger` for the
+/// messages that R prints with `MATHLIB_WARNING` about non-integer
+/// arguments.
+/// Like R, "argument out of domain" is not reported, since those
+/// functions return NaN instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MlWarningKind {
+    /// `ME_RANGE`: value out of range.
+    Range,
+    /// `ME_NOCONV`: process did not converge.
+    NoConv,
+    /// `ME_PRECISION`: does not have "full" precision.
+    Precision,
+    /// `ME_UNDERFLOW`: an underflow occurred.
+    Underflow,
+    /// An argument that should be an integer is not, e.g., "non-integer
+    /// x = 2.5" in `dpois`.
+    NonInteger,
+}
+
+/// A warning raised by a function in this crate, like R's `ML_WARNING`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MlWarning {
+    /// What went wrong, e.g., `MlWarningKind::Precision` when an iteration
+    /// stopped before reaching full accuracy.
+    pub kind: MlWarningKind,
+    /// The name of the function that raised the warning, e.g., "ptukey".
+    pub function: &'static str,
+}
+
+impl fmt::Display for MlWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self.kind {
+            MlWarningKind::Range => "value out of range",
+            MlWarningKind::NoConv => "convergence failed",
+            MlWarningKind::Precision => "full precision may not have been achieved",
+            MlWarningKind::Underflow => "underflow occurred",
+            MlWarningKind::NonInteger => "non-integer argument",
+        };
+        write!(f, "{} in '{}'", msg, self.function)
+    }
+}
+
+/// The maximum number of warnings kept, like R's `nwarnings` default.
+const MAX_WARNINGS: usize = 50;
+
+thread_local! {
+    static WARNINGS: RefCell<Vec<MlWarning>> = RefCell::new(Vec::new());
+}
+
+/// Record a warning for the current thread, instead of printing it as the
+/// C code does.
+///
+/// Only the first `MAX_WARNINGS` warnings are kept until they are taken.
+pub fn ml_warning(kind: MlWarningKind, function: &'static str) {
+    WARNINGS.with(|w| {
+        let mut w = w.borrow_mut();
+        if w.len() < MAX_WARNINGS {
+            w.push(MlWarning { kind, function });
+        }
+    });
+}
+
+/// Returns and clears the warnings raised on the current thread.
+///
+/// For example, `ptukey` raises `MlWarningKind::Precision` when its
+/// integral did not converge.
+/// At most the first 50 warnings since the last call are returned.
+pub fn take_warnings() -> Vec<MlWarning> {
+    WARNINGS.with(|w| std::mem::take(&mut *w.borrow_mut()))
+}
@@ -0,0 +1,406 @@
+use crate::choose::choose;
+use crate::choose::lchoose;
+use crate::dpq::r_d__0;
+use crate::dpq::r_dt_0;
+use crate::dpq::r_dt_1;
+use crate::dpq::r_dt_qiv;
+use crate::dpq::r_dt_val;
+use crate::dpq::r_q_p01_check;
+use crate::libc::DBL_EPSILON;
+use crate::nmath::ml_warn_return_nan;
+use crate::nmath::r_finite;
+use crate::nmath::r_forceint;
+use crate::nmath::ML_NAN;
+use crate::rng::Rng;
+use crate::warning::ml_warning;
+use crate::warning::MlWarningKind;
+use std::error::Error;
+use std::fmt;
+
+//
+//  Mathlib : A C Library of Special Functions
+//  Copyright (C) 1999-2014  The R Core Team
+//
+//  This program is free software; you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation; either version 2 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program; if not, a copy is available at
+//  <https://www.R-project.org/Licenses/>
+
+/// The default limit on the number of counts held by a `WilcoxCounts`.
+///
+/// At 8 bytes per count, this is 128 MiB.
+/// It is enough for all sample sizes up to `m = n = 100`.
+pub const WILCOX_DEFAULT_LIMIT: usize = 1 << 24;
+
+/// The error returned when the counts for the Wilcoxon distribution would
+/// not fit in the memory limit of a `WilcoxCounts`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WilcoxError {
+    /// The smaller of the two sample sizes held by the cache.
+    pub m: usize,
+    /// The larger of the two sample sizes held by the cache.
+    pub n: usize,
+    /// The number of counts needed for these sample sizes.
+    pub required: usize,
+    /// The limit of the cache.
+    pub limit: usize,
+}
+
+impl fmt::Display for WilcoxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "wilcox counts for sample sizes {} and {} need up to {} values, \
+             exceeding the limit of {}",
+            self.m, self.n, self.required, self.limit
+        )
+    }
+}
+
+impl Error for WilcoxError {}
+
+/// A cache of the number of choices with a given Wilcoxon rank sum
+/// statistic.
+///
+/// The C code stores these counts in a global, lazily allocated array.
+/// Here the cache is owned by the caller and passed to `dwilcox`, `pwilcox`
+/// and `qwilcox` as their last argument, so it can be kept per thread or
+/// shared behind a lock.
+/// Counts computed for one pair of sample sizes are reused for all smaller
+/// ones.
+///
+/// The memory held by the cache is bounded by its limit, counted in `f64`
+/// values.
+/// Sample sizes that would need more than that return a `WilcoxError`
+/// instead of allocating.
+#[derive(Clone, Debug)]
+pub struct WilcoxCounts {
+    /// `w[i][j][k]` is `cwilcox(k, i, j)` for `i <= j`, or -1 if not yet
+    /// computed; `w[i][j]` is empty until first used.
+    w: Vec<Vec<Vec<f64>>>,
+    allocated_m: usize,
+    allocated_n: usize,
+    limit: usize,
+}
+
+impl Default for WilcoxCounts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WilcoxCounts {
+    /// Creates an empty cache with the default limit,
+    /// `WILCOX_DEFAULT_LIMIT`.
+    pub fn new() -> Self {
+        Self::with_limit(WILCOX_DEFAULT_LIMIT)
+    }
+
+    /// Creates an empty cache holding at most `limit` counts.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            w: Vec::new(),
+            allocated_m: 0,
+            allocated_n: 0,
+            limit,
+        }
+    }
+
+    /// Returns the maximum number of counts this cache may hold.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Frees all cached counts.
+    ///
+    /// This is the counterpart of R's `wilcox_free()`.
+    pub fn clear(&mut self) {
+        self.w = Vec::new();
+        self.allocated_m = 0;
+        self.allocated_n = 0;
+    }
+
+    /// An upper bound on the number of counts stored for sample sizes up to
+    /// `m <= n`: `w[i][j]` holds `i * j / 2 + 1` values, so the total is at
+    /// most `(m * (m + 1) / 2) * (n * (n + 1) / 2) / 2 + (m + 1) * (n + 1)`.
+    fn required(m: usize, n: usize) -> Option<usize> {
+        let sm = m.checked_mul(m + 1)? / 2;
+        let sn = n.checked_mul(n + 1)? / 2;
+        (sm.checked_mul(sn)? / 2).checked_add((m + 1).checked_mul(n + 1)?)
+    }
+
+    /// Makes room for the counts of sample sizes `m` and `n`.
+    ///
+    /// Unlike the C code, which frees `w` when larger sizes are requested,
+    /// the existing counts are kept.
+    fn init_maybe(&mut self, m: i32, n: i32) -> Result<(), WilcoxError> {
+        let (m, n) = if m > n { (n, m) } else { (m, n) };
+        let m = (m as usize).max(self.allocated_m);
+        let n = (n as usize).max(self.allocated_n);
+        let required = Self::required(m, n).unwrap_or(usize::MAX);
+        if required > self.limit {
+            return Err(WilcoxError {
+                m,
+                n,
+                required,
+                limit: self.limit,
+            });
+        }
+        if self.w.len() < m + 1 {
+            self.w.resize(m + 1, Vec::new());
+        }
+        for wi in self.w.iter_mut() {
+            if wi.len() < n + 1 {
+                wi.resize(n + 1, Vec::new());
+            }
+        }
+        self.allocated_m = m;
+        self.allocated_n = n;
+        Ok(())
+    }
+
+    /// This counts the number of choices with statistic = k.
+    fn cwilcox(&mut self, k: i32, m: i32, n: i32) -> f64 {
+        let u = m * n;
+        if k < 0 || k > u {
+            return 0.;
+        }
+        let c = u / 2;
+        let k = if k > c { u - k } else { k }; // hence  k <= floor(u / 2)
+        let (i, j) = if m < n { (m, n) } else { (n, m) }; // hence  i <= j
+
+        if j == 0 {
+            // and hence i == 0
+            return (k == 0) as i32 as f64;
+        }
+
+        // We can simplify things if k is small.  Consider the Mann-Whitney
+        // definition, and sort y.  Then if the statistic is k, no more
+        // than k of the y's can be <= any x[i], and since they are sorted
+        // these can only be in the first k.  So the count is the same as
+        // if there were just k y's.
+        if j > 0 && k < j {
+            return self.cwilcox(k, i, k);
+        }
+
+        let (iu, ju, ku) = (i as usize, j as usize, k as usize);
+        if self.w[iu][ju].is_empty() {
+            self.w[iu][ju] = vec![-1.; c as usize + 1];
+        }
+        if self.w[iu][ju][ku] < 0. {
+            self.w[iu][ju][ku] = self.cwilcox(k - j, i - 1, j) + self.cwilcox(k, i, j - 1);
+        }
+        self.w[iu][ju][ku]
+    }
+}
+
+/// The density of the Wilcoxon rank sum distribution.
+///
+/// `counts` caches the counts shared by repeated calls; see `WilcoxCounts`.
+pub fn dwilcox(
+    x: f64,
+    m: f64,
+    n: f64,
+    give_log: bool,
+    counts: &mut WilcoxCounts,
+) -> Result<f64, WilcoxError> {
+    // NaNs propagated correctly
+    if x.is_nan() || m.is_nan() || n.is_nan() {
+        return Ok(x + m + n);
+    }
+    let m = r_forceint(m);
+    let n = r_forceint(n);
+    if m <= 0. || n <= 0. {
+        return Ok(ml_warn_return_nan());
+    }
+
+    if (x - r_forceint(x)).abs() > 1e-7 {
+        return Ok(r_d__0(give_log));
+    }
+    let x = r_forceint(x);
+    if x < 0. || x > m * n {
+        return Ok(r_d__0(give_log));
+    }
+
+    let (mm, nn, xx) = (m as i32, n as i32, x as i32);
+    counts.init_maybe(mm, nn)?;
+    let d = if give_log {
+        counts.cwilcox(xx, mm, nn).ln() - lchoose(m + n, n)
+    } else {
+        counts.cwilcox(xx, mm, nn) / choose(m + n, n)
+    };
+
+    Ok(d)
+}
+
+/// The distribution function of the Wilcoxon rank sum distribution.
+///
+/// `counts` caches the counts shared by repeated calls; see `WilcoxCounts`.
+pub fn pwilcox(
+    q: f64,
+    m: f64,
+    n: f64,
+    lower_tail: bool,
+    log_p: bool,
+    counts: &mut WilcoxCounts,
+) -> Result<f64, WilcoxError> {
+    if q.is_nan() || m.is_nan() || n.is_nan() {
+        return Ok(q + m + n);
+    }
+    if !r_finite(m) || !r_finite(n) {
+        return Ok(ml_warn_return_nan());
+    }
+    let m = r_forceint(m);
+    let n = r_forceint(n);
+    if m <= 0. || n <= 0. {
+        return Ok(ml_warn_return_nan());
+    }
+
+    let mut q = (q + 1e-7).floor();
+
+    if q < 0.0 {
+        return Ok(r_dt_0(lower_tail, log_p));
+    }
+    if q >= m * n {
+        return Ok(r_dt_1(lower_tail, log_p));
+    }
+
+    let (mm, nn) = (m as i32, n as i32);
+    counts.init_maybe(mm, nn)?;
+    let c = choose(m + n, n);
+    let mut p = 0.;
+    let mut lower_tail = lower_tail;
+    // Use summation of probs over the shorter range
+    if q <= (m * n / 2.) {
+        let mut i = 0;
+        while i as f64 <= q {
+            p += counts.cwilcox(i, mm, nn) / c;
+            i += 1;
+        }
+    } else {
+        q = m * n - q;
+        let mut i = 0;
+        while (i as f64) < q {
+            p += counts.cwilcox(i, mm, nn) / c;
+            i += 1;
+        }
+        lower_tail = !lower_tail; // p = 1 - p;
+    }
+
+    Ok(r_dt_val(p, lower_tail, log_p))
+}
+
+/// The quantile function of the Wilcoxon rank sum distribution.
+///
+/// `counts` caches the counts shared by repeated calls; see `WilcoxCounts`.
+pub fn qwilcox(
+    x: f64,
+    m: f64,
+    n: f64,
+    lower_tail: bool,
+    log_p: bool,
+    counts: &mut WilcoxCounts,
+) -> Result<f64, WilcoxError> {
+    if x.is_nan() || m.is_nan() || n.is_nan() {
+        return Ok(x + m + n);
+    }
+    if !r_finite(x) || !r_finite(m) || !r_finite(n) {
+        return Ok(ml_warn_return_nan());
+    }
+    if let Some(x) = r_q_p01_check(x, log_p) {
+        return Ok(x);
+    }
+
+    let m = r_forceint(m);
+    let n = r_forceint(n);
+    if m <= 0. || n <= 0. {
+        return Ok(ml_warn_return_nan());
+    }
+
+    if x == r_dt_0(lower_tail, log_p) {
+        return Ok(0.);
+    }
+    if x == r_dt_1(lower_tail, log_p) {
+        return Ok(m * n);
+    }
+
+    let mut x = x;
+    if log_p || !lower_tail {
+        x = r_dt_qiv(x, lower_tail, log_p); // lower_tail,non-log "p"
+    }
+
+    let (mm, nn) = (m as i32, n as i32);
+    counts.init_maybe(mm, nn)?;
+    let c = choose(m + n, n);
+    let mut p = 0.;
+    let mut q = 0;
+    if x <= 0.5 {
+        x -= 10. * DBL_EPSILON;
+        loop {
+            p += counts.cwilcox(q, mm, nn) / c;
+            if p >= x {
+                break;
+            }
+            q += 1;
+        }
+    } else {
+        x = 1. - x + 10. * DBL_EPSILON;
+        loop {
+            p += counts.cwilcox(q, mm, nn) / c;
+            if p > x {
+                q = (m * n - q as f64) as i32;
+                break;
+            }
+            q += 1;
+        }
+    }
+
+    Ok(q as f64)
+}
+
+/// Random variates from the Wilcoxon rank sum distribution.
+///
+/// The C code allocates `m + n` integers; NaN is returned with an
+/// `MlWarningKind::Range` warning when that exceeds
+/// `WILCOX_DEFAULT_LIMIT`.
+pub fn rwilcox<R: Rng + ?Sized>(m: f64, n: f64, rng: &mut R) -> f64 {
+    // NaNs propagated correctly
+    if m.is_nan() || n.is_nan() {
+        return m + n;
+    }
+    let m = r_forceint(m);
+    let n = r_forceint(n);
+    if m < 0. || n < 0. {
+        return ml_warn_return_nan();
+    }
+
+    if m == 0. || n == 0. {
+        return 0.;
+    }
+    if !(m + n <= WILCOX_DEFAULT_LIMIT as f64) {
+        ml_warning(MlWarningKind::Range, "rwilcox");
+        return ML_NAN;
+    }
+
+    let mut r = 0.0;
+    let mut k = (m + n) as i32;
+    let mut x: Vec<i32> = (0..k).collect();
+    let mut i = 0;
+    while (i as f64) < n {
+        let j = rng.unif_index(k as f64) as usize;
+        r += x[j] as f64;
+        k -= 1;
+        x[j] = x[k as usize];
+        i += 1;
+    }
+    r - n * (n - 1.) / 2.
+}
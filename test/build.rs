@@ -2,46 +2,136 @@ fn main() {
     // std::env::set_var("CFLAGS", "-DDEBUG_bratio");
     cc::Build::new()
         .file("nmath/bd0.c")
+        .file("nmath/bessel_i.c")
+        .file("nmath/bessel_j.c")
+        .file("nmath/bessel_k.c")
+        .file("nmath/bessel_y.c")
+        .file("nmath/beta.c")
         .file("nmath/chebyshev.c")
+        .file("nmath/choose.c")
         .file("nmath/cospi.c")
         .file("nmath/d1mach.c")
+        .file("nmath/dbeta.c")
+        .file("nmath/dbinom.c")
+        .file("nmath/dcauchy.c")
+        .file("nmath/dchisq.c")
+        .file("nmath/dexp.c")
+        .file("nmath/df.c")
         .file("nmath/dgamma.c")
+        .file("nmath/dgeom.c")
+        .file("nmath/dhyper.c")
+        .file("nmath/dlnorm.c")
+        .file("nmath/dlogis.c")
+        .file("nmath/dnbeta.c")
+        .file("nmath/dnbinom.c")
+        .file("nmath/dnchisq.c")
+        .file("nmath/dnf.c")
         .file("nmath/dnorm.c")
         .file("nmath/dnt.c")
         .file("nmath/dpois.c")
         .file("nmath/dt.c")
+        .file("nmath/dunif.c")
+        .file("nmath/dweibull.c")
         .file("nmath/fmax2.c")
         .file("nmath/fmin2.c")
+        .file("nmath/fsign.c")
         .file("nmath/gamma.c")
+        .file("nmath/gamma_cody.c")
         .file("nmath/i1mach.c")
+        .file("nmath/imax2.c")
+        .file("nmath/imin2.c")
         .file("nmath/lbeta.c")
         .file("nmath/lgamma.c")
         .file("nmath/lgammacor.c")
         .file("nmath/log1p.c")
         .file("nmath/mlutils.c")
         .file("nmath/pbeta.c")
+        .file("nmath/pbinom.c")
+        .file("nmath/pcauchy.c")
         .file("nmath/pchisq.c")
+        .file("nmath/pexp.c")
+        .file("nmath/pf.c")
         .file("nmath/pgamma.c")
+        .file("nmath/pgeom.c")
+        .file("nmath/phyper.c")
+        .file("nmath/plnorm.c")
+        .file("nmath/plogis.c")
         .file("nmath/pnbeta.c")
+        .file("nmath/pnbinom.c")
         .file("nmath/pnchisq.c")
         .file("nmath/pnf.c")
         .file("nmath/pnorm.c")
         .file("nmath/pnt.c")
+        .file("nmath/polygamma.c")
+        .file("nmath/ppois.c")
         .file("nmath/pt.c")
+        .file("nmath/ptukey.c")
+        .file("nmath/punif.c")
+        .file("nmath/pweibull.c")
+        .file("nmath/qbeta.c")
+        .file("nmath/qbinom.c")
+        .file("nmath/qcauchy.c")
         .file("nmath/qchisq.c")
+        .file("nmath/qexp.c")
+        .file("nmath/qf.c")
         .file("nmath/qgamma.c")
+        .file("nmath/qgeom.c")
+        .file("nmath/qhyper.c")
+        .file("nmath/qlnorm.c")
+        .file("nmath/qlogis.c")
         .file("nmath/qnbeta.c")
+        .file("nmath/qnbinom.c")
         .file("nmath/qnchisq.c")
         .file("nmath/qnf.c")
         .file("nmath/qnorm.c")
         .file("nmath/qnt.c")
+        .file("nmath/qpois.c")
         .file("nmath/qt.c")
+        .file("nmath/qtukey.c")
+        .file("nmath/qunif.c")
+        .file("nmath/qweibull.c")
+        .file("nmath/rbeta.c")
+        .file("nmath/rbinom.c")
+        .file("nmath/rcauchy.c")
+        .file("nmath/rchisq.c")
+        .file("nmath/rexp.c")
+        .file("nmath/rgamma.c")
+        .file("nmath/rgeom.c")
+        .file("nmath/rhyper.c")
+        .file("nmath/rlnorm.c")
+        .file("nmath/rlogis.c")
+        .file("nmath/rnbinom.c")
+        .file("nmath/rnorm.c")
+        .file("nmath/rpois.c")
+        .file("nmath/runif.c")
+        .file("nmath/rweibull.c")
+        .file("nmath/sexp.c")
+        .file("nmath/signrank.c")
+        .file("nmath/snorm.c")
+        .file("nmath/standalone/sunif.c")
         .file("nmath/stirlerr.c")
         .file("nmath/toms708.c")
+        .file("nmath/wilcox.c")
         .warnings(false)
         .define("MATHLIB_STANDALONE", "1")
+        // R is configured with a working log1p on all supported platforms.
+        .define("HAVE_WORKING_LOG1P", "1")
+        // R uses `long double` accumulators where the platform has them.
+        .define("HAVE_LONG_DOUBLE", "1")
+        .define("HAVE_LOG1PL", "1")
         .include("nmath")
         .include("nmath/R_ext")
         .compile("nmath");
+    // A second copy of snorm.c with its own normal kind, so that all kinds
+    // can be tested without changing `norm_rand` for the other tests.
+    cc::Build::new()
+        .file("nmath/snorm.c")
+        .warnings(false)
+        .define("MATHLIB_STANDALONE", "1")
+        .define("norm_rand", "norm_rand_kinds")
+        .define("N01_kind", "N01_kind_kinds")
+        .include("nmath")
+        .include("nmath/R_ext")
+        .compile("snorm_kinds");
     println!("cargo:rerun-if-changed=nmath");
 }
@@ -0,0 +1,41 @@
+/*
+ *  Mathlib : A C Library of Special Functions
+ *  Copyright (C) 1998 Ross Ihaka
+ *  Copyright (C) 2000 The R Core Team
+ *
+ *  This program is free software; you can redistribute it and/or modify
+ *  it under the terms of the GNU General Public License as published by
+ *  the Free Software Foundation; either version 2 of the License, or
+ *  (at your option) any later version.
+ *
+ *  This program is distributed in the hope that it will be useful,
+ *  but WITHOUT ANY WARRANTY; without even the implied warranty of
+ *  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ *  GNU General Public License for more details.
+ *
+ *  You should have received a copy of the GNU General Public License
+ *  along with this program; if not, a copy is available at
+ *  https://www.R-project.org/Licenses/
+ *
+ *  DESCRIPTION
+ *
+ *	The density of the exponential distribution.
+ */
+
+#include "nmath.h"
+#include "dpq.h"
+
+double dexp(double x, double scale, int give_log)
+{
+#ifdef IEEE_754
+    /* NaNs propagated correctly */
+    if (ISNAN(x) || ISNAN(scale)) return x + scale;
+#endif
+    if (scale <= 0.0) ML_WARN_return_NAN;
+
+    if (x < 0.)
+	return R_D__0;
+    return (give_log ?
+	    (-x / scale) - log(scale) :
+	    exp(-x / scale) / scale);
+}
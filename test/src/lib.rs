@@ -14,23 +14,132 @@ mod test_math {
             pub fn Rf_i1mach(i: i32) -> i32;
             pub fn Rf_lgammacor(x: f64) -> f64;
             pub fn Rf_stirlerr(n: f64) -> f64;
+            pub fn R_unif_index(dn: f64) -> f64;
+            pub fn bessel_i(x: f64, alpha: f64, expo: f64) -> f64;
+            pub fn bessel_j(x: f64, alpha: f64) -> f64;
+            pub fn bessel_k(x: f64, alpha: f64, expo: f64) -> f64;
+            pub fn bessel_y(x: f64, alpha: f64) -> f64;
+            pub fn beta(a: f64, b: f64) -> f64;
+            pub fn choose(n: f64, k: f64) -> f64;
             pub fn cospi(x: f64) -> f64;
+            pub fn dbeta(x: f64, a: f64, b: f64, give_log: i32) -> f64;
+            pub fn dbinom(x: f64, n: f64, p: f64, give_log: i32) -> f64;
+            pub fn dcauchy(x: f64, location: f64, scale: f64, give_log: i32) -> f64;
+            pub fn dexp(x: f64, scale: f64, give_log: i32) -> f64;
+            pub fn df(x: f64, m: f64, n: f64, give_log: i32) -> f64;
             pub fn dgamma(x: f64, shape: f64, scale: f64, give_log: bool) -> f64;
+            pub fn dgeom(x: f64, p: f64, give_log: i32) -> f64;
+            pub fn dhyper(x: f64, r: f64, b: f64, n: f64, give_log: i32) -> f64;
+            pub fn digamma(x: f64) -> f64;
+            pub fn dlnorm(x: f64, meanlog: f64, sdlog: f64, give_log: i32) -> f64;
+            pub fn dlogis(x: f64, location: f64, scale: f64, give_log: i32) -> f64;
+            pub fn dnbeta(x: f64, a: f64, b: f64, ncp: f64, give_log: i32) -> f64;
+            pub fn dnbinom(x: f64, size: f64, prob: f64, give_log: i32) -> f64;
+            pub fn dnbinom_mu(x: f64, size: f64, mu: f64, give_log: i32) -> f64;
+            pub fn dnchisq(x: f64, df: f64, ncp: f64, give_log: i32) -> f64;
+            pub fn dnf(x: f64, df1: f64, df2: f64, ncp: f64, give_log: i32) -> f64;
             pub fn dnorm4(x: f64, mu: f64, sigma: f64, give_log: bool) -> f64;
+            pub fn dnt(x: f64, df: f64, ncp: f64, give_log: i32) -> f64;
             pub fn dpois(x: f64, lambda: f64, give_log: bool) -> f64;
+            pub fn dpsifn(
+                x: f64,
+                n: i32,
+                kode: i32,
+                m: i32,
+                ans: *mut f64,
+                nz: *mut i32,
+                ierr: *mut i32,
+            );
+            pub fn dsignrank(x: f64, n: f64, give_log: i32) -> f64;
             pub fn dt(x: f64, n: f64, give_log: bool) -> f64;
+            pub fn dunif(x: f64, a: f64, b: f64, give_log: i32) -> f64;
+            pub fn dweibull(x: f64, shape: f64, scale: f64, give_log: i32) -> f64;
+            pub fn dwilcox(x: f64, m: f64, n: f64, give_log: i32) -> f64;
+            pub fn exp_rand() -> f64;
             pub fn gammafn(x: f64) -> f64;
             pub fn lbeta(a: f64, b: f64) -> f64;
+            pub fn lchoose(n: f64, k: f64) -> f64;
+            pub fn log1mexp(x: f64) -> f64;
+            pub fn log1pexp(x: f64) -> f64;
             pub fn log1pmx(x: f64) -> f64;
             pub fn lgammafn(x: f64) -> f64;
             pub fn lgammafn_sign(x: f64, sgn: Option<&mut i32>) -> f64;
+            pub fn norm_rand_kinds() -> f64;
+            pub fn pbinom(x: f64, n: f64, p: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pcauchy(x: f64, location: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pchisq(x: f64, df: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pentagamma(x: f64) -> f64;
+            pub fn pexp(x: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pf(x: f64, df1: f64, df2: f64, lower_tail: i32, log_p: i32) -> f64;
             pub fn pgamma(x: f64, alph: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pgeom(x: f64, p: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn phyper(x: f64, nr: f64, nb: f64, n: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn plnorm(x: f64, meanlog: f64, sdlog: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn plogis(x: f64, location: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pnbeta(x: f64, a: f64, b: f64, ncp: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pnbinom(x: f64, size: f64, prob: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pnbinom_mu(x: f64, size: f64, mu: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pnchisq(x: f64, df: f64, ncp: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pnf(x: f64, df1: f64, df2: f64, ncp: f64, lower_tail: i32, log_p: i32) -> f64;
             pub fn pnorm5(x: f64, mu: f64, sigma: f64, lower_tail: i32, log_p: i32) -> f64;
             pub fn pnt(t: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) -> f64;
+            pub fn ppois(x: f64, lambda: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn psigamma(x: f64, deriv: f64) -> f64;
+            pub fn psignrank(x: f64, n: f64, lower_tail: i32, log_p: i32) -> f64;
             pub fn pt(x: f64, n: f64, lower_tail: bool, log_p: bool) -> f64;
+            pub fn ptukey(q: f64, rr: f64, cc: f64, df: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn punif(x: f64, a: f64, b: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pweibull(x: f64, shape: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn pwilcox(q: f64, m: f64, n: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qbeta(alpha: f64, p: f64, q: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qbinom(p: f64, n: f64, pr: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qcauchy(p: f64, location: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qchisq(p: f64, df: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qexp(p: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qf(p: f64, df1: f64, df2: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qgamma(p: f64, alpha: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qgeom(p: f64, prob: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qhyper(p: f64, nr: f64, nb: f64, n: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qlnorm(p: f64, meanlog: f64, sdlog: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qlogis(p: f64, location: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qnt(p: f64, df: f64, ncp: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qpois(p: f64, lambda: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qsignrank(x: f64, n: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qt(p: f64, ndf: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qnbeta(p: f64, a: f64, b: f64, ncp: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qnbinom(p: f64, size: f64, prob: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qnbinom_mu(p: f64, size: f64, mu: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qnchisq(p: f64, df: f64, ncp: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qnf(p: f64, df1: f64, df2: f64, ncp: f64, lower_tail: i32, log_p: i32) -> f64;
             pub fn qnorm5(p: f64, mu: f64, sigma: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qtukey(p: f64, rr: f64, cc: f64, df: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qunif(p: f64, a: f64, b: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qweibull(p: f64, shape: f64, scale: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn qwilcox(x: f64, m: f64, n: f64, lower_tail: i32, log_p: i32) -> f64;
+            pub fn rbeta(aa: f64, bb: f64) -> f64;
+            pub fn rbinom(nin: f64, pp: f64) -> f64;
+            pub fn rcauchy(location: f64, scale: f64) -> f64;
+            pub fn rchisq(df: f64) -> f64;
+            pub fn rexp(scale: f64) -> f64;
+            pub fn rgamma(a: f64, scale: f64) -> f64;
+            pub fn rgeom(p: f64) -> f64;
+            pub fn rhyper(nn1in: f64, nn2in: f64, kkin: f64) -> f64;
+            pub fn rlnorm(meanlog: f64, sdlog: f64) -> f64;
+            pub fn rlogis(location: f64, scale: f64) -> f64;
+            pub fn rnbinom(size: f64, prob: f64) -> f64;
+            pub fn rnbinom_mu(size: f64, mu: f64) -> f64;
+            pub fn rnorm(mu: f64, sigma: f64) -> f64;
+            pub fn rpois(mu: f64) -> f64;
+            pub fn rsignrank(n: f64) -> f64;
+            pub fn runif(a: f64, b: f64) -> f64;
+            pub fn rweibull(shape: f64, scale: f64) -> f64;
+            pub fn rwilcox(m: f64, n: f64) -> f64;
             pub fn sinpi(x: f64) -> f64;
             pub fn tanpi(x: f64) -> f64;
+            pub fn tetragamma(x: f64) -> f64;
+            pub fn trigamma(x: f64) -> f64;
+            /// The normal kind of `norm_rand_kinds`, see `build.rs`.
+            pub static mut N01_kind_kinds: i32;
         }
     }
 
@@ -133,6 +242,441 @@ mod test_math {
         assert_eq!(cospi(0.234), unsafe { c::cospi(0.234) });
     }
 
+    #[test]
+    fn test_bessel_i() {
+        assert!(bessel_i(f64::NAN, 1.0, 1.0).is_nan());
+        assert!(bessel_i(1.0, f64::NAN, 1.0).is_nan());
+        assert_eq!(bessel_i(0.0, 0.0, 1.0), 1.0);
+        assert_eq!(bessel_i(0.0, 2.0, 1.0), 0.0);
+        assert_eq!(bessel_i(800.0, 1.0, 1.0), f64::INFINITY);
+        assert_eq!(take_warnings(), vec![]);
+        assert!(bessel_i(-1.0, 1.0, 1.0).is_nan());
+        // Orders too large for the algorithm, without allocating for them.
+        assert!(bessel_i(1.0, 1e12, 1.0).is_nan());
+        assert!(bessel_i(1.0, -1e12, 1.0).is_nan());
+        assert!(bessel_i(1.0, f64::INFINITY, 1.0).is_nan());
+        assert!(bessel_i(1.0, f64::NEG_INFINITY, 2.0).is_nan());
+        assert!(bessel_i_ex(1.0, f64::INFINITY, 1.0, &mut []).is_nan());
+        let w = MlWarning {
+            kind: MlWarningKind::Range,
+            function: "bessel_i",
+        };
+        assert_eq!(take_warnings(), vec![w; 6]);
+        fn helper(x: f64, alpha: f64, expo: f64) {
+            let actual = bessel_i(x, alpha, expo);
+            let expected = unsafe { c::bessel_i(x, alpha, expo) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "bessel_i({}, {}, {}) = {} != {}",
+                x,
+                alpha,
+                expo,
+                actual,
+                expected
+            );
+            // The work buffer only needs 1 + floor(|alpha|) values.
+            let mut bi = vec![0.0; 1 + alpha.abs().floor() as usize];
+            assert_eq!(
+                bessel_i_ex(x, alpha, expo, &mut bi).to_bits(),
+                actual.to_bits()
+            );
+        }
+        for &x in &[
+            1e-12, 5e-5, 0.3, 1.0, 2.5, 10.0, 35.0, 100.0, 700.0, 1500.0, 2e5,
+        ] {
+            for &alpha in &[-3.5, -2.0, -0.3, 0.0, 0.3, 0.5, 1.0, 2.7, 10.0, 60.5, 250.0] {
+                helper(x, alpha, 1.0);
+                helper(x, alpha, 2.0);
+            }
+        }
+        // Large orders at small x underflow, as in C.
+        assert!(take_warnings()
+            .iter()
+            .all(|w| w.kind == MlWarningKind::Precision && w.function == "bessel_i"));
+    }
+
+    #[test]
+    fn test_bessel_j() {
+        assert!(bessel_j(f64::NAN, 1.0).is_nan());
+        assert!(bessel_j(1.0, f64::NAN).is_nan());
+        assert_eq!(bessel_j(0.0, 0.0), 1.0);
+        assert_eq!(bessel_j(0.0, 2.0), 0.0);
+        assert_eq!(take_warnings(), vec![]);
+        assert!(bessel_j(-1.0, 1.0).is_nan());
+        assert!(bessel_j(1.0, 2e7).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![
+                MlWarning {
+                    kind: MlWarningKind::Range,
+                    function: "bessel_j"
+                },
+                MlWarning {
+                    kind: MlWarningKind::Range,
+                    function: "bessel_j"
+                }
+            ]
+        );
+        // Beyond the algorithm's limit on x the result is zero, as in C.
+        assert_eq!(bessel_j(2e5, 1.0), 0.0);
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::Range,
+                function: "J_bessel"
+            }]
+        );
+        fn helper(x: f64, alpha: f64) {
+            let actual = bessel_j(x, alpha);
+            let expected = unsafe { c::bessel_j(x, alpha) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "bessel_j({}, {}) = {} != {}",
+                x,
+                alpha,
+                actual,
+                expected
+            );
+            let mut bj = vec![0.0; 1 + alpha.abs().floor() as usize];
+            assert_eq!(bessel_j_ex(x, alpha, &mut bj).to_bits(), actual.to_bits());
+        }
+        for &x in &[
+            1e-12, 5e-5, 0.3, 1.0, 2.9, 3.5, 10.0, 15.9, 20.0, 30.0, 40.0, 150.0, 1e4,
+        ] {
+            for &alpha in &[-3.5, -2.0, -0.3, 0.0, 0.3, 0.5, 1.0, 2.7, 10.0, 60.5, 250.0] {
+                helper(x, alpha);
+            }
+        }
+        // Large orders at small x underflow, as in C.
+        assert!(take_warnings()
+            .iter()
+            .all(|w| w.kind == MlWarningKind::Precision && w.function == "bessel_j"));
+    }
+
+    #[test]
+    fn test_bessel_k() {
+        assert!(bessel_k(f64::NAN, 1.0, 1.0).is_nan());
+        assert!(bessel_k(1.0, f64::NAN, 1.0).is_nan());
+        assert_eq!(bessel_k(0.0, 1.0, 1.0), f64::INFINITY);
+        assert_eq!(bessel_k(800.0, 1.0, 1.0), 0.0);
+        // K is symmetric in the order.
+        assert_eq!(bessel_k(2.0, -1.3, 1.0), bessel_k(2.0, 1.3, 1.0));
+        assert_eq!(take_warnings(), vec![]);
+        assert!(bessel_k(-1.0, 1.0, 1.0).is_nan());
+        // Orders too large for the algorithm, without allocating for them.
+        assert!(bessel_k(1.0, 1e12, 1.0).is_nan());
+        assert!(bessel_k(1.0, -1e12, 1.0).is_nan());
+        assert!(bessel_k(1.0, f64::INFINITY, 1.0).is_nan());
+        assert!(bessel_k(1.0, f64::NEG_INFINITY, 2.0).is_nan());
+        assert!(bessel_k_ex(1.0, f64::INFINITY, 1.0, &mut []).is_nan());
+        let w = MlWarning {
+            kind: MlWarningKind::Range,
+            function: "bessel_k",
+        };
+        assert_eq!(take_warnings(), vec![w; 6]);
+        fn helper(x: f64, alpha: f64, expo: f64) {
+            let actual = bessel_k(x, alpha, expo);
+            let expected = unsafe { c::bessel_k(x, alpha, expo) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "bessel_k({}, {}, {}) = {} != {}",
+                x,
+                alpha,
+                expo,
+                actual,
+                expected
+            );
+            let mut bk = vec![0.0; 1 + alpha.abs().floor() as usize];
+            assert_eq!(
+                bessel_k_ex(x, alpha, expo, &mut bk).to_bits(),
+                actual.to_bits()
+            );
+        }
+        for &x in &[1e-12, 1e-5, 0.3, 1.0, 2.5, 4.0, 10.0, 100.0, 700.0, 1e17] {
+            for &alpha in &[-3.5, -0.3, 0.0, 0.3, 0.5, 0.7, 1.0, 2.7, 10.0, 60.5, 250.0] {
+                helper(x, alpha, 1.0);
+                helper(x, alpha, 2.0);
+            }
+        }
+        assert_eq!(take_warnings(), vec![]);
+    }
+
+    #[test]
+    fn test_bessel_y() {
+        assert!(bessel_y(f64::NAN, 1.0).is_nan());
+        assert!(bessel_y(1.0, f64::NAN).is_nan());
+        assert_eq!(bessel_y(0.0, 1.0), f64::NEG_INFINITY);
+        assert_eq!(take_warnings(), vec![]);
+        assert!(bessel_y(-1.0, 1.0).is_nan());
+        assert!(bessel_y(1.0, 2e7).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![
+                MlWarning {
+                    kind: MlWarningKind::Range,
+                    function: "bessel_y"
+                },
+                MlWarning {
+                    kind: MlWarningKind::Range,
+                    function: "bessel_y"
+                }
+            ]
+        );
+        fn helper(x: f64, alpha: f64) {
+            let actual = bessel_y(x, alpha);
+            let expected = unsafe { c::bessel_y(x, alpha) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "bessel_y({}, {}) = {} != {}",
+                x,
+                alpha,
+                actual,
+                expected
+            );
+            let mut by = vec![0.0; 1 + alpha.abs().floor() as usize];
+            assert_eq!(bessel_y_ex(x, alpha, &mut by).to_bits(), actual.to_bits());
+        }
+        for &x in &[
+            1e-12, 5e-5, 0.3, 1.0, 2.9, 3.5, 10.0, 15.9, 20.0, 30.0, 40.0, 150.0, 1e4, 2e5,
+        ] {
+            for &alpha in &[-3.5, -2.0, -0.3, 0.0, 0.3, 0.5, 1.0, 2.7, 10.0, 60.5, 250.0] {
+                helper(x, alpha);
+            }
+        }
+        // Large orders at small x overflow, and negative orders at large x
+        // reflect through J beyond its limit on x, as in C.
+        assert!(take_warnings()
+            .iter()
+            .all(
+                |w| (w.kind == MlWarningKind::Precision && w.function == "bessel_y")
+                    || (w.kind == MlWarningKind::Range && w.function == "J_bessel")
+            ));
+    }
+
+    #[test]
+    fn test_beta() {
+        assert!(beta(f64::NAN, 1.0).is_nan());
+        assert!(beta(1.0, f64::NAN).is_nan());
+        assert!(beta(-1.0, 1.0).is_nan());
+        assert_eq!(beta(0.0, 2.0), f64::INFINITY);
+        assert_eq!(beta(f64::INFINITY, 2.0), 0.0);
+        assert_eq!(beta(2.0, 3.0), 1.0 / 12.0);
+        fn helper(a: f64, b: f64) {
+            let actual = beta(a, b);
+            let expected = unsafe { c::beta(a, b) };
+            assert_eq!(actual.to_bits(), expected.to_bits(), "beta({}, {})", a, b);
+        }
+        for &a in &[1e-300, 1e-10, 0.3, 1.0, 2.5, 10.0, 85.0, 100.0, 1e3, 1e10] {
+            for &b in &[1e-8, 0.5, 1.0, 3.7, 40.0, 90.0, 1e4] {
+                helper(a, b);
+                helper(b, a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_choose() {
+        assert!(choose(f64::NAN, 1.0).is_nan());
+        assert!(choose(1.0, f64::NAN).is_nan());
+        assert_eq!(choose(5.0, 2.0), 10.0);
+        assert_eq!(choose(5.0, -1.0), 0.0);
+        assert_eq!(choose(5.0, 0.0), 1.0);
+        assert_eq!(choose(3.0, 5.0), 0.0);
+        assert_eq!(choose(-3.0, 2.0), 6.0);
+        assert_eq!(take_warnings(), vec![]);
+        assert_eq!(choose(5.0, 2.4), 10.0);
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::NonInteger,
+                function: "choose"
+            }]
+        );
+        fn helper(n: f64, k: f64) {
+            let actual = choose(n, k);
+            let expected = unsafe { c::choose(n, k) };
+            assert_eq!(actual.to_bits(), expected.to_bits(), "choose({}, {})", n, k);
+        }
+        for &n in &[
+            -60.5, -35.0, -4.0, -0.5, 0.0, 0.7, 4.0, 10.0, 29.5, 50.0, 61.3, 100.0, 1030.0,
+        ] {
+            for &k in &[0.0, 1.0, 2.0, 3.0, 7.0, 29.0, 30.0, 31.0, 45.0, 70.0, 500.0] {
+                helper(n, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dbeta() {
+        assert!(dbeta(0.5, -1.0, 2.0, false).is_nan());
+        assert_eq!(dbeta(-0.1, 2.0, 3.0, false), 0.0);
+        assert_eq!(dbeta(1.5, 2.0, 3.0, true), f64::NEG_INFINITY);
+        assert_eq!(dbeta(0.0, 0.5, 3.0, false), f64::INFINITY);
+        assert_eq!(dbeta(0.3, 0.0, 0.0, false), 0.0);
+        assert_eq!(dbeta(0.0, 0.0, 2.0, false), f64::INFINITY);
+        fn helper(x: f64, a: f64, b: f64, give_log: bool) {
+            let expected = unsafe { c::dbeta(x, a, b, give_log as i32) };
+            let actual = dbeta(x, a, b, give_log);
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-15 * expected.abs()),
+                "dbeta({}, {}, {}, {})",
+                x,
+                a,
+                b,
+                give_log
+            );
+        }
+        for &x in &[0.0, 1e-10, 0.1, 0.5, 0.77, 1.0] {
+            // a, b <= 2 use the direct formula, larger ones `dbinom_raw`.
+            for &(a, b) in &[(0.5, 0.5), (1.0, 1.0), (2.0, 5.0), (3.5, 1.5), (20.0, 30.5)] {
+                helper(x, a, b, false);
+                helper(x, a, b, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dbinom() {
+        assert!(dbinom(1.0, 5.0, 1.5, false).is_nan());
+        assert!(dbinom(1.0, 5.5, 0.5, false).is_nan());
+        assert!(dbinom(1.0, -1.0, 0.5, false).is_nan());
+        assert_eq!(dbinom(2.5, 5.0, 0.3, false), 0.0);
+        assert_eq!(dbinom(2.5, 5.0, 0.3, true), f64::NEG_INFINITY);
+        assert_eq!(dbinom(-1.0, 5.0, 0.3, false), 0.0);
+        assert_eq!(dbinom(6.0, 5.0, 0.3, false), 0.0);
+        assert_eq!(dbinom(0.0, 0.0, 0.3, false), 1.0);
+        assert_eq!(dbinom(0.0, 5.0, 0.0, false), 1.0);
+        assert_eq!(dbinom(5.0, 5.0, 1.0, false), 1.0);
+        fn helper(x: f64, n: f64, p: f64, give_log: bool) {
+            let actual = dbinom(x, n, p, give_log);
+            let expected = unsafe { c::dbinom(x, n, p, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-15 * expected.abs()),
+                "dbinom({}, {}, {}, {})",
+                x,
+                n,
+                p,
+                give_log
+            );
+        }
+        for &(n, p) in &[
+            (1.0, 0.5),
+            (10.0, 0.05),
+            (10.0, 0.95),
+            (137.0, 0.3),
+            (1e6, 1e-4),
+        ] {
+            for &x in &[0.0, 1.0, 2.0, 5.0, 10.0, 41.0, 100.0, 137.0, 1e6] {
+                helper(x, n, p, false);
+                helper(x, n, p, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dcauchy() {
+        assert!(dcauchy(1.0, 0.0, 0.0, false).is_nan());
+        assert!(dcauchy(1.0, 0.0, -1.0, false).is_nan());
+        assert_eq!(dcauchy(f64::INFINITY, 0.0, 1.0, false), 0.0);
+        for &(location, scale) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 20.0)] {
+            for &x in &[-1e10, -10.0, -1.0, 0.0, 0.5, 3.0, 1e5, 1e300] {
+                for &give_log in &[false, true] {
+                    let expected = unsafe { c::dcauchy(x, location, scale, give_log as i32) };
+                    assert_eq!(
+                        dcauchy(x, location, scale, give_log),
+                        expected,
+                        "dcauchy({}, {}, {}, {})",
+                        x,
+                        location,
+                        scale,
+                        give_log
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dchisq() {
+        assert!(dchisq(1.0, -1.0, false).is_nan());
+        assert_eq!(dchisq(0.0, 2.0, false), unsafe {
+            c::dgamma(0.0, 1.0, 2.0, false)
+        });
+        assert_eq!(dchisq(0.4, 0.5, false), unsafe {
+            c::dgamma(0.4, 0.25, 2.0, false)
+        });
+        assert_eq!(dchisq(3.21, 5.0, false), unsafe {
+            c::dgamma(3.21, 2.5, 2.0, false)
+        });
+        assert_eq!(dchisq(123.0, 10.0, true), unsafe {
+            c::dgamma(123.0, 5.0, 2.0, true)
+        });
+    }
+
+    #[test]
+    fn test_dexp() {
+        assert!(dexp(1.0, 0.0, false).is_nan());
+        assert!(dexp(1.0, -1.0, false).is_nan());
+        assert_eq!(dexp(-1.0, 2.0, false), 0.0);
+        assert_eq!(dexp(-1.0, 2.0, true), f64::NEG_INFINITY);
+        assert_eq!(dexp(0.0, 2.0, false), 0.5);
+        for &scale in &[1e-5, 0.5, 1.0, 3.0, 1e10] {
+            for &x in &[0.0, 1e-10, 0.5, 1.0, 10.0, 1e3, 1e10] {
+                for &give_log in &[false, true] {
+                    let expected = unsafe { c::dexp(x, scale, give_log as i32) };
+                    assert_eq!(
+                        dexp(x, scale, give_log),
+                        expected,
+                        "dexp({}, {}, {})",
+                        x,
+                        scale,
+                        give_log
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_df() {
+        assert!(df(1.0, -1.0, 2.0, false).is_nan());
+        assert_eq!(df(-1.0, 3.0, 2.0, false), 0.0);
+        assert_eq!(df(0.0, 1.0, 2.0, false), f64::INFINITY);
+        assert_eq!(df(0.0, 2.0, 2.0, false), 1.0);
+        assert_eq!(df(0.0, 3.0, 2.0, false), 0.0);
+        assert_eq!(df(1.0, f64::INFINITY, f64::INFINITY, false), f64::INFINITY);
+        assert_eq!(df(1.5, f64::INFINITY, f64::INFINITY, false), 0.0);
+        fn helper(x: f64, m: f64, n: f64, give_log: bool) {
+            let actual = df(x, m, n, give_log);
+            let expected = unsafe { c::df(x, m, n, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "df({}, {}, {}, {})",
+                x,
+                m,
+                n,
+                give_log
+            );
+        }
+        // Includes the limits for df1 > 1e14 and df2 = Inf.
+        let dfs = [0.5, 1.0, 2.0, 3.5, 10.0, 1e6, 1e15, f64::INFINITY];
+        for &m in &dfs {
+            for &n in &dfs {
+                for &x in &[1e-5, 0.1, 0.5, 1.0, 1.7, 5.0, 1e3] {
+                    helper(x, m, n, false);
+                    helper(x, m, n, true);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_dgamma() {
         assert_eq!(dgamma(0.0, 0.0, 1.0, false), unsafe {
@@ -153,6 +697,315 @@ mod test_math {
         });
     }
 
+    #[test]
+    fn test_dgeom() {
+        assert!(dgeom(1.0, 0.0, false).is_nan());
+        assert!(dgeom(1.0, 1.5, false).is_nan());
+        assert_eq!(dgeom(2.5, 0.3, false), 0.0);
+        assert_eq!(dgeom(-1.0, 0.3, true), f64::NEG_INFINITY);
+        assert_eq!(dgeom(f64::INFINITY, 0.3, false), 0.0);
+        assert_eq!(dgeom(0.0, 1.0, false), 1.0);
+        fn helper(x: f64, p: f64, give_log: bool) {
+            let actual = dgeom(x, p, give_log);
+            let expected = unsafe { c::dgeom(x, p, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-15 * expected.abs()),
+                "dgeom({}, {}, {})",
+                x,
+                p,
+                give_log
+            );
+        }
+        for &p in &[1e-10, 0.01, 0.3, 0.5, 0.99, 1.0] {
+            for &x in &[0.0, 1.0, 2.0, 10.0, 100.0, 1e5, 1e10] {
+                helper(x, p, false);
+                helper(x, p, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dhyper() {
+        assert!(dhyper(1.0, 5.0, 5.0, 11.0, false).is_nan());
+        assert!(dhyper(1.0, 5.5, 5.0, 3.0, false).is_nan());
+        assert_eq!(dhyper(-1.0, 5.0, 5.0, 3.0, false), 0.0);
+        assert_eq!(dhyper(1.5, 5.0, 5.0, 3.0, true), f64::NEG_INFINITY);
+        assert_eq!(dhyper(4.0, 5.0, 5.0, 3.0, false), 0.0);
+        assert_eq!(dhyper(0.0, 5.0, 5.0, 0.0, false), 1.0);
+        fn helper(x: f64, r: f64, b: f64, n: f64, give_log: bool) {
+            let actual = dhyper(x, r, b, n, give_log);
+            let expected = unsafe { c::dhyper(x, r, b, n, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-15 * expected.abs()),
+                "dhyper({}, {}, {}, {}, {})",
+                x,
+                r,
+                b,
+                n,
+                give_log
+            );
+        }
+        for &(r, b, n) in &[
+            (5.0, 5.0, 3.0),
+            (150.0, 150.0, 60.0),
+            (10.0, 1e6, 1e3),
+            (1e9, 2e9, 1e8),
+        ] {
+            for &x in &[0.0, 1.0, 2.0, 3.0, 10.0, 30.0, 59.0, 3e7, 33333333.0] {
+                helper(x, r, b, n, false);
+                helper(x, r, b, n, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_digamma() {
+        assert!(digamma(f64::NAN).is_nan());
+        assert!(digamma(0.0).is_nan());
+        assert!(digamma(-2.0).is_nan());
+        assert_eq!(trigamma(-2.0), f64::INFINITY);
+        assert!(tetragamma(-2.0).is_nan());
+        assert_eq!(pentagamma(0.0), f64::INFINITY);
+        // -gamma, the Euler-Mascheroni constant.
+        assert!(abs_diff_eq!(
+            digamma(1.0),
+            -0.5772156649015329,
+            epsilon = 1e-15
+        ));
+        // pi^2 / 6
+        assert!(abs_diff_eq!(
+            trigamma(1.0),
+            1.6449340668482264,
+            epsilon = 1e-15
+        ));
+        fn helper(x: f64) {
+            for (actual, expected) in [
+                (digamma(x), unsafe { c::digamma(x) }),
+                (trigamma(x), unsafe { c::trigamma(x) }),
+                (tetragamma(x), unsafe { c::tetragamma(x) }),
+                (pentagamma(x), unsafe { c::pentagamma(x) }),
+            ] {
+                assert!(
+                    actual == expected
+                        || (actual.is_nan() && expected.is_nan())
+                        || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                    "x = {}: {} != {}",
+                    x,
+                    actual,
+                    expected
+                );
+            }
+        }
+        for &x in &[
+            -10.5, -3.3, -1.5, -0.7, -1e-8, 1e-300, 1e-17, 1e-5, 0.1, 0.5, 1.0, 2.5, 7.3, 20.0,
+            150.0, 1e5, 1e15, 1e300,
+        ] {
+            helper(x);
+        }
+    }
+
+    #[test]
+    fn test_dlnorm() {
+        assert!(dlnorm(1.0, 0.0, -1.0, false).is_nan());
+        assert!(dlnorm(f64::INFINITY, f64::INFINITY, 1.0, false).is_nan());
+        assert_eq!(dlnorm(1.0, 0.0, 0.0, false), f64::INFINITY);
+        assert_eq!(dlnorm(2.0, 0.0, 0.0, true), f64::NEG_INFINITY);
+        assert_eq!(dlnorm(0.0, 0.0, 1.0, false), 0.0);
+        assert_eq!(dlnorm(-1.0, 0.0, 1.0, true), f64::NEG_INFINITY);
+        for &(meanlog, sdlog) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0)] {
+            for &x in &[1e-300, 1e-10, 0.5, 1.0, 3.0, 1e5, 1e300] {
+                for &give_log in &[false, true] {
+                    let expected = unsafe { c::dlnorm(x, meanlog, sdlog, give_log as i32) };
+                    assert_eq!(
+                        dlnorm(x, meanlog, sdlog, give_log),
+                        expected,
+                        "dlnorm({}, {}, {}, {})",
+                        x,
+                        meanlog,
+                        sdlog,
+                        give_log
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dlogis() {
+        assert!(dlogis(1.0, 0.0, 0.0, false).is_nan());
+        assert_eq!(dlogis(0.0, 0.0, 1.0, false), 0.25);
+        assert_eq!(dlogis(-2.0, 0.0, 1.0, false), dlogis(2.0, 0.0, 1.0, false));
+        for &(location, scale) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 20.0)] {
+            for &x in &[-1e10, -10.0, -1.0, 0.0, 0.5, 3.0, 1e5, 1e300] {
+                for &give_log in &[false, true] {
+                    let expected = unsafe { c::dlogis(x, location, scale, give_log as i32) };
+                    assert_eq!(
+                        dlogis(x, location, scale, give_log),
+                        expected,
+                        "dlogis({}, {}, {}, {})",
+                        x,
+                        location,
+                        scale,
+                        give_log
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dnbeta() {
+        assert!(dnbeta(0.5, 2.0, 3.0, -1.0, false).is_nan());
+        assert!(dnbeta(0.5, f64::INFINITY, 3.0, 1.0, false).is_nan());
+        assert_eq!(dnbeta(1.5, 2.0, 3.0, 1.0, false), 0.0);
+        assert_eq!(dnbeta(0.3, 2.0, 3.0, 0.0, true), dbeta(0.3, 2.0, 3.0, true));
+        fn helper(x: f64, a: f64, b: f64, ncp: f64, give_log: bool) {
+            let actual = dnbeta(x, a, b, ncp, give_log);
+            let expected = unsafe { c::dnbeta(x, a, b, ncp, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "dnbeta({}, {}, {}, {}, {})",
+                x,
+                a,
+                b,
+                ncp,
+                give_log
+            );
+        }
+        for &x in &[0.0, 1e-5, 0.2, 0.5, 0.9, 1.0] {
+            for &(a, b) in &[(0.5, 0.5), (2.0, 3.0), (10.0, 1.5)] {
+                for &ncp in &[0.5, 10.0, 200.0, 5000.0] {
+                    helper(x, a, b, ncp, false);
+                    helper(x, a, b, ncp, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dnbinom() {
+        assert!(dnbinom(1.0, 3.0, 0.0, false).is_nan());
+        assert!(dnbinom(1.0, -1.0, 0.5, false).is_nan());
+        assert!(dnbinom_mu(1.0, 3.0, -1.0, false).is_nan());
+        assert_eq!(dnbinom(2.5, 3.0, 0.5, false), 0.0);
+        assert_eq!(dnbinom_mu(-1.0, 3.0, 2.0, true), f64::NEG_INFINITY);
+        // size == 0 is a point mass at zero.
+        assert_eq!(dnbinom(0.0, 0.0, 0.5, false), 1.0);
+        assert_eq!(dnbinom(3.0, 0.0, 0.5, false), 0.0);
+        assert_eq!(dnbinom_mu(0.0, 0.0, 2.0, true), 0.0);
+        // size = Inf is the Poisson limit.
+        assert_eq!(
+            dnbinom_mu(3.0, f64::INFINITY, 2.0, false),
+            dpois(3.0, 2.0, false)
+        );
+        fn helper(x: f64, size: f64, prob: f64, mu: f64, give_log: bool) {
+            let actual = dnbinom(x, size, prob, give_log);
+            let expected = unsafe { c::dnbinom(x, size, prob, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-15 * expected.abs()),
+                "dnbinom({}, {}, {}, {})",
+                x,
+                size,
+                prob,
+                give_log
+            );
+            let actual = dnbinom_mu(x, size, mu, give_log);
+            let expected = unsafe { c::dnbinom_mu(x, size, mu, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-15 * expected.abs()),
+                "dnbinom_mu({}, {}, {}, {})",
+                x,
+                size,
+                mu,
+                give_log
+            );
+        }
+        // Huge sizes use the formula for x << size in `dnbinom_mu`.
+        for &size in &[0.5, 1.0, 3.0, 47.5, 1e5, 1e12, f64::INFINITY] {
+            for &(prob, mu) in &[(0.01, 1e-3), (0.3, 0.5), (0.5, 3.0), (0.99, 200.0)] {
+                for &x in &[0.0, 1.0, 2.0, 7.0, 100.0, 1e4] {
+                    helper(x, size, prob, mu, false);
+                    helper(x, size, prob, mu, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dnchisq() {
+        assert!(dnchisq(1.0, -1.0, 2.0, false).is_nan());
+        assert!(dnchisq(1.0, 2.0, f64::INFINITY, false).is_nan());
+        assert_eq!(dnchisq(-1.0, 3.0, 2.0, false), 0.0);
+        assert_eq!(dnchisq(0.0, 1.0, 2.0, false), f64::INFINITY);
+        assert_eq!(dnchisq(f64::INFINITY, 1.0, 2.0, true), f64::NEG_INFINITY);
+        assert_eq!(dnchisq(2.5, 3.0, 0.0, false), dchisq(2.5, 3.0, false));
+        fn helper(x: f64, df: f64, ncp: f64, give_log: bool) {
+            let actual = dnchisq(x, df, ncp, give_log);
+            let expected = unsafe { c::dnchisq(x, df, ncp, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "dnchisq({}, {}, {}, {})",
+                x,
+                df,
+                ncp,
+                give_log
+            );
+        }
+        for &df in &[0.0, 0.5, 3.0, 20.0, 500.0] {
+            // ncp > 1000 with an underflowing mid term uses the central
+            // approximation.
+            for &ncp in &[0.5, 10.0, 80.0, 300.0, 2000.0] {
+                for &x in &[1e-8, 0.3, 2.0, 15.0, 100.0, 400.0, 3000.0] {
+                    helper(x, df, ncp, false);
+                    helper(x, df, ncp, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dnf() {
+        assert!(dnf(1.0, 0.0, 2.0, 1.0, false).is_nan());
+        assert!(dnf(1.0, 3.0, 2.0, f64::INFINITY, false).is_nan());
+        assert_eq!(dnf(-1.0, 3.0, 2.0, 1.0, false), 0.0);
+        assert_eq!(
+            dnf(1.0, f64::INFINITY, f64::INFINITY, 1.0, false),
+            f64::INFINITY
+        );
+        fn helper(x: f64, df1: f64, df2: f64, ncp: f64, give_log: bool) {
+            let actual = dnf(x, df1, df2, ncp, give_log);
+            let expected = unsafe { c::dnf(x, df1, df2, ncp, give_log as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "dnf({}, {}, {}, {}, {})",
+                x,
+                df1,
+                df2,
+                ncp,
+                give_log
+            );
+        }
+        // Includes the limits for df1 > 1e14 and df2 = Inf.
+        let dfs = [0.5, 2.0, 3.5, 10.0, 1e6, 1e15, f64::INFINITY];
+        for &df1 in &dfs {
+            for &df2 in &dfs {
+                for &ncp in &[0.0, 1.0, 30.0, 150.0] {
+                    for &x in &[1e-5, 0.1, 1.0, 1.7, 30.0] {
+                        helper(x, df1, df2, ncp, false);
+                        helper(x, df1, df2, ncp, true);
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_dnorm() {
         assert_eq!(dnorm(0.0, 0.0, 1.0, false), unsafe {
@@ -175,12 +1028,140 @@ mod test_math {
         });
     }
 
+    #[test]
+    fn test_dnt() {
+        assert!(dnt(1.0, -1.0, 1.0, false).is_nan());
+        assert_eq!(dnt(f64::INFINITY, 3.0, 1.0, false), 0.0);
+        assert_eq!(dnt(0.3, 3.0, 0.0, false), dt(0.3, 3.0, false));
+        fn helper(x: f64, df: f64, ncp: f64, give_log: bool) {
+            let expected = unsafe { c::dnt(x, df, ncp, give_log as i32) };
+            assert!(
+                abs_diff_eq!(
+                    dnt(x, df, ncp, give_log),
+                    expected,
+                    epsilon = 1e-14 * expected.abs()
+                ),
+                "dnt({}, {}, {}, {})",
+                x,
+                df,
+                ncp,
+                give_log
+            );
+        }
+        for &df in &[0.5, 1.0, 3.0, 30.0, 1e9, f64::INFINITY] {
+            for &ncp in &[-1.0, 0.5, 2.0] {
+                for &x in &[-3.0, 0.0, 1e-10, 0.5, 2.5] {
+                    helper(x, df, ncp, false);
+                    helper(x, df, ncp, true);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_dpois() {
         assert!(dpois(-1.0, -1.0, false).is_nan());
         assert!(dpois(1.0, -1.0, false).is_nan());
         assert_eq!(dpois(1.0, 1.0, false), unsafe { c::dpois(1.0, 1.0, false) });
         assert_eq!(dpois(1.0, 1.0, true), unsafe { c::dpois(1.0, 1.0, true) });
+        take_warnings();
+        assert_eq!(dpois(2.5, 3.0, false), 0.0);
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::NonInteger,
+                function: "dpois"
+            }]
+        );
+        assert_eq!(dpois(2.5, 3.0, true), f64::NEG_INFINITY);
+        assert_eq!(dpois(-1.0, 3.0, true), f64::NEG_INFINITY);
+        assert_eq!(dpois(f64::INFINITY, 3.0, true), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_dpsifn() {
+        fn helper(x: f64, n: i32, kode: i32, m: i32) {
+            let mut actual = vec![0.0; m as usize];
+            let (mut nz, mut ierr) = (0, 0);
+            dpsifn(x, n, kode, m, &mut actual, &mut nz, &mut ierr);
+            let mut expected = vec![0.0; m as usize];
+            let (mut c_nz, mut c_ierr) = (0, 0);
+            unsafe { c::dpsifn(x, n, kode, m, expected.as_mut_ptr(), &mut c_nz, &mut c_ierr) };
+            assert_eq!(
+                (nz, ierr),
+                (c_nz, c_ierr),
+                "dpsifn({}, {}, {}, {})",
+                x,
+                n,
+                kode,
+                m
+            );
+            if ierr != 0 {
+                return;
+            }
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert!(
+                    a == e || abs_diff_eq!(*a, *e, epsilon = 1e-14 * e.abs()),
+                    "dpsifn({}, {}, {}, {}) = {:?} != {:?}",
+                    x,
+                    n,
+                    kode,
+                    m,
+                    actual,
+                    expected
+                );
+            }
+        }
+        for &x in &[1e-20, 1e-5, 0.3, 1.0, 2.5, 7.3, 20.0, 150.0, 1e5] {
+            for &n in &[0, 1, 2, 5, 20] {
+                for &kode in &[1, 2] {
+                    for &m in &[1, 2, 5] {
+                        helper(x, n, kode, m);
+                    }
+                }
+            }
+        }
+        // Input errors, and the reflection formula only for m = 1 and n <= 3.
+        helper(1.0, -1, 1, 1);
+        helper(1.0, 0, 3, 1);
+        helper(1.0, 0, 1, 0);
+        helper(-1.5, 4, 1, 1);
+        helper(-1.5, 0, 1, 2);
+    }
+
+    #[test]
+    fn test_dsignrank() {
+        assert!(dsignrank(1.0, 0.0, false).is_nan());
+        assert!(dsignrank(f64::NAN, 3.0, false).is_nan());
+        assert_eq!(dsignrank(1.5, 3.0, false), 0.0);
+        assert_eq!(dsignrank(7.0, 3.0, true), f64::NEG_INFINITY);
+        assert_eq!(dsignrank(0.0, 1.0, false), 0.5);
+        take_warnings();
+        assert!(dsignrank(10.0, 1e5, false).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::Range,
+                function: "dsignrank"
+            }]
+        );
+        for &n in &[1.0, 2.0, 3.0, 10.0, 50.0, 300.0] {
+            let mut x = -1.0;
+            while x <= n * (n + 1.0) / 2.0 + 1.0 {
+                for &give_log in &[false, true] {
+                    let expected = unsafe { c::dsignrank(x, n, give_log as i32) };
+                    assert_eq!(
+                        dsignrank(x, n, give_log),
+                        expected,
+                        "dsignrank({}, {}, {})",
+                        x,
+                        n,
+                        give_log
+                    );
+                }
+                x += if n > 100.0 { 4513.0 } else { 1.0 };
+            }
+        }
     }
 
     #[test]
@@ -207,12 +1188,165 @@ mod test_math {
         ));
     }
 
+    #[test]
+    fn test_dunif() {
+        assert!(dunif(0.5, 1.0, 1.0, false).is_nan());
+        assert!(dunif(0.5, 1.0, 0.0, false).is_nan());
+        assert_eq!(dunif(0.5, 0.0, 2.0, false), 0.5);
+        assert_eq!(dunif(2.0, 0.0, 2.0, false), 0.5);
+        assert_eq!(dunif(2.5, 0.0, 2.0, true), f64::NEG_INFINITY);
+        assert_eq!(dunif(1e300, f64::NEG_INFINITY, f64::INFINITY, false), 0.0);
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 1e10)] {
+            for &x in &[-1e10, -1.0, 0.0, 0.25, 0.5, 3.0, 1e5, 1e10] {
+                for &give_log in &[false, true] {
+                    let expected = unsafe { c::dunif(x, a, b, give_log as i32) };
+                    assert_eq!(
+                        dunif(x, a, b, give_log),
+                        expected,
+                        "dunif({}, {}, {}, {})",
+                        x,
+                        a,
+                        b,
+                        give_log
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dweibull() {
+        assert!(dweibull(1.0, 0.0, 1.0, false).is_nan());
+        assert!(dweibull(1.0, 1.0, -1.0, false).is_nan());
+        assert_eq!(dweibull(-1.0, 2.0, 1.0, false), 0.0);
+        assert_eq!(dweibull(f64::INFINITY, 2.0, 1.0, true), f64::NEG_INFINITY);
+        assert_eq!(dweibull(0.0, 0.5, 1.0, false), f64::INFINITY);
+        assert_eq!(dweibull(0.0, 2.0, 1.0, false), 0.0);
+        // Shape 1 is the exponential distribution.
+        assert_eq!(dweibull(2.0, 1.0, 3.0, false), dexp(2.0, 3.0, false));
+        for &shape in &[0.3, 1.0, 2.5, 10.0] {
+            for &scale in &[1e-3, 1.0, 7.0] {
+                for &x in &[1e-10, 0.01, 0.5, 1.0, 2.0, 10.0, 1e3] {
+                    for &give_log in &[false, true] {
+                        let expected = unsafe { c::dweibull(x, shape, scale, give_log as i32) };
+                        assert_eq!(
+                            dweibull(x, shape, scale, give_log),
+                            expected,
+                            "dweibull({}, {}, {}, {})",
+                            x,
+                            shape,
+                            scale,
+                            give_log
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dwilcox() {
+        let mut counts = WilcoxCounts::new();
+        assert!(dwilcox(1.0, 0.0, 3.0, false, &mut counts).unwrap().is_nan());
+        assert!(dwilcox(f64::NAN, 2.0, 3.0, false, &mut counts)
+            .unwrap()
+            .is_nan());
+        assert_eq!(dwilcox(1.5, 2.0, 3.0, false, &mut counts), Ok(0.0));
+        assert_eq!(
+            dwilcox(7.0, 2.0, 3.0, true, &mut counts),
+            Ok(f64::NEG_INFINITY)
+        );
+        // Larger sizes first, so that smaller ones reuse the cached counts.
+        for &(m, n) in &[
+            (30.0, 40.0),
+            (1.0, 1.0),
+            (2.0, 3.0),
+            (7.0, 5.0),
+            (10.0, 10.0),
+        ] {
+            let mut x = -1.0;
+            while x <= m * n + 1.0 {
+                for &give_log in &[false, true] {
+                    let expected = unsafe { c::dwilcox(x, m, n, give_log as i32) };
+                    assert_eq!(
+                        dwilcox(x, m, n, give_log, &mut counts),
+                        Ok(expected),
+                        "dwilcox({}, {}, {}, {})",
+                        x,
+                        m,
+                        n,
+                        give_log
+                    );
+                }
+                x += 1.0;
+            }
+        }
+        // Sizes whose counts would exceed the limit are rejected.
+        let mut small = WilcoxCounts::with_limit(1000);
+        assert_eq!(small.limit(), 1000);
+        assert!(dwilcox(3.0, 5.0, 5.0, false, &mut small).is_ok());
+        let err = dwilcox(3.0, 50.0, 40.0, false, &mut small).unwrap_err();
+        assert_eq!((err.m, err.n, err.limit), (40, 50, 1000));
+        assert!(err.required > 1000);
+        assert!(err.to_string().contains("exceeding the limit of 1000"));
+        small.clear();
+        assert_eq!(
+            dwilcox(3.0, 5.0, 5.0, false, &mut small),
+            dwilcox(3.0, 5.0, 5.0, false, &mut counts)
+        );
+        // m = n = 3 needs (6 * 6) / 2 + 4 * 4 = 34 counts.
+        let mut exact = WilcoxCounts::with_limit(34);
+        assert!(dwilcox(4.0, 3.0, 3.0, false, &mut exact).is_ok());
+        let mut short = WilcoxCounts::with_limit(33);
+        assert_eq!(
+            dwilcox(4.0, 3.0, 3.0, false, &mut short),
+            Err(WilcoxError {
+                m: 3,
+                n: 3,
+                required: 34,
+                limit: 33
+            })
+        );
+    }
+
+    #[test]
+    fn test_exp_rand() {
+        set_c_seed(7);
+        let mut rng = Lcg(7);
+        for _ in 0..10000 {
+            let expected = unsafe { c::exp_rand() };
+            assert_eq!(exp_rand(&mut rng), expected);
+        }
+        // set.seed(1); rexp(3)
+        let mut rng = RRng::new(1, RngKind::MersenneTwister);
+        for &e in &[0.7551818, 1.1816428, 0.1457067] {
+            let x = exp_rand(&mut rng);
+            assert!(abs_diff_eq!(x, e, epsilon = 5e-8), "{} != {}", x, e);
+        }
+    }
+
     #[test]
     fn test_gammafn() {
         assert!(gammafn(-1.0).is_nan());
         assert!(gammafn(0.0).is_nan());
         assert_eq!(gammafn(0.1), unsafe { c::gammafn(0.1) });
         assert_eq!(gammafn(1.0), unsafe { c::gammafn(1.0) });
+        assert_eq!(take_warnings(), vec![]);
+        assert_eq!(gammafn(1e-310), f64::INFINITY);
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::Range,
+                function: "gammafn"
+            }]
+        );
+        for &x in &[1e-10, 0.5, 1.7, 4.2, 9.9, -0.5, -3.3] {
+            assert_eq!(gammafn(x), unsafe { c::gammafn(x) }, "gammafn({})", x);
+        }
+        // |x| > 10 uses Stirling's formula, except for integers up to 50.
+        for &x in &[10.75, 33.0, 49.5, 120.3, -12.5] {
+            assert_eq!(gammafn(x), unsafe { c::gammafn(x) }, "gammafn({})", x);
+        }
     }
 
     #[test]
@@ -231,11 +1365,60 @@ mod test_math {
         assert!(lbeta(100.0, -100.0).is_nan());
     }
 
+    #[test]
+    fn test_lchoose() {
+        assert!(lchoose(f64::NAN, 1.0).is_nan());
+        assert!(lchoose(1.0, f64::NAN).is_nan());
+        assert_eq!(lchoose(5.0, -1.0), f64::NEG_INFINITY);
+        assert_eq!(lchoose(5.0, 0.0), 0.0);
+        assert_eq!(lchoose(3.0, 5.0), f64::NEG_INFINITY);
+        assert_eq!(take_warnings(), vec![]);
+        assert_eq!(lchoose(5.0, 0.4), 0.0);
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::NonInteger,
+                function: "lchoose"
+            }]
+        );
+        fn helper(n: f64, k: f64) {
+            let actual = lchoose(n, k);
+            let expected = unsafe { c::lchoose(n, k) };
+            assert_eq!(
+                actual.to_bits(),
+                expected.to_bits(),
+                "lchoose({}, {})",
+                n,
+                k
+            );
+        }
+        for &n in &[
+            -60.5, -35.0, -4.0, -0.5, 0.0, 0.7, 4.0, 10.0, 29.5, 50.0, 61.3, 100.0, 1030.0,
+        ] {
+            for &k in &[0.0, 1.0, 2.0, 3.0, 7.0, 29.0, 30.0, 31.0, 45.0, 70.0, 500.0] {
+                helper(n, k);
+            }
+        }
+    }
+
     #[test]
     fn test_lgammafn() {
         assert_eq!(lgammafn(0.0), unsafe { c::lgammafn(0.0) });
         assert_eq!(lgammafn(-1.0), unsafe { c::lgammafn(-1.0) });
         assert_eq!(lgammafn(1.0), unsafe { c::lgammafn(1.0) });
+        // x < -10
+        assert_eq!(lgammafn(-28.3), unsafe { c::lgammafn(-28.3) });
+        assert_eq!(lgammafn(-10.5), unsafe { c::lgammafn(-10.5) });
+        assert_eq!(take_warnings(), vec![]);
+        // Too near a negative integer for full precision.
+        assert_eq!(lgammafn(1e-9 - 11.0), unsafe { c::lgammafn(1e-9 - 11.0) });
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::Precision,
+                function: "lgamma"
+            }]
+        );
     }
 
     #[test]
@@ -260,6 +1443,21 @@ mod test_math {
         });
     }
 
+    #[test]
+    fn test_log1pexp() {
+        assert_eq!(log1pexp(0.0), 2.0f64.ln());
+        assert_eq!(log1pexp(800.0), 800.0);
+        assert_eq!(log1pexp(-800.0), 0.0);
+        for &x in &[
+            -745.0, -40.0, -1e-10, 1e-10, 1.0, 17.9, 18.0, 18.1, 25.0, 33.3, 33.4, 1e10,
+        ] {
+            assert_eq!(log1pexp(x), unsafe { c::log1pexp(x) }, "log1pexp({})", x);
+        }
+        for &x in &[1e-300, 1e-10, 0.5, 2.0f64.ln(), 1.0, 30.0, 800.0] {
+            assert_eq!(log1mexp(x), unsafe { c::log1mexp(x) }, "log1mexp({})", x);
+        }
+    }
+
     #[test]
     fn test_log1pmx() {
         assert_eq!(log1pmx(0.0), unsafe { c::log1pmx(0.0) });
@@ -271,6 +1469,175 @@ mod test_math {
         assert_eq!(log1pmx(0.81), unsafe { c::log1pmx(0.81) });
     }
 
+    #[test]
+    fn test_pbinom() {
+        assert!(pbinom(1.0, 5.5, 0.5, true, false).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::NonInteger,
+                function: "pbinom"
+            }]
+        );
+        assert!(pbinom(1.0, f64::INFINITY, 0.5, true, false).is_nan());
+        assert!(pbinom(1.0, 5.0, -0.1, true, false).is_nan());
+        assert_eq!(pbinom(-1.0, 5.0, 0.3, true, false), 0.0);
+        assert_eq!(pbinom(5.0, 5.0, 0.3, true, false), 1.0);
+        assert_eq!(pbinom(5.0, 5.0, 0.3, false, true), f64::NEG_INFINITY);
+        fn helper(x: f64, n: f64, p: f64, lower_tail: bool, log_p: bool) {
+            let actual = pbinom(x, n, p, lower_tail, log_p);
+            let expected = unsafe { c::pbinom(x, n, p, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "pbinom({}, {}, {}, {}, {})",
+                x,
+                n,
+                p,
+                lower_tail,
+                log_p
+            );
+        }
+        for &(n, p) in &[
+            (1.0, 0.5),
+            (10.0, 0.05),
+            (10.0, 0.95),
+            (137.0, 0.3),
+            (1e6, 1e-4),
+        ] {
+            for &x in &[0.0, 1.0, 2.0, 2.5, 5.0, 9.0, 41.0, 100.0, 1e6] {
+                for &lower_tail in &[true, false] {
+                    helper(x, n, p, lower_tail, false);
+                    helper(x, n, p, lower_tail, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pcauchy() {
+        assert!(pcauchy(1.0, 0.0, 0.0, true, false).is_nan());
+        assert!(pcauchy(f64::INFINITY, f64::INFINITY, 1.0, true, false).is_nan());
+        assert_eq!(pcauchy(f64::NEG_INFINITY, 0.0, 1.0, true, false), 0.0);
+        assert_eq!(
+            pcauchy(f64::INFINITY, 0.0, 1.0, false, true),
+            f64::NEG_INFINITY
+        );
+        assert_eq!(pcauchy(0.0, 0.0, 1.0, true, false), 0.5);
+        for &(location, scale) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 20.0)] {
+            for &x in &[-1e300, -1e10, -10.0, -1.0, 0.0, 0.5, 3.0, 1e5, 1e300] {
+                for &lower_tail in &[true, false] {
+                    for &log_p in &[false, true] {
+                        let expected = unsafe {
+                            c::pcauchy(x, location, scale, lower_tail as i32, log_p as i32)
+                        };
+                        assert_eq!(
+                            pcauchy(x, location, scale, lower_tail, log_p),
+                            expected,
+                            "pcauchy({}, {}, {}, {}, {})",
+                            x,
+                            location,
+                            scale,
+                            lower_tail,
+                            log_p
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pchisq() {
+        assert!(pchisq(1.0, -1.0, true, false).is_nan());
+        fn helper(x: f64, df: f64, lower_tail: bool, log_p: bool) {
+            assert_eq!(
+                pchisq(x, df, lower_tail, log_p),
+                unsafe { c::pchisq(x, df, lower_tail as i32, log_p as i32) },
+                "pchisq({}, {}, {}, {})",
+                x,
+                df,
+                lower_tail,
+                log_p
+            );
+        }
+        for &x in &[0.0, 1e-10, 0.1, 0.65, 1.0, 3.21, 12.5, 123.0, 1e5] {
+            for &df in &[1e-3, 0.5, 1.0, 2.0, 5.0, 30.0, 1e3] {
+                helper(x, df, true, false);
+                helper(x, df, false, false);
+                helper(x, df, true, true);
+                helper(x, df, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pexp() {
+        assert!(pexp(1.0, -1.0, true, false).is_nan());
+        assert_eq!(pexp(-1.0, 2.0, true, false), 0.0);
+        assert_eq!(pexp(0.0, 2.0, false, true), 0.0);
+        // The log survival probability is exact far out in the upper tail,
+        // and the log lower tail probability stays accurate near zero.
+        assert_eq!(pexp(1e3, 1.0, false, true), -1e3);
+        assert_eq!(pexp(1e5, 2.0, false, true), -5e4);
+        assert_eq!(pexp(1e-20, 1.0, true, true), (1e-20f64).ln());
+        assert_eq!(pexp(1e-20, 1.0, false, true), -1e-20);
+        for &scale in &[1e-5, 0.5, 1.0, 3.0, 1e10] {
+            for &x in &[1e-300, 1e-10, 0.5, 1.0, 10.0, 1e3, 1e10] {
+                for &lower_tail in &[true, false] {
+                    for &log_p in &[false, true] {
+                        let expected =
+                            unsafe { c::pexp(x, scale, lower_tail as i32, log_p as i32) };
+                        assert_eq!(
+                            pexp(x, scale, lower_tail, log_p),
+                            expected,
+                            "pexp({}, {}, {}, {})",
+                            x,
+                            scale,
+                            lower_tail,
+                            log_p
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pf() {
+        assert!(pf(1.0, -1.0, 2.0, true, false).is_nan());
+        assert_eq!(pf(0.0, 3.0, 2.0, true, false), 0.0);
+        assert_eq!(pf(f64::INFINITY, 3.0, 2.0, true, true), 0.0);
+        assert_eq!(pf(1.0, f64::INFINITY, f64::INFINITY, true, false), 0.5);
+        assert_eq!(pf(0.9, f64::INFINITY, f64::INFINITY, true, false), 0.0);
+        assert_eq!(pf(1.1, f64::INFINITY, f64::INFINITY, false, false), 0.0);
+        fn helper(x: f64, df1: f64, df2: f64, lower_tail: bool, log_p: bool) {
+            let actual = pf(x, df1, df2, lower_tail, log_p);
+            let expected = unsafe { c::pf(x, df1, df2, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "pf({}, {}, {}, {}, {})",
+                x,
+                df1,
+                df2,
+                lower_tail,
+                log_p
+            );
+        }
+        let dfs = [0.5, 1.0, 2.0, 3.5, 10.0, 1e6, f64::INFINITY];
+        for &df1 in &dfs {
+            for &df2 in &dfs {
+                for &x in &[1e-5, 0.1, 0.5, 1.0, 1.7, 5.0, 1e3] {
+                    helper(x, df1, df2, true, false);
+                    helper(x, df1, df2, false, false);
+                    helper(x, df1, df2, true, true);
+                    helper(x, df1, df2, false, true);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_pgamma() {
         assert!(pgamma(0.0, -1.0, 1.0, true, false).is_nan());
@@ -297,6 +1664,334 @@ mod test_math {
         });
     }
 
+    #[test]
+    fn test_pgeom() {
+        assert!(pgeom(1.0, 0.0, true, false).is_nan());
+        assert!(pgeom(1.0, 1.5, true, false).is_nan());
+        assert_eq!(pgeom(-1.0, 0.3, true, false), 0.0);
+        assert_eq!(pgeom(f64::INFINITY, 0.3, false, true), f64::NEG_INFINITY);
+        assert_eq!(pgeom(3.0, 1.0, true, false), 1.0);
+        assert_eq!(pgeom(3.0, 1.0, false, true), f64::NEG_INFINITY);
+        // P(X > x) = (1 - p)^(x + 1), so the log survival probability is
+        // (x + 1) * log1p(-p) even where (1 - p)^(x + 1) underflows.
+        assert_eq!(
+            pgeom(1e6, 0.5, false, true),
+            (1e6 + 1.0) * (-0.5f64).ln_1p()
+        );
+        assert_eq!(pgeom(9.0, 1e-20, false, true), 10.0 * (-1e-20f64).ln_1p());
+        fn helper(x: f64, p: f64, lower_tail: bool, log_p: bool) {
+            let actual = pgeom(x, p, lower_tail, log_p);
+            let expected = unsafe { c::pgeom(x, p, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "pgeom({}, {}, {}, {})",
+                x, p, lower_tail, log_p
+            );
+        }
+        for &p in &[1e-20, 1e-10, 0.01, 0.3, 0.5, 0.99, 1.0] {
+            for &x in &[0.0, 1.0, 2.5, 10.0, 100.0, 1e5, 1e10] {
+                for &lower_tail in &[true, false] {
+                    helper(x, p, lower_tail, false);
+                    helper(x, p, lower_tail, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_phyper() {
+        assert!(phyper(1.0, 5.0, 5.0, 11.0, true, false).is_nan());
+        assert!(phyper(1.0, f64::INFINITY, 5.0, 3.0, true, false).is_nan());
+        assert_eq!(phyper(-1.0, 5.0, 5.0, 3.0, true, false), 0.0);
+        assert_eq!(phyper(3.0, 5.0, 5.0, 3.0, true, false), 1.0);
+        // The small right tail from PR#6772 is dhyper(0, 150, 150, 60).
+        assert!(abs_diff_eq!(
+            phyper(59.0, 150.0, 150.0, 60.0, false, false),
+            dhyper(0.0, 150.0, 150.0, 60.0, false),
+            epsilon = 1e-13 * 5.111204798e-22
+        ));
+        fn helper(x: f64, nr: f64, nb: f64, n: f64, lower_tail: bool, log_p: bool) {
+            let actual = phyper(x, nr, nb, n, lower_tail, log_p);
+            let expected = unsafe { c::phyper(x, nr, nb, n, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "phyper({}, {}, {}, {}, {}, {})",
+                x, nr, nb, n, lower_tail, log_p
+            );
+        }
+        for &(nr, nb, n) in &[
+            (5.0, 5.0, 3.0),
+            (150.0, 150.0, 60.0),
+            (10.0, 1e6, 1e3),
+            (1e9, 2e9, 1e8),
+        ] {
+            for &x in &[0.0, 1.0, 2.0, 3.0, 10.0, 30.0, 59.0, 3e7, 33333333.0] {
+                for &lower_tail in &[true, false] {
+                    helper(x, nr, nb, n, lower_tail, false);
+                    helper(x, nr, nb, n, lower_tail, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_plnorm() {
+        assert!(plnorm(1.0, 0.0, -1.0, true, false).is_nan());
+        assert_eq!(plnorm(0.0, 0.0, 1.0, true, false), 0.0);
+        assert_eq!(plnorm(-1.0, 0.0, 1.0, false, true), 0.0);
+        assert_eq!(plnorm(1.0, 0.0, 1.0, true, false), 0.5);
+        for &(meanlog, sdlog) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0)] {
+            for &x in &[1e-300, 1e-10, 0.5, 1.0, 3.0, 1e5, 1e300] {
+                for &lower_tail in &[true, false] {
+                    for &log_p in &[false, true] {
+                        let expected = unsafe {
+                            c::plnorm(x, meanlog, sdlog, lower_tail as i32, log_p as i32)
+                        };
+                        let actual = plnorm(x, meanlog, sdlog, lower_tail, log_p);
+                        // `pnorm5` can differ from C in the last bit.
+                        assert!(
+                            actual == expected
+                                || abs_diff_eq!(actual, expected, epsilon = 1e-15 * expected.abs()),
+                            "plnorm({}, {}, {}, {}, {})",
+                            x,
+                            meanlog,
+                            sdlog,
+                            lower_tail,
+                            log_p
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_plogis() {
+        assert!(plogis(1.0, 0.0, 0.0, true, false).is_nan());
+        assert!(plogis(f64::INFINITY, f64::INFINITY, 1.0, true, false).is_nan());
+        assert_eq!(plogis(f64::NEG_INFINITY, 0.0, 1.0, true, false), 0.0);
+        assert_eq!(
+            plogis(f64::INFINITY, 0.0, 1.0, false, true),
+            f64::NEG_INFINITY
+        );
+        assert_eq!(plogis(0.0, 0.0, 1.0, true, false), 0.5);
+        // Far in the tails the log probabilities do not underflow.
+        assert_eq!(plogis(-800.0, 0.0, 1.0, true, true), -800.0);
+        assert_eq!(plogis(800.0, 0.0, 1.0, false, true), -800.0);
+        for &(location, scale) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 20.0)] {
+            for &x in &[
+                -1e300, -1e10, -40.0, -20.0, -1.0, 0.0, 0.5, 3.0, 25.0, 1e5, 1e300,
+            ] {
+                for &lower_tail in &[true, false] {
+                    for &log_p in &[false, true] {
+                        let expected = unsafe {
+                            c::plogis(x, location, scale, lower_tail as i32, log_p as i32)
+                        };
+                        assert_eq!(
+                            plogis(x, location, scale, lower_tail, log_p),
+                            expected,
+                            "plogis({}, {}, {}, {}, {})",
+                            x,
+                            location,
+                            scale,
+                            lower_tail,
+                            log_p
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pnbeta() {
+        assert!(pnbeta(0.5, 2.0, 3.0, -1.0, true, false).is_nan());
+        assert!(pnbeta(0.5, 0.0, 3.0, 1.0, true, false).is_nan());
+        assert_eq!(pnbeta(0.0, 2.0, 3.0, 1.0, true, false), 0.0);
+        assert_eq!(pnbeta(1.0, 2.0, 3.0, 1.0, true, true), 0.0);
+        assert_eq!(pnbeta(1.2, 2.0, 3.0, 1.0, false, false), 0.0);
+        fn helper(x: f64, a: f64, b: f64, ncp: f64, lower_tail: bool, log_p: bool) {
+            let actual = pnbeta(x, a, b, ncp, lower_tail, log_p);
+            let expected = unsafe { c::pnbeta(x, a, b, ncp, lower_tail as i32, log_p as i32) };
+            // The `long double` sum in C limits the log and the upper tail of a probability
+            // near 1.
+            let tail = match (lower_tail, log_p) {
+                (true, false) => 0.0,
+                (true, true) | (false, false) => 1e-18,
+                (false, true) => 1e-18 / expected.exp(),
+            };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs() + tail),
+                "pnbeta({}, {}, {}, {}, {}, {})",
+                x,
+                a,
+                b,
+                ncp,
+                lower_tail,
+                log_p
+            );
+        }
+        // Cancellation in `temp` turns a last-bit difference in `pbeta` into a 3e-10 error.
+        assert_eq!(pnbeta(0.3, 1.0, 1.0, 50.0, true, false), unsafe {
+            c::pnbeta(0.3, 1.0, 1.0, 50.0, 1, 0)
+        });
+        for &x in &[1e-5, 0.2, 0.5, 0.9, 0.999] {
+            for &(a, b) in &[(0.5, 0.5), (2.0, 3.0), (10.0, 1.5)] {
+                // ncp = 200 needs many more than the original 100 iterations.
+                for &ncp in &[0.5, 10.0, 200.0] {
+                    helper(x, a, b, ncp, true, false);
+                    helper(x, a, b, ncp, false, false);
+                    helper(x, a, b, ncp, true, true);
+                    helper(x, a, b, ncp, false, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pnbinom() {
+        assert!(pnbinom(1.0, 3.0, 0.0, true, false).is_nan());
+        assert!(pnbinom(1.0, f64::INFINITY, 0.5, true, false).is_nan());
+        assert!(pnbinom_mu(1.0, 3.0, f64::INFINITY, true, false).is_nan());
+        assert_eq!(pnbinom(-1.0, 3.0, 0.5, true, false), 0.0);
+        assert_eq!(pnbinom_mu(f64::INFINITY, 3.0, 2.0, true, false), 1.0);
+        // size == 0 is a point mass at zero.
+        assert_eq!(pnbinom(0.0, 0.0, 0.5, true, false), 1.0);
+        assert_eq!(pnbinom_mu(-1.0, 0.0, 2.0, true, false), 0.0);
+        // size = Inf is the Poisson limit.
+        assert_eq!(
+            pnbinom_mu(3.0, f64::INFINITY, 2.0, true, false),
+            ppois(3.0, 2.0, true, false)
+        );
+        // `pbeta` differs from C by up to about 6e-14 for tiny tail probabilities.
+        fn helper(x: f64, size: f64, prob: f64, mu: f64, lower_tail: bool, log_p: bool) {
+            let actual = pnbinom(x, size, prob, lower_tail, log_p);
+            let expected = unsafe { c::pnbinom(x, size, prob, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "pnbinom({}, {}, {}, {}, {})",
+                x,
+                size,
+                prob,
+                lower_tail,
+                log_p
+            );
+            let actual = pnbinom_mu(x, size, mu, lower_tail, log_p);
+            let expected = unsafe { c::pnbinom_mu(x, size, mu, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "pnbinom_mu({}, {}, {}, {}, {})",
+                x,
+                size,
+                mu,
+                lower_tail,
+                log_p
+            );
+        }
+        for &size in &[0.5, 1.0, 3.0, 47.5, 1e5, 1e12] {
+            for &(prob, mu) in &[(0.01, 1e-3), (0.3, 0.5), (0.5, 3.0), (0.99, 200.0)] {
+                for &x in &[0.0, 1.0, 2.0, 7.0, 100.0, 1e4] {
+                    for &lower_tail in &[true, false] {
+                        helper(x, size, prob, mu, lower_tail, false);
+                        helper(x, size, prob, mu, lower_tail, true);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pnchisq() {
+        assert!(pnchisq(1.0, -1.0, 2.0, true, false).is_nan());
+        assert!(pnchisq(1.0, f64::INFINITY, 2.0, true, false).is_nan());
+        assert_eq!(pnchisq(-1.0, 3.0, 2.0, true, false), 0.0);
+        assert_eq!(pnchisq(f64::INFINITY, 3.0, 2.0, false, false), 0.0);
+        // Point mass exp(-ncp/2) at zero for df = 0.
+        assert_eq!(pnchisq(0.0, 0.0, 2.0, true, false), (-1.0f64).exp());
+        fn helper(x: f64, df: f64, ncp: f64, lower_tail: bool, log_p: bool) {
+            let actual = pnchisq(x, df, ncp, lower_tail, log_p);
+            let expected = unsafe { c::pnchisq(x, df, ncp, lower_tail as i32, log_p as i32) };
+            // For ncp > 1417 the Poisson weights start from a sum of rounded logarithms.
+            let rel = if ncp > 1417.0 { 1e-13 } else { 1e-14 };
+            // For ncp >= 80 the upper tail is `1 - ans` with `ans` rounded to `f64`.
+            // Otherwise the `long double` sum in C limits the log of a probability near 1.
+            let tail = match (ncp >= 80.0 && !lower_tail, log_p) {
+                (false, false) => 0.0,
+                (false, true) => 1e-18,
+                (true, false) => 2.5e-16,
+                (true, true) => 2.5e-16 / expected.exp(),
+            };
+            assert!(
+                actual == expected
+                    || (actual.is_nan() && expected.is_nan())
+                    || abs_diff_eq!(actual, expected, epsilon = rel * expected.abs() + tail),
+                "pnchisq({}, {}, {}, {}, {})",
+                x,
+                df,
+                ncp,
+                lower_tail,
+                log_p
+            );
+        }
+        for &df in &[0.0, 0.5, 3.0, 20.0, 500.0] {
+            // ncp < 80 sums Poisson weighted `pchisq`, larger ncp use AS 275.
+            for &ncp in &[0.5, 10.0, 79.0, 80.0, 300.0, 2000.0] {
+                for &x in &[1e-8, 0.3, 2.0, 15.0, 100.0, 400.0, 3000.0] {
+                    helper(x, df, ncp, true, false);
+                    helper(x, df, ncp, false, false);
+                    helper(x, df, ncp, true, true);
+                    helper(x, df, ncp, false, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pnf() {
+        assert!(pnf(1.0, 0.0, 2.0, 1.0, true, false).is_nan());
+        assert!(pnf(1.0, 3.0, 2.0, f64::INFINITY, true, false).is_nan());
+        assert!(pnf(1.0, f64::INFINITY, f64::INFINITY, 1.0, true, false).is_nan());
+        assert_eq!(pnf(0.0, 3.0, 2.0, 1.0, true, false), 0.0);
+        assert_eq!(
+            pnf(f64::INFINITY, 3.0, 2.0, 1.0, false, true),
+            f64::NEG_INFINITY
+        );
+        assert_eq!(
+            pnf(1.5, 3.0, f64::INFINITY, 2.0, true, false),
+            pnchisq(4.5, 3.0, 2.0, true, false)
+        );
+        fn helper(x: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool, log_p: bool) {
+            let actual = pnf(x, df1, df2, ncp, lower_tail, log_p);
+            let expected = unsafe { c::pnf(x, df1, df2, ncp, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "pnf({}, {}, {}, {}, {}, {})",
+                x,
+                df1,
+                df2,
+                ncp,
+                lower_tail,
+                log_p
+            );
+        }
+        // df2 > 1e8 uses `pnchisq`.
+        let dfs = [0.5, 2.0, 3.5, 10.0, 1e9, f64::INFINITY];
+        for &df1 in &[0.5, 2.0, 3.5, 10.0] {
+            for &df2 in &dfs {
+                for &ncp in &[0.0, 1.0, 30.0] {
+                    for &x in &[0.5, 1.0, 1.7, 5.0, 30.0] {
+                        helper(x, df1, df2, ncp, true, false);
+                        helper(x, df1, df2, ncp, false, false);
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_pnorm() {
         assert_eq!(pnorm(0.0, 0.0, 1.0, true, false), 0.5);
@@ -375,27 +2070,159 @@ mod test_math {
             unsafe { c::pnt(-1.0, 10.0, 10.0, false, true) },
             epsilon = 1e-15
         ));
-    }
-
-    #[test]
-    fn test_pt() {
-        assert_eq!(pt(0.1, 1.0, false, false), unsafe {
-            c::pt(0.1, 1.0, false, false)
-        });
-        assert_eq!(pt(-1.0, 1.0, false, false), unsafe {
-            c::pt(-1.0, 1.0, false, false)
-        });
-        assert!(abs_diff_eq!(
-            pt(-1.0, 10.0, false, false),
-            unsafe { c::pt(-1.0, 10.0, false, false) },
-            epsilon = 1e-15
-        ));
-        assert_eq!(pt(1.0, 1.0, false, false), unsafe {
-            c::pt(1.0, 1.0, false, false)
+        assert_eq!(pnt(0.0, 3.0, 1.0, true, false), unsafe {
+            c::pnt(0.0, 3.0, 1.0, true, false)
         });
         assert!(abs_diff_eq!(
-            pt(1.0, 1.0, false, true),
-            unsafe { c::pt(1.0, 1.0, false, true) },
+            pnt(2.0, 7.0, 5.0, true, false),
+            unsafe { c::pnt(2.0, 7.0, 5.0, true, false) },
+            epsilon = 1e-15
+        ));
+    }
+
+    #[test]
+    fn test_ppois() {
+        assert!(ppois(1.0, -1.0, true, false).is_nan());
+        assert_eq!(ppois(-1.0, 3.0, true, false), 0.0);
+        assert_eq!(ppois(2.0, 0.0, true, false), 1.0);
+        assert_eq!(ppois(f64::INFINITY, 3.0, false, true), f64::NEG_INFINITY);
+        fn helper(x: f64, lambda: f64, lower_tail: bool, log_p: bool) {
+            let actual = ppois(x, lambda, lower_tail, log_p);
+            let expected = unsafe { c::ppois(x, lambda, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "ppois({}, {}, {}, {})",
+                x,
+                lambda,
+                lower_tail,
+                log_p
+            );
+        }
+        let lambdas: [f64; 8] = [1e-300, 1e-10, 0.5, 3.0, 47.5, 1e5, 1e10, 1e15];
+        for &lambda in &lambdas {
+            let mut xs = vec![0.0, 1.0, 2.5, 10.0, 100.0];
+            // Around the mean, where the asymptotic expansion in `pgamma` is used.
+            for &z in &[-10.0, -1.0, 0.0, 1.0, 10.0] {
+                xs.push((lambda + z * lambda.sqrt()).floor().max(0.0));
+            }
+            for &x in &xs {
+                for &lower_tail in &[true, false] {
+                    helper(x, lambda, lower_tail, false);
+                    helper(x, lambda, lower_tail, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_psigamma() {
+        assert!(psigamma(f64::NAN, 1.0).is_nan());
+        assert!(psigamma(1.0, -1.0).is_nan());
+        assert_eq!(psigamma(2.5, 0.0), digamma(2.5));
+        assert_eq!(psigamma(2.5, 1.0), trigamma(2.5));
+        assert_eq!(psigamma(2.5, 2.0), tetragamma(2.5));
+        assert_eq!(psigamma(2.5, 3.0), pentagamma(2.5));
+        // deriv is rounded to an integer.
+        assert_eq!(psigamma(2.5, 1.2), trigamma(2.5));
+        // Negative x is not supported above deriv = 3.
+        assert!(psigamma(-1.5, 4.0).is_nan());
+        assert_eq!(take_warnings(), vec![]);
+        assert!(psigamma(2.5, 101.0).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::Range,
+                function: "psigamma"
+            }]
+        );
+        fn helper(x: f64, deriv: f64) {
+            let actual = psigamma(x, deriv);
+            let expected = unsafe { c::psigamma(x, deriv) };
+            assert!(
+                actual == expected
+                    || (actual.is_nan() && expected.is_nan())
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "psigamma({}, {}) = {} != {}",
+                x,
+                deriv,
+                actual,
+                expected
+            );
+        }
+        for &x in &[-2.5, -0.3, 1e-5, 0.3, 1.0, 2.5, 7.3, 20.0, 150.0, 1e5, 1e20] {
+            for &deriv in &[0.0, 1.0, 2.0, 3.0, 4.0, 7.0, 15.0, 30.0, 60.0, 100.0] {
+                helper(x, deriv);
+            }
+        }
+    }
+
+    #[test]
+    fn test_psignrank() {
+        assert!(psignrank(1.0, f64::INFINITY, true, false).is_nan());
+        assert!(psignrank(1.0, -3.0, true, false).is_nan());
+        assert_eq!(psignrank(-1.0, 3.0, true, false), 0.0);
+        assert_eq!(psignrank(6.0, 3.0, false, true), f64::NEG_INFINITY);
+        // The largest statistic n * (n + 1) / 2 must fit in an i32.
+        take_warnings();
+        assert_eq!(psignrank(10.0, 65535.0, true, false), 0.0);
+        assert_eq!(psignrank(-1.0, 1e5, true, false), 0.0);
+        assert_eq!(take_warnings(), vec![]);
+        assert!(psignrank(10.0, 65536.0, true, false).is_nan());
+        assert!(psignrank(10.0, 70000.0, true, false).is_nan());
+        assert!(psignrank(10.0, 1e5, true, false).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![
+                MlWarning {
+                    kind: MlWarningKind::Range,
+                    function: "psignrank"
+                };
+                3
+            ]
+        );
+        for &n in &[1.0, 2.0, 3.0, 10.0, 50.0, 300.0] {
+            let mut x = -1.0;
+            while x <= n * (n + 1.0) / 2.0 + 1.0 {
+                for &lower_tail in &[true, false] {
+                    for &log_p in &[false, true] {
+                        let expected =
+                            unsafe { c::psignrank(x, n, lower_tail as i32, log_p as i32) };
+                        assert_eq!(
+                            psignrank(x, n, lower_tail, log_p),
+                            expected,
+                            "psignrank({}, {}, {}, {})",
+                            x,
+                            n,
+                            lower_tail,
+                            log_p
+                        );
+                    }
+                }
+                x += if n > 100.0 { 4513.0 } else { 1.0 };
+            }
+        }
+    }
+
+    #[test]
+    fn test_pt() {
+        assert_eq!(pt(0.1, 1.0, false, false), unsafe {
+            c::pt(0.1, 1.0, false, false)
+        });
+        assert_eq!(pt(-1.0, 1.0, false, false), unsafe {
+            c::pt(-1.0, 1.0, false, false)
+        });
+        assert!(abs_diff_eq!(
+            pt(-1.0, 10.0, false, false),
+            unsafe { c::pt(-1.0, 10.0, false, false) },
+            epsilon = 1e-15
+        ));
+        assert_eq!(pt(1.0, 1.0, false, false), unsafe {
+            c::pt(1.0, 1.0, false, false)
+        });
+        assert!(abs_diff_eq!(
+            pt(1.0, 1.0, false, true),
+            unsafe { c::pt(1.0, 1.0, false, true) },
             epsilon = 1e-15
         ));
         assert!(abs_diff_eq!(
@@ -415,6 +2242,762 @@ mod test_math {
         ));
     }
 
+    #[test]
+    fn test_ptukey() {
+        assert!(ptukey(f64::NAN, 1.0, 3.0, 10.0, true, false).is_nan());
+        assert!(ptukey(3.0, 1.0, 1.0, 10.0, true, false).is_nan());
+        assert!(ptukey(3.0, 1.0, 3.0, 1.0, true, false).is_nan());
+        assert_eq!(ptukey(0.0, 1.0, 3.0, 10.0, true, false), 0.0);
+        assert_eq!(
+            ptukey(f64::INFINITY, 1.0, 3.0, 10.0, false, true),
+            f64::NEG_INFINITY
+        );
+        fn helper(q: f64, rr: f64, cc: f64, df: f64, lower_tail: bool, log_p: bool) {
+            let actual = ptukey(q, rr, cc, df, lower_tail, log_p);
+            let expected = unsafe { c::ptukey(q, rr, cc, df, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "ptukey({}, {}, {}, {}, {}, {})",
+                q, rr, cc, df, lower_tail, log_p
+            );
+        }
+        // df above 25000 uses the range distribution directly.
+        for &df in &[2.0, 5.0, 99.0, 500.0, 3000.0, 1e4, 1e5] {
+            for &(rr, cc) in &[(1.0, 2.0), (1.0, 3.0), (2.0, 5.0), (1.0, 20.0)] {
+                for &q in &[0.1, 1.0, 2.5, 3.5, 5.0, 10.0, 50.0] {
+                    for &lower_tail in &[true, false] {
+                        helper(q, rr, cc, df, lower_tail, false);
+                        helper(q, rr, cc, df, lower_tail, true);
+                    }
+                }
+            }
+        }
+        assert_eq!(take_warnings(), vec![]);
+    }
+
+    #[test]
+    fn test_punif() {
+        assert!(punif(0.5, 1.0, 0.0, true, false).is_nan());
+        assert!(punif(0.5, 0.0, f64::INFINITY, true, false).is_nan());
+        assert_eq!(punif(1.0, 1.0, 1.0, true, false), 1.0);
+        assert_eq!(punif(0.5, 1.0, 1.0, true, false), 0.0);
+        assert_eq!(punif(0.5, 0.0, 2.0, false, false), 0.75);
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 1e10)] {
+            for &x in &[-1e10, -1.0, 0.0, 0.25, 0.5, 3.0, 1e5, 1e10] {
+                for &lower_tail in &[true, false] {
+                    for &log_p in &[false, true] {
+                        let expected =
+                            unsafe { c::punif(x, a, b, lower_tail as i32, log_p as i32) };
+                        assert_eq!(
+                            punif(x, a, b, lower_tail, log_p),
+                            expected,
+                            "punif({}, {}, {}, {}, {})",
+                            x,
+                            a,
+                            b,
+                            lower_tail,
+                            log_p
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pweibull() {
+        assert!(pweibull(1.0, 0.0, 1.0, true, false).is_nan());
+        assert!(pweibull(1.0, 1.0, 0.0, true, false).is_nan());
+        assert_eq!(pweibull(0.0, 2.0, 1.0, true, false), 0.0);
+        assert_eq!(pweibull(-1.0, 2.0, 1.0, false, true), 0.0);
+        // The log survival probability is -(x / scale)^shape.
+        assert_eq!(pweibull(100.0, 2.0, 1.0, false, true), -1e4);
+        assert_eq!(pweibull(0.5, 2.0, 1.0, false, true), -0.25);
+        assert!(abs_diff_eq!(
+            pweibull(1e-10, 1.0, 1.0, true, true),
+            (1e-10f64).ln() - 5e-11,
+            epsilon = 1e-15
+        ));
+        for &shape in &[0.3, 1.0, 2.5, 10.0] {
+            for &scale in &[1e-3, 1.0, 7.0] {
+                for &x in &[1e-10, 0.01, 0.5, 1.0, 2.0, 10.0, 1e3] {
+                    for &lower_tail in &[true, false] {
+                        for &log_p in &[false, true] {
+                            let expected = unsafe {
+                                c::pweibull(x, shape, scale, lower_tail as i32, log_p as i32)
+                            };
+                            assert_eq!(
+                                pweibull(x, shape, scale, lower_tail, log_p),
+                                expected,
+                                "pweibull({}, {}, {}, {}, {})",
+                                x,
+                                shape,
+                                scale,
+                                lower_tail,
+                                log_p
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pwilcox() {
+        let mut counts = WilcoxCounts::new();
+        assert!(pwilcox(1.0, f64::INFINITY, 3.0, true, false, &mut counts)
+            .unwrap()
+            .is_nan());
+        assert!(pwilcox(1.0, 2.0, -3.0, true, false, &mut counts)
+            .unwrap()
+            .is_nan());
+        assert_eq!(pwilcox(-1.0, 2.0, 3.0, true, false, &mut counts), Ok(0.0));
+        assert_eq!(
+            pwilcox(6.0, 2.0, 3.0, false, true, &mut counts),
+            Ok(f64::NEG_INFINITY)
+        );
+        for &(m, n) in &[
+            (1.0, 1.0),
+            (2.0, 3.0),
+            (7.0, 5.0),
+            (10.0, 10.0),
+            (30.0, 40.0),
+        ] {
+            let mut q = -1.0;
+            while q <= m * n + 1.0 {
+                for &lower_tail in &[true, false] {
+                    for &log_p in &[false, true] {
+                        let expected =
+                            unsafe { c::pwilcox(q, m, n, lower_tail as i32, log_p as i32) };
+                        assert_eq!(
+                            pwilcox(q, m, n, lower_tail, log_p, &mut counts),
+                            Ok(expected),
+                            "pwilcox({}, {}, {}, {}, {})",
+                            q,
+                            m,
+                            n,
+                            lower_tail,
+                            log_p
+                        );
+                    }
+                }
+                q += 1.0;
+            }
+        }
+    }
+
+    #[test]
+    fn test_qbeta() {
+        assert!(qbeta(0.5, -1.0, 2.0, true, false).is_nan());
+        assert!(qbeta(1.5, 1.0, 2.0, true, false).is_nan());
+        assert_eq!(qbeta(0.0, 2.0, 3.0, true, false), 0.0);
+        assert_eq!(qbeta(1.0, 2.0, 3.0, true, false), 1.0);
+        assert_eq!(qbeta(0.0, 2.0, 3.0, true, true), 1.0);
+        assert_eq!(qbeta(0.3, 0.0, 0.0, true, false), 0.0);
+        assert_eq!(qbeta(0.7, 0.0, 0.0, true, false), 1.0);
+        assert_eq!(qbeta(0.7, f64::INFINITY, 2.0, true, false), 1.0);
+        fn helper(alpha: f64, p: f64, q: f64, lower_tail: bool, log_p: bool) {
+            let actual = qbeta(alpha, p, q, lower_tail, log_p);
+            let expected = unsafe { c::qbeta(alpha, p, q, lower_tail as i32, log_p as i32) };
+            // Quantiles for tiny shapes are ill-conditioned; `pbeta` itself
+            // is only accurate to a few ulps there.
+            assert!(
+                actual == expected
+                    || (actual.is_nan() && expected.is_nan())
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "qbeta({}, {}, {}, {}, {})",
+                alpha,
+                p,
+                q,
+                lower_tail,
+                log_p
+            );
+        }
+        let shapes = [1e-200, 1e-10, 0.01, 0.5, 1.0, 2.5, 40.0, 1e6];
+        for &a in &shapes {
+            for &b in &shapes {
+                for &alpha in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999] {
+                    helper(alpha, a, b, true, false);
+                    helper(alpha, a, b, false, false);
+                }
+                for &alpha in &[-800.0, -20.0, -1.0, -1e-5, -1e-20] {
+                    helper(alpha, a, b, true, true);
+                    helper(alpha, a, b, false, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qbinom() {
+        assert!(qbinom(0.5, 5.5, 0.5, true, false).is_nan());
+        assert!(qbinom(1.5, 5.0, 0.5, true, false).is_nan());
+        assert!(qbinom(0.5, 5.0, f64::INFINITY, true, false).is_nan());
+        assert_eq!(qbinom(0.0, 5.0, 0.3, true, false), 0.0);
+        assert_eq!(qbinom(1.0, 5.0, 0.3, true, false), 5.0);
+        assert_eq!(qbinom(f64::NEG_INFINITY, 5.0, 0.3, false, true), 5.0);
+        assert_eq!(qbinom(0.5, 5.0, 1.0, true, false), 5.0);
+        assert_eq!(qbinom(0.5, 0.0, 0.3, true, false), 0.0);
+        // Exact quantiles map back onto their own lower tail probability.
+        for x in 0..=20 {
+            let p = pbinom(x as f64, 20.0, 0.4, true, false);
+            assert_eq!(qbinom(p, 20.0, 0.4, true, false), x as f64);
+        }
+        fn helper(p: f64, n: f64, pr: f64, lower_tail: bool, log_p: bool) {
+            let actual = qbinom(p, n, pr, lower_tail, log_p);
+            let expected = unsafe { c::qbinom(p, n, pr, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "qbinom({}, {}, {}, {}, {})",
+                p, n, pr, lower_tail, log_p
+            );
+        }
+        // n >= 1e5 uses the search with larger increments.
+        for &(n, pr) in &[
+            (1.0, 0.5),
+            (10.0, 0.05),
+            (137.0, 0.3),
+            (1e5, 0.5),
+            (1e9, 1e-3),
+        ] {
+            for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999, 1.0 - 1e-15] {
+                helper(p, n, pr, true, false);
+                helper(p, n, pr, false, false);
+            }
+            for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                helper(p, n, pr, true, true);
+                helper(p, n, pr, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qcauchy() {
+        assert!(qcauchy(1.5, 0.0, 1.0, true, false).is_nan());
+        assert!(qcauchy(0.5, 0.0, f64::NAN, true, false).is_nan());
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0)] {
+            for &lower_tail in &[true, false] {
+                for &p in &[
+                    0.0,
+                    1e-300,
+                    1e-10,
+                    0.01,
+                    0.3,
+                    0.5,
+                    0.9,
+                    0.999,
+                    1.0 - 1e-15,
+                    1.0,
+                ] {
+                    let expected = unsafe { c::qcauchy(p, a, b, lower_tail as i32, 0) };
+                    assert_eq!(
+                        qcauchy(p, a, b, lower_tail, false),
+                        expected,
+                        "qcauchy({}, {}, {}, {}, false)",
+                        p,
+                        a,
+                        b,
+                        lower_tail
+                    );
+                }
+                for &p in &[f64::NEG_INFINITY, -800.0, -5.0, -1.0, -0.1, -1e-20, 0.0] {
+                    let expected = unsafe { c::qcauchy(p, a, b, lower_tail as i32, 1) };
+                    assert_eq!(
+                        qcauchy(p, a, b, lower_tail, true),
+                        expected,
+                        "qcauchy({}, {}, {}, {}, true)",
+                        p,
+                        a,
+                        b,
+                        lower_tail
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qchisq() {
+        assert!(qchisq(0.5, -1.0, true, false).is_nan());
+        assert!(qchisq(1.5, 1.0, true, false).is_nan());
+        assert_eq!(qchisq(0.0, 3.0, true, false), 0.0);
+        assert_eq!(qchisq(1.0, 3.0, true, false), f64::INFINITY);
+        fn helper(p: f64, df: f64, lower_tail: bool, log_p: bool) {
+            let expected = unsafe { c::qchisq(p, df, lower_tail as i32, log_p as i32) };
+            assert!(
+                abs_diff_eq!(
+                    qchisq(p, df, lower_tail, log_p),
+                    expected,
+                    epsilon = 1e-13 * expected.abs()
+                ),
+                "qchisq({}, {}, {}, {})",
+                p,
+                df,
+                lower_tail,
+                log_p
+            );
+        }
+        for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.95, 1.0 - 1e-12] {
+            for &df in &[1e-3, 0.5, 1.0, 2.0, 5.0, 30.0, 1e3] {
+                helper(p, df, true, false);
+                helper(p, df, false, false);
+                helper(p.ln(), df, true, true);
+                helper(p.ln(), df, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qexp() {
+        assert!(qexp(0.5, -1.0, true, false).is_nan());
+        assert!(qexp(1.5, 1.0, true, false).is_nan());
+        assert_eq!(qexp(0.0, 2.0, true, false), 0.0);
+        assert_eq!(qexp(1.0, 2.0, true, false), f64::INFINITY);
+        assert_eq!(qexp(-1e3, 1.0, false, true), 1e3);
+        for &scale in &[1e-5, 0.5, 1.0, 3.0, 1e10] {
+            for &x in &[1e-10, 0.5, 1.0, 10.0, 1e3] {
+                let p = pexp(x, scale, false, true);
+                let q = qexp(p, scale, false, true);
+                assert!(
+                    abs_diff_eq!(q, x, epsilon = 1e-15 * x),
+                    "qexp({}, {})",
+                    p,
+                    scale
+                );
+            }
+            for &lower_tail in &[true, false] {
+                for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999, 1.0 - 1e-15] {
+                    let expected = unsafe { c::qexp(p, scale, lower_tail as i32, 0) };
+                    assert_eq!(qexp(p, scale, lower_tail, false), expected);
+                }
+                for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                    let expected = unsafe { c::qexp(p, scale, lower_tail as i32, 1) };
+                    assert_eq!(qexp(p, scale, lower_tail, true), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qf() {
+        assert!(qf(0.5, -1.0, 2.0, true, false).is_nan());
+        assert_eq!(qf(0.0, 3.0, 2.0, true, false), 0.0);
+        assert_eq!(qf(1.0, 3.0, 2.0, true, false), f64::INFINITY);
+        assert_eq!(qf(0.3, f64::INFINITY, f64::INFINITY, true, false), 1.0);
+        fn helper(p: f64, df1: f64, df2: f64, lower_tail: bool, log_p: bool) {
+            let actual = qf(p, df1, df2, lower_tail, log_p);
+            let expected = unsafe { c::qf(p, df1, df2, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "qf({}, {}, {}, {}, {})",
+                p,
+                df1,
+                df2,
+                lower_tail,
+                log_p
+            );
+        }
+        // df > 4e5 uses `qchisq`.
+        let dfs = [0.5, 1.0, 2.0, 3.5, 10.0, 1e6, f64::INFINITY];
+        for &df1 in &dfs {
+            for &df2 in &dfs {
+                for &p in &[1e-10, 0.01, 0.5, 0.9, 0.999] {
+                    helper(p, df1, df2, true, false);
+                    helper(p, df1, df2, false, false);
+                    helper(p.ln(), df1, df2, true, true);
+                    helper(p.ln(), df1, df2, false, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qgamma() {
+        assert!(qgamma(0.5, -1.0, 1.0, true, false).is_nan());
+        assert!(qgamma(0.5, 1.0, 0.0, true, false).is_nan());
+        assert!(qgamma(-0.5, 1.0, 1.0, true, false).is_nan());
+        assert_eq!(qgamma(0.5, 0.0, 1.0, true, false), 0.0);
+        assert_eq!(qgamma(0.0, 2.0, 1.0, true, false), 0.0);
+        assert_eq!(qgamma(0.0, 2.0, 1.0, false, false), f64::INFINITY);
+        assert_eq!(qgamma(f64::NEG_INFINITY, 2.0, 1.0, true, true), 0.0);
+        fn helper(p: f64, alpha: f64, scale: f64, lower_tail: bool, log_p: bool) {
+            let expected = unsafe { c::qgamma(p, alpha, scale, lower_tail as i32, log_p as i32) };
+            assert!(
+                abs_diff_eq!(
+                    qgamma(p, alpha, scale, lower_tail, log_p),
+                    expected,
+                    epsilon = 1e-13 * expected.abs()
+                ),
+                "qgamma({}, {}, {}, {}, {})",
+                p,
+                alpha,
+                scale,
+                lower_tail,
+                log_p
+            );
+        }
+        // Tiny shape parameters.
+        for &alpha in &[1e-300, 1e-20, 1e-12, 1e-10, 1e-5, 0.01] {
+            for &p in &[1e-10, 0.01, 0.5, 0.99] {
+                helper(p, alpha, 1.0, true, false);
+                helper(p, alpha, 2.0, false, false);
+            }
+        }
+        // `log_p = true` inputs far in the tail.
+        for &alpha in &[1e-10, 0.1, 1.0, 3.3, 100.0, 1e4] {
+            for &p in &[-1e5, -1000.0, -100.0, -1e-5, -1e-20] {
+                helper(p, alpha, 1.0, true, true);
+                helper(p, alpha, 0.5, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qgeom() {
+        assert!(qgeom(0.5, 0.0, true, false).is_nan());
+        assert!(qgeom(1.5, 0.3, true, false).is_nan());
+        assert_eq!(qgeom(0.5, 1.0, true, false), 0.0);
+        assert_eq!(qgeom(0.0, 0.3, true, false), 0.0);
+        assert_eq!(qgeom(1.0, 0.3, true, false), f64::INFINITY);
+        assert_eq!(qgeom(0.0, 0.3, true, true), f64::INFINITY);
+        // Exact quantiles map back onto their own tail probability.
+        for x in 0..=50 {
+            let p = pgeom(x as f64, 0.2, false, true);
+            assert_eq!(qgeom(p, 0.2, false, true), x as f64);
+        }
+        fn helper(p: f64, prob: f64, lower_tail: bool, log_p: bool) {
+            let actual = qgeom(p, prob, lower_tail, log_p);
+            let expected = unsafe { c::qgeom(p, prob, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "qgeom({}, {}, {}, {})",
+                p, prob, lower_tail, log_p
+            );
+        }
+        for &prob in &[1e-20, 1e-10, 0.01, 0.3, 0.5, 0.99] {
+            for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999, 1.0 - 1e-15] {
+                helper(p, prob, true, false);
+                helper(p, prob, false, false);
+            }
+            for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                helper(p, prob, true, true);
+                helper(p, prob, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qlnorm() {
+        assert!(qlnorm(1.5, 0.0, 1.0, true, false).is_nan());
+        assert!(qlnorm(0.5, 0.0, f64::NAN, true, false).is_nan());
+        assert!(qlnorm(-5.0, 1.0, -1.0, false, true).is_nan());
+        assert!(qlnorm(0.5, 1e10, -1.0, true, false).is_nan());
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0)] {
+            for &lower_tail in &[true, false] {
+                for &p in &[
+                    0.0,
+                    1e-300,
+                    1e-10,
+                    0.01,
+                    0.3,
+                    0.5,
+                    0.9,
+                    0.999,
+                    1.0 - 1e-15,
+                    1.0,
+                ] {
+                    let expected = unsafe { c::qlnorm(p, a, b, lower_tail as i32, 0) };
+                    assert_eq!(
+                        qlnorm(p, a, b, lower_tail, false),
+                        expected,
+                        "qlnorm({}, {}, {}, {}, false)",
+                        p,
+                        a,
+                        b,
+                        lower_tail
+                    );
+                }
+                for &p in &[f64::NEG_INFINITY, -800.0, -5.0, -1.0, -0.1, -1e-20, 0.0] {
+                    let expected = unsafe { c::qlnorm(p, a, b, lower_tail as i32, 1) };
+                    assert_eq!(
+                        qlnorm(p, a, b, lower_tail, true),
+                        expected,
+                        "qlnorm({}, {}, {}, {}, true)",
+                        p,
+                        a,
+                        b,
+                        lower_tail
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qlogis() {
+        assert!(qlogis(1.5, 0.0, 1.0, true, false).is_nan());
+        assert!(qlogis(0.5, 0.0, f64::NAN, true, false).is_nan());
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0)] {
+            for &lower_tail in &[true, false] {
+                for &p in &[
+                    0.0,
+                    1e-300,
+                    1e-10,
+                    0.01,
+                    0.3,
+                    0.5,
+                    0.9,
+                    0.999,
+                    1.0 - 1e-15,
+                    1.0,
+                ] {
+                    let expected = unsafe { c::qlogis(p, a, b, lower_tail as i32, 0) };
+                    assert_eq!(
+                        qlogis(p, a, b, lower_tail, false),
+                        expected,
+                        "qlogis({}, {}, {}, {}, false)",
+                        p,
+                        a,
+                        b,
+                        lower_tail
+                    );
+                }
+                for &p in &[f64::NEG_INFINITY, -800.0, -5.0, -1.0, -0.1, -1e-20, 0.0] {
+                    let expected = unsafe { c::qlogis(p, a, b, lower_tail as i32, 1) };
+                    assert_eq!(
+                        qlogis(p, a, b, lower_tail, true),
+                        expected,
+                        "qlogis({}, {}, {}, {}, true)",
+                        p,
+                        a,
+                        b,
+                        lower_tail
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qnt() {
+        assert!(qnt(0.5, -1.0, 1.0, true, false).is_nan());
+        assert_eq!(qnt(0.0, 3.0, 1.0, true, false), f64::NEG_INFINITY);
+        assert_eq!(qnt(1.0, 3.0, 1.0, true, false), f64::INFINITY);
+        assert_eq!(qnt(0.3, 3.0, 0.0, true, false), qt(0.3, 3.0, true, false));
+        fn helper(p: f64, df: f64, ncp: f64, lower_tail: bool) {
+            let expected = unsafe { c::qnt(p, df, ncp, lower_tail as i32, 0) };
+            assert!(
+                abs_diff_eq!(
+                    qnt(p, df, ncp, lower_tail, false),
+                    expected,
+                    epsilon = 1e-14 * expected.abs()
+                ),
+                "qnt({}, {}, {}, {})",
+                p,
+                df,
+                ncp,
+                lower_tail
+            );
+        }
+        for &df in &[0.5, 1.0, 3.0, 30.0, f64::INFINITY] {
+            for &ncp in &[-1.0, 0.5, 2.0] {
+                for &p in &[0.01, 0.3, 0.5, 0.9, 0.999] {
+                    helper(p, df, ncp, true);
+                    helper(p, df, ncp, false);
+                }
+            }
+        }
+        // Far in the tail `pnt` loses most of its digits to cancellation.
+        helper(1.0 - 1e-10, 20.0, -3.0, true);
+        helper(1e-10, 20.0, -3.0, false);
+    }
+
+    #[test]
+    fn test_qhyper() {
+        assert!(qhyper(0.5, 5.0, 5.0, 11.0, true, false).is_nan());
+        assert!(qhyper(0.5, f64::INFINITY, 5.0, 3.0, true, false).is_nan());
+        assert_eq!(qhyper(0.0, 5.0, 2.0, 3.0, true, false), 1.0);
+        assert_eq!(qhyper(1.0, 5.0, 2.0, 3.0, true, false), 3.0);
+        // Exact quantiles map back onto their own lower tail probability.
+        for x in 0..=20 {
+            let p = phyper(x as f64, 30.0, 40.0, 20.0, true, false);
+            assert_eq!(qhyper(p, 30.0, 40.0, 20.0, true, false), x as f64);
+        }
+        fn helper(p: f64, nr: f64, nb: f64, n: f64, lower_tail: bool, log_p: bool) {
+            let actual = qhyper(p, nr, nb, n, lower_tail, log_p);
+            let expected = unsafe { c::qhyper(p, nr, nb, n, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "qhyper({}, {}, {}, {}, {}, {})",
+                p, nr, nb, n, lower_tail, log_p
+            );
+        }
+        // N >= 1000 works on the log scale.
+        for &(nr, nb, n) in &[
+            (5.0, 5.0, 3.0),
+            (150.0, 150.0, 60.0),
+            (10.0, 1e6, 1e3),
+            (3e3, 5e3, 2e3),
+        ] {
+            for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999] {
+                helper(p, nr, nb, n, true, false);
+                helper(p, nr, nb, n, false, false);
+            }
+            for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                helper(p, nr, nb, n, true, true);
+                helper(p, nr, nb, n, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qnbeta() {
+        assert!(qnbeta(0.5, f64::INFINITY, 3.0, 1.0, true, false).is_nan());
+        assert!(qnbeta(0.5, 2.0, 3.0, -1.0, true, false).is_nan());
+        assert!(qnbeta(1.5, 2.0, 3.0, 1.0, true, false).is_nan());
+        assert_eq!(qnbeta(0.0, 2.0, 3.0, 1.0, true, false), 0.0);
+        assert_eq!(qnbeta(0.0, 2.0, 3.0, 1.0, true, true), 1.0);
+        fn helper(p: f64, a: f64, b: f64, ncp: f64, lower_tail: bool, log_p: bool) {
+            let actual = qnbeta(p, a, b, ncp, lower_tail, log_p);
+            let expected = unsafe { c::qnbeta(p, a, b, ncp, lower_tail as i32, log_p as i32) };
+            // Bisection may branch differently on last-bit differences of
+            // `pnbeta`, which is only accurate to about 1e-9.
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-11 * expected.abs()),
+                "qnbeta({}, {}, {}, {}, {}, {})",
+                p,
+                a,
+                b,
+                ncp,
+                lower_tail,
+                log_p
+            );
+        }
+        for &p in &[1e-8, 0.1, 0.5, 0.95] {
+            for &(a, b) in &[(0.5, 0.5), (2.0, 3.0), (10.0, 1.5)] {
+                for &ncp in &[0.5, 10.0, 200.0] {
+                    helper(p, a, b, ncp, true, false);
+                    helper(p, a, b, ncp, false, false);
+                    helper(p.ln(), a, b, ncp, true, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qnbinom() {
+        assert!(qnbinom(0.5, 3.0, 1.5, true, false).is_nan());
+        assert!(qnbinom(0.5, -1.0, 0.5, true, false).is_nan());
+        assert_eq!(qnbinom(0.5, 0.0, 0.0, true, false), 0.0);
+        assert_eq!(qnbinom(0.5, 0.0, 0.5, true, false), 0.0);
+        assert_eq!(qnbinom(0.5, 3.0, 1.0, true, false), 0.0);
+        assert_eq!(qnbinom(1.0, 3.0, 0.5, true, false), f64::INFINITY);
+        assert_eq!(
+            qnbinom_mu(0.7, f64::INFINITY, 2.0, true, false),
+            qpois(0.7, 2.0, true, false)
+        );
+        // Exact quantiles map back onto their own lower tail probability.
+        for x in 0..=20 {
+            let p = pnbinom(x as f64, 3.5, 0.4, true, false);
+            assert_eq!(qnbinom(p, 3.5, 0.4, true, false), x as f64);
+        }
+        fn helper(p: f64, size: f64, prob: f64, mu: f64, lower_tail: bool, log_p: bool) {
+            let actual = qnbinom(p, size, prob, lower_tail, log_p);
+            let expected = unsafe { c::qnbinom(p, size, prob, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "qnbinom({}, {}, {}, {}, {})",
+                p, size, prob, lower_tail, log_p
+            );
+            let actual = qnbinom_mu(p, size, mu, lower_tail, log_p);
+            let expected = unsafe { c::qnbinom_mu(p, size, mu, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "qnbinom_mu({}, {}, {}, {}, {})",
+                p, size, mu, lower_tail, log_p
+            );
+        }
+        // Large quantiles use the search with larger increments.
+        for &size in &[0.5, 3.0, 47.5, 1e5] {
+            for &(prob, mu) in &[(1e-4, 1e-3), (0.3, 0.5), (0.5, 3.0), (0.99, 1e6)] {
+                for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999] {
+                    helper(p, size, prob, mu, true, false);
+                    helper(p, size, prob, mu, false, false);
+                }
+                for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                    helper(p, size, prob, mu, true, true);
+                    helper(p, size, prob, mu, false, true);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qnchisq() {
+        assert!(qnchisq(0.5, f64::INFINITY, 2.0, true, false).is_nan());
+        assert!(qnchisq(0.5, 3.0, -2.0, true, false).is_nan());
+        assert_eq!(qnchisq(0.0, 3.0, 2.0, true, false), 0.0);
+        assert_eq!(qnchisq(1.0, 3.0, 2.0, true, false), f64::INFINITY);
+        fn helper(p: f64, df: f64, ncp: f64, lower_tail: bool) {
+            let actual = qnchisq(p, df, ncp, lower_tail, false);
+            let expected = unsafe { c::qnchisq(p, df, ncp, lower_tail as i32, 0) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "qnchisq({}, {}, {}, {})",
+                p,
+                df,
+                ncp,
+                lower_tail
+            );
+        }
+        for &df in &[0.0, 0.5, 3.0, 20.0, 500.0] {
+            for &ncp in &[0.5, 10.0, 80.0, 300.0] {
+                for &p in &[1e-10, 0.01, 0.5, 0.9, 0.999] {
+                    helper(p, df, ncp, true);
+                    helper(p, df, ncp, false);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qnf() {
+        assert!(qnf(0.5, 0.0, 2.0, 1.0, true, false).is_nan());
+        assert!(qnf(0.5, f64::INFINITY, f64::INFINITY, 1.0, true, false).is_nan());
+        assert_eq!(qnf(0.0, 3.0, 2.0, 1.0, true, false), 0.0);
+        assert_eq!(qnf(1.0, 3.0, 2.0, 1.0, true, false), f64::INFINITY);
+        fn helper(p: f64, df1: f64, df2: f64, ncp: f64, lower_tail: bool) {
+            let actual = qnf(p, df1, df2, ncp, lower_tail, false);
+            let expected = unsafe { c::qnf(p, df1, df2, ncp, lower_tail as i32, 0) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-11 * expected.abs()),
+                "qnf({}, {}, {}, {}, {})",
+                p,
+                df1,
+                df2,
+                ncp,
+                lower_tail
+            );
+        }
+        // df2 > 1e8 uses `qnchisq`.
+        let dfs = [0.5, 2.0, 3.5, 10.0, 1e9, f64::INFINITY];
+        for &df1 in &[0.5, 2.0, 3.5, 10.0] {
+            for &df2 in &dfs {
+                for &ncp in &[0.0, 1.0, 30.0] {
+                    for &p in &[0.01, 0.5, 0.9] {
+                        helper(p, df1, df2, ncp, true);
+                        helper(p, df1, df2, ncp, false);
+                    }
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_qnorm() {
         assert_eq!(qnorm(0.0, 0.5, 1.0, true, false), unsafe {
@@ -426,11 +3009,1004 @@ mod test_math {
         assert_eq!(qnorm(0.4, 0.5, 1.0, false, false), unsafe {
             c::qnorm5(0.4, 0.5, 1.0, 0, 0)
         });
+        assert_eq!(qnorm(0.975, 0.0, 1.0, true, false), unsafe {
+            c::qnorm5(0.975, 0.0, 1.0, 1, 0)
+        });
+        assert_eq!(qnorm(0.025, 0.0, 1.0, false, false), unsafe {
+            c::qnorm5(0.025, 0.0, 1.0, 0, 0)
+        });
         assert!(abs_diff_eq!(
             qnorm(-2.3, 0.5, 1.0, false, true),
             unsafe { c::qnorm5(-2.3, 0.5, 1.0, 0, 1) },
             epsilon = 1e-15
         ));
+        assert!(qnorm(0.3, 0.0, -1.0, true, false).is_nan());
+        assert!(qnorm(-5.0, 1.0, -1.0, false, true).is_nan());
+    }
+
+    /// Small linear congruential generator, only used to drive the random
+    /// variate generators in these tests.
+    struct Lcg(u64);
+
+    impl Rng for Lcg {
+        fn unif_rand(&mut self) -> f64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+        }
+    }
+
+    thread_local! {
+        static C_RNG: std::cell::RefCell<Box<dyn Rng>> = std::cell::RefCell::new(Box::new(Lcg(0)));
+    }
+
+    /// The uniform generator called by the C random variate generators.
+    ///
+    /// It draws from a per-thread generator, an `Lcg` by default, so that a
+    /// test can feed the C and Rust generators the same stream.
+    #[no_mangle]
+    extern "C" fn unif_rand() -> f64 {
+        C_RNG.with(|rng| rng.borrow_mut().unif_rand())
+    }
+
+    /// Seed the per-thread generator behind the C `unif_rand`.
+    fn set_c_seed(seed: u64) {
+        set_c_rng(Lcg(seed));
+    }
+
+    /// Replace the per-thread generator behind the C `unif_rand`.
+    fn set_c_rng<R: Rng + 'static>(rng: R) {
+        C_RNG.with(|c_rng| *c_rng.borrow_mut() = Box::new(rng));
+    }
+
+    #[test]
+    fn test_qpois() {
+        assert!(qpois(0.5, -1.0, true, false).is_nan());
+        assert!(qpois(0.5, f64::INFINITY, true, false).is_nan());
+        assert!(qpois(1.5, 3.0, true, false).is_nan());
+        assert_eq!(qpois(0.5, 0.0, true, false), 0.0);
+        assert_eq!(qpois(0.0, 3.0, true, false), 0.0);
+        assert_eq!(qpois(1.0, 3.0, true, false), f64::INFINITY);
+        assert_eq!(qpois(0.0, 3.0, true, true), f64::INFINITY);
+        // Exact quantiles map back onto their own lower tail probability.
+        for x in 0..=20 {
+            let p = ppois(x as f64, 4.5, true, false);
+            assert_eq!(qpois(p, 4.5, true, false), x as f64);
+        }
+        fn helper(p: f64, lambda: f64, lower_tail: bool, log_p: bool) {
+            let actual = qpois(p, lambda, lower_tail, log_p);
+            let expected = unsafe { c::qpois(p, lambda, lower_tail as i32, log_p as i32) };
+            assert_eq!(
+                actual, expected,
+                "qpois({}, {}, {}, {})",
+                p, lambda, lower_tail, log_p
+            );
+        }
+        // lambda >= 1e5 uses the search with larger increments.
+        for &lambda in &[1e-300, 1e-10, 0.5, 3.0, 47.5, 1e5, 1e10, 1e15] {
+            for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999, 1.0 - 1e-15] {
+                helper(p, lambda, true, false);
+                helper(p, lambda, false, false);
+            }
+            for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                helper(p, lambda, true, true);
+                helper(p, lambda, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qsignrank() {
+        assert!(qsignrank(1.5, 3.0, true, false).is_nan());
+        assert!(qsignrank(0.5, 0.0, true, false).is_nan());
+        assert_eq!(qsignrank(0.0, 3.0, true, false), 0.0);
+        assert_eq!(qsignrank(0.0, 3.0, false, false), 6.0);
+        take_warnings();
+        assert!(qsignrank(0.5, 1e5, true, false).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::Range,
+                function: "qsignrank"
+            }]
+        );
+        // Exact quantiles map back onto their own lower tail probability.
+        for q in 0..=28 {
+            let p = psignrank(q as f64, 7.0, true, false);
+            assert_eq!(qsignrank(p, 7.0, true, false), q as f64);
+        }
+        for &n in &[1.0, 2.0, 3.0, 10.0, 50.0, 300.0] {
+            for &lower_tail in &[true, false] {
+                for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999, 1.0 - 1e-15] {
+                    let expected = unsafe { c::qsignrank(p, n, lower_tail as i32, 0) };
+                    assert_eq!(qsignrank(p, n, lower_tail, false), expected);
+                }
+                for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                    let expected = unsafe { c::qsignrank(p, n, lower_tail as i32, 1) };
+                    assert_eq!(qsignrank(p, n, lower_tail, true), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qt() {
+        assert!(qt(0.5, -1.0, true, false).is_nan());
+        assert_eq!(qt(0.0, 3.0, true, false), f64::NEG_INFINITY);
+        assert_eq!(qt(0.0, 3.0, false, false), f64::INFINITY);
+        assert_eq!(qt(0.5, 3.0, true, false), 0.0);
+        fn helper(p: f64, ndf: f64, lower_tail: bool, log_p: bool) {
+            let actual = qt(p, ndf, lower_tail, log_p);
+            let expected = unsafe { c::qt(p, ndf, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-14 * expected.abs()),
+                "qt({}, {}, {}, {})",
+                p,
+                ndf,
+                lower_tail,
+                log_p
+            );
+        }
+        // Includes the special cases for df < 1, df ~= 1, df ~= 2 and huge df.
+        for &ndf in &[0.1, 0.5, 1.0, 1.1, 2.0, 2.5, 7.0, 30.0, 1e5, 1e21] {
+            for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.9, 0.999] {
+                helper(p, ndf, true, false);
+                helper(p, ndf, false, false);
+            }
+            for &p in &[-1e5, -800.0, -5.0, -0.1, -1e-20] {
+                helper(p, ndf, true, true);
+                helper(p, ndf, false, true);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qtukey() {
+        assert!(qtukey(f64::NAN, 1.0, 3.0, 10.0, true, false).is_nan());
+        assert!(qtukey(0.5, 1.0, 1.0, 10.0, true, false).is_nan());
+        assert!(qtukey(1.5, 1.0, 3.0, 10.0, true, false).is_nan());
+        assert_eq!(qtukey(0.0, 1.0, 3.0, 10.0, true, false), 0.0);
+        assert_eq!(qtukey(1.0, 1.0, 3.0, 10.0, true, false), f64::INFINITY);
+        fn helper(p: f64, rr: f64, cc: f64, df: f64, lower_tail: bool, log_p: bool) {
+            let actual = qtukey(p, rr, cc, df, lower_tail, log_p);
+            let expected = unsafe { c::qtukey(p, rr, cc, df, lower_tail as i32, log_p as i32) };
+            assert!(
+                actual == expected
+                    || abs_diff_eq!(actual, expected, epsilon = 1e-13 * expected.abs()),
+                "qtukey({}, {}, {}, {}, {}, {})",
+                p,
+                rr,
+                cc,
+                df,
+                lower_tail,
+                log_p
+            );
+        }
+        for &df in &[2.0, 5.0, 99.0, 500.0, 1e5] {
+            for &(rr, cc) in &[(1.0, 2.0), (1.0, 3.0), (2.0, 5.0), (1.0, 10.0)] {
+                for &p in &[0.01, 0.5, 0.9, 0.95, 0.99] {
+                    helper(p, rr, cc, df, true, false);
+                    helper(p, rr, cc, df, false, false);
+                    helper(p.ln(), rr, cc, df, false, true);
+                }
+            }
+        }
+        assert_eq!(take_warnings(), vec![]);
+        // The secant iteration fails far in the upper tail.
+        assert!(qtukey(0.999999, 3.0, 50.0, 2.0, true, false).is_nan());
+        assert!(unsafe { c::qtukey(0.999999, 3.0, 50.0, 2.0, 1, 0) }.is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![MlWarning {
+                kind: MlWarningKind::NoConv,
+                function: "qtukey"
+            }]
+        );
+        assert_eq!(take_warnings(), vec![]);
+    }
+
+    #[test]
+    fn test_qunif() {
+        assert!(qunif(1.5, 0.0, 1.0, true, false).is_nan());
+        assert!(qunif(0.5, 1.0, 0.0, true, false).is_nan());
+        assert!(qunif(0.5, f64::NEG_INFINITY, 0.0, true, false).is_nan());
+        assert_eq!(qunif(0.3, 2.0, 2.0, true, false), 2.0);
+        assert_eq!(qunif(0.25, 0.0, 2.0, false, false), 1.5);
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 1e10)] {
+            for &lower_tail in &[true, false] {
+                for &p in &[0.0, 1e-300, 1e-10, 0.3, 0.5, 0.999, 1.0] {
+                    let expected = unsafe { c::qunif(p, a, b, lower_tail as i32, 0) };
+                    assert_eq!(qunif(p, a, b, lower_tail, false), expected);
+                }
+                for &p in &[f64::NEG_INFINITY, -800.0, -1.0, -1e-20, 0.0] {
+                    let expected = unsafe { c::qunif(p, a, b, lower_tail as i32, 1) };
+                    assert_eq!(qunif(p, a, b, lower_tail, true), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qweibull() {
+        assert!(qweibull(0.5, 0.0, 1.0, true, false).is_nan());
+        assert!(qweibull(0.5, 1.0, -1.0, true, false).is_nan());
+        assert!(qweibull(1.5, 1.0, 1.0, true, false).is_nan());
+        assert_eq!(qweibull(0.0, 2.0, 1.0, true, false), 0.0);
+        assert_eq!(
+            qweibull(f64::NEG_INFINITY, 2.0, 1.0, false, true),
+            f64::INFINITY
+        );
+        assert_eq!(qweibull(-1e4, 2.0, 1.0, false, true), 100.0);
+        for &shape in &[0.3, 1.0, 2.5, 10.0] {
+            for &scale in &[1e-3, 1.0, 7.0] {
+                for &lower_tail in &[true, false] {
+                    for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999, 1.0 - 1e-15] {
+                        let expected =
+                            unsafe { c::qweibull(p, shape, scale, lower_tail as i32, 0) };
+                        assert_eq!(qweibull(p, shape, scale, lower_tail, false), expected);
+                    }
+                    for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                        let expected =
+                            unsafe { c::qweibull(p, shape, scale, lower_tail as i32, 1) };
+                        assert_eq!(qweibull(p, shape, scale, lower_tail, true), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_qwilcox() {
+        let mut counts = WilcoxCounts::new();
+        assert!(qwilcox(1.5, 2.0, 3.0, true, false, &mut counts)
+            .unwrap()
+            .is_nan());
+        assert!(qwilcox(0.5, 0.0, 3.0, true, false, &mut counts)
+            .unwrap()
+            .is_nan());
+        assert_eq!(qwilcox(0.0, 2.0, 3.0, true, false, &mut counts), Ok(0.0));
+        assert_eq!(qwilcox(0.0, 2.0, 3.0, false, false, &mut counts), Ok(6.0));
+        // Exact quantiles map back onto their own lower tail probability.
+        for q in 0..=35 {
+            let p = pwilcox(q as f64, 5.0, 7.0, true, false, &mut counts).unwrap();
+            assert_eq!(qwilcox(p, 5.0, 7.0, true, false, &mut counts), Ok(q as f64));
+        }
+        for &(m, n) in &[
+            (1.0, 1.0),
+            (2.0, 3.0),
+            (7.0, 5.0),
+            (10.0, 10.0),
+            (30.0, 40.0),
+        ] {
+            for &lower_tail in &[true, false] {
+                for &p in &[1e-300, 1e-10, 0.01, 0.3, 0.5, 0.9, 0.999, 1.0 - 1e-15] {
+                    let expected = unsafe { c::qwilcox(p, m, n, lower_tail as i32, 0) };
+                    assert_eq!(
+                        qwilcox(p, m, n, lower_tail, false, &mut counts),
+                        Ok(expected)
+                    );
+                }
+                for &p in &[-800.0, -5.0, -0.1, -1e-20] {
+                    let expected = unsafe { c::qwilcox(p, m, n, lower_tail as i32, 1) };
+                    assert_eq!(
+                        qwilcox(p, m, n, lower_tail, true, &mut counts),
+                        Ok(expected)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_seed() {
+        // set.seed(1); .Random.seed[1:3]
+        let mut rng = RRng::new(1, RngKind::MersenneTwister);
+        let seed = rng.random_seed();
+        assert_eq!(seed.len(), 626);
+        assert_eq!(&seed[..3], &[10403, 624, -169270483]);
+        // runif(1) moves mti
+        rng.unif_rand();
+        assert_eq!(rng.random_seed()[1], 1);
+
+        for &kind in &[
+            RngKind::WichmannHill,
+            RngKind::MarsagliaMulticarry,
+            RngKind::SuperDuper,
+            RngKind::MersenneTwister,
+            RngKind::KnuthTaocp,
+            RngKind::KnuthTaocp2002,
+            RngKind::LecuyerCmrg,
+        ] {
+            for &norm_kind in &[
+                NormKind::BuggyKindermanRamage,
+                NormKind::AhrensDieter,
+                NormKind::BoxMuller,
+                NormKind::Inversion,
+                NormKind::KindermanRamage,
+            ] {
+                for &sample_kind in &[SampleKind::Rounding, SampleKind::Rejection] {
+                    let mut rng = RRng::new(42, kind);
+                    rng.set_norm_kind(norm_kind);
+                    rng.set_sample_kind(sample_kind);
+                    // An odd number of normals leaves a Box-Muller normal.
+                    for _ in 0..777 {
+                        rng.norm_rand();
+                    }
+                    let seed = rng.random_seed();
+                    let mut restored = RRng::from_random_seed(&seed).unwrap();
+                    assert_eq!(restored, rng, "{:?}", &seed[..1]);
+                    assert_eq!(restored.random_seed(), seed);
+                    for _ in 0..1000 {
+                        assert_eq!(restored.norm_rand(), rng.norm_rand());
+                        assert_eq!(restored.unif_index(1e9), rng.unif_index(1e9));
+                        assert_eq!(restored.unif_rand(), rng.unif_rand());
+                    }
+                }
+            }
+        }
+
+        let rng = RRng::new(3, RngKind::MersenneTwister);
+        let seed = rng.random_seed();
+        // As R, the extra elements are ignored.
+        let mut longer = seed.clone();
+        longer.extend(&[1, 2, 3]);
+        assert_eq!(RRng::from_random_seed(&longer).unwrap(), rng);
+        // 403 is the code used before R 3.6.0, with "Rounding".
+        let mut old = seed.clone();
+        old[0] = 403;
+        let old = RRng::from_random_seed(&old).unwrap();
+        assert_eq!(old.sample_kind(), SampleKind::Rounding);
+        assert_eq!(old.norm_kind(), NormKind::Inversion);
+        assert_eq!(old.kind(), RngKind::MersenneTwister);
+
+        assert_eq!(RRng::from_random_seed(&[]), Err(RandomSeedError::Empty));
+        // User-supplied kinds
+        for &code in &[10405, 10303, -1, 20403, 10408] {
+            let mut invalid = seed.clone();
+            invalid[0] = code;
+            assert_eq!(
+                RRng::from_random_seed(&invalid),
+                Err(RandomSeedError::InvalidKind(code))
+            );
+        }
+        assert_eq!(
+            RRng::from_random_seed(&seed[..625]),
+            Err(RandomSeedError::WrongLength {
+                kind: RngKind::MersenneTwister,
+                len: 625
+            })
+        );
+        let mut zero = vec![0; 626];
+        zero[0] = 10403;
+        assert_eq!(
+            RRng::from_random_seed(&zero),
+            Err(RandomSeedError::InvalidSeeds(RngKind::MersenneTwister))
+        );
+        assert_eq!(
+            RRng::from_random_seed(&[10407, 1, 2, 3, 4, 5, -1]),
+            Err(RandomSeedError::InvalidSeeds(RngKind::LecuyerCmrg))
+        );
+        // Wichmann-Hill seeds are taken modulo the moduli.
+        let mut wh = RRng::from_random_seed(&[10400, 30269, 30308, 2]).unwrap();
+        assert_eq!(wh.random_seed(), vec![10400, 1, 1, 2]);
+        let expected = 171.0 / 30269.0 + 172.0 / 30307.0 + 340.0 / 30323.0;
+        assert_eq!(wh.unif_rand(), expected);
+    }
+
+    #[test]
+    fn test_rbeta() {
+        let mut rng = Lcg(42);
+        assert!(rbeta(-1.0, 2.0, &mut rng).is_nan());
+        let x = rbeta(0.0, 0.0, &mut rng);
+        assert!(x == 0.0 || x == 1.0);
+        assert_eq!(rbeta(0.0, 2.0, &mut rng), 0.0);
+        assert_eq!(rbeta(2.0, 0.0, &mut rng), 1.0);
+        assert_eq!(rbeta(f64::INFINITY, 2.0, &mut rng), 1.0);
+        assert_eq!(rbeta(2.0, f64::INFINITY, &mut rng), 0.0);
+        // Both of Cheng's algorithms BB and BC, with the same uniforms as C.
+        for &(a, b) in &[(0.2, 0.7), (0.5, 3.0), (2.0, 0.3), (1.5, 2.5), (30.0, 0.9)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rbeta(a, b) };
+                assert_eq!(rbeta(a, b, &mut rng), expected, "rbeta({}, {})", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rbinom() {
+        let mut rng = Lcg(42);
+        assert!(rbinom(5.5, 0.5, &mut rng).is_nan());
+        assert!(rbinom(-1.0, 0.5, &mut rng).is_nan());
+        assert!(rbinom(5.0, 1.5, &mut rng).is_nan());
+        assert_eq!(rbinom(0.0, 0.5, &mut rng), 0.0);
+        assert_eq!(rbinom(5.0, 0.0, &mut rng), 0.0);
+        assert_eq!(rbinom(5.0, 1.0, &mut rng), 5.0);
+        // Inversion for n*p < 30 and BTPE otherwise, with the same uniforms
+        // as C; the huge n goes through `qbinom`.
+        let cases = [
+            (10.0, 0.3),
+            (100.0, 0.9),
+            (1000.0, 0.5),
+            (1e6, 0.2),
+            (3e9, 0.4),
+        ];
+        for &(n, p) in &cases {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rbinom(n, p) };
+                assert_eq!(rbinom(n, p, &mut rng), expected, "rbinom({}, {})", n, p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rcauchy() {
+        let mut rng = Lcg(42);
+        assert!(rcauchy(f64::NAN, 1.0, &mut rng).is_nan());
+        assert!(rcauchy(0.0, f64::INFINITY, &mut rng).is_nan());
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0), (1.0, 0.0)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rcauchy(a, b) };
+                assert_eq!(rcauchy(a, b, &mut rng), expected, "rcauchy({}, {})", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rchisq() {
+        let mut rng = Lcg(42);
+        assert!(rchisq(-1.0, &mut rng).is_nan());
+        assert!(rchisq(f64::INFINITY, &mut rng).is_nan());
+        assert_eq!(rchisq(0.0, &mut rng), 0.0);
+        for &df in &[1e-3, 0.5, 1.0, 2.0, 3.0, 20.0, 1e3] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rchisq(df) };
+                assert_eq!(rchisq(df, &mut rng), expected, "rchisq({})", df);
+            }
+            set_c_rng(RRng::new(1, RngKind::MersenneTwister));
+            let mut rng = RRng::new(1, RngKind::MersenneTwister);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rchisq(df) };
+                assert_eq!(rchisq(df, &mut rng), expected, "rchisq({})", df);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rexp() {
+        let mut rng = Lcg(42);
+        assert!(rexp(-1.0, &mut rng).is_nan());
+        assert!(rexp(f64::INFINITY, &mut rng).is_nan());
+        assert_eq!(rexp(0.0, &mut rng), 0.0);
+        for &scale in &[1e-5, 0.5, 1.0, 3.0, 1e10] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rexp(scale) };
+                assert_eq!(rexp(scale, &mut rng), expected, "rexp({})", scale);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rgamma() {
+        let mut rng = Lcg(42);
+        assert!(rgamma(f64::NAN, 1.0, &mut rng).is_nan());
+        assert!(rgamma(1.0, f64::NAN, &mut rng).is_nan());
+        assert!(rgamma(-1.0, 1.0, &mut rng).is_nan());
+        assert!(rgamma(1.0, -1.0, &mut rng).is_nan());
+        assert_eq!(rgamma(0.0, 1.0, &mut rng), 0.0);
+        assert_eq!(rgamma(1.0, 0.0, &mut rng), 0.0);
+        assert_eq!(rgamma(f64::INFINITY, 1.0, &mut rng), f64::INFINITY);
+        assert_eq!(rgamma(1.0, f64::INFINITY, &mut rng), f64::INFINITY);
+        // GS for a < 1, and GD with all three approximations for a >= 1
+        for &(a, scale) in &[
+            (1e-3, 1.0),
+            (0.5, 2.0),
+            (0.999, 1.0),
+            (1.0, 1.0),
+            (2.0, 0.5),
+            (3.686, 1.0),
+            (5.0, 3.0),
+            (13.022, 1.0),
+            (100.0, 1e-3),
+        ] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rgamma(a, scale) };
+                assert_eq!(
+                    rgamma(a, scale, &mut rng),
+                    expected,
+                    "rgamma({}, {})",
+                    a,
+                    scale
+                );
+            }
+            // Seeded like R, including the normal deviates of GD.
+            set_c_rng(RRng::new(1, RngKind::MersenneTwister));
+            let mut rng = RRng::new(1, RngKind::MersenneTwister);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rgamma(a, scale) };
+                assert_eq!(
+                    rgamma(a, scale, &mut rng),
+                    expected,
+                    "rgamma({}, {})",
+                    a,
+                    scale
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rgeom() {
+        let mut rng = Lcg(42);
+        assert!(rgeom(0.0, &mut rng).is_nan());
+        assert!(rgeom(1.5, &mut rng).is_nan());
+        assert_eq!(rgeom(1.0, &mut rng), 0.0);
+        for &p in &[1e-10, 0.01, 0.3, 0.5, 0.99] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rgeom(p) };
+                assert_eq!(rgeom(p, &mut rng), expected, "rgeom({})", p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rhyper() {
+        let mut rng = Lcg(42);
+        assert!(rhyper(5.0, 5.0, 11.0, &mut rng).is_nan());
+        assert!(rhyper(f64::INFINITY, 5.0, 3.0, &mut rng).is_nan());
+        assert_eq!(rhyper(5.0, 5.0, 10.0, &mut rng), 5.0);
+        assert_eq!(rhyper(5.0, 0.0, 3.0, &mut rng), 3.0);
+        // Inversion (HIN) for small means and H2PE otherwise, with the same
+        // uniforms as C; the huge urn goes through `qhyper`.
+        let cases = [
+            (5.0, 5.0, 3.0),
+            (20.0, 10.0, 25.0),
+            (150.0, 150.0, 60.0),
+            (1e4, 3e4, 1e4),
+            (3e4, 1e4, 35e3),
+            (3e9, 1e9, 1.0),
+            (3e9, 1e9, 5.0),
+        ];
+        for &(nn1, nn2, kk) in &cases {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rhyper(nn1, nn2, kk) };
+                assert_eq!(
+                    rhyper(nn1, nn2, kk, &mut rng),
+                    expected,
+                    "rhyper({}, {}, {})",
+                    nn1,
+                    nn2,
+                    kk
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rlnorm() {
+        let mut rng = Lcg(42);
+        assert!(rlnorm(f64::NAN, 1.0, &mut rng).is_nan());
+        assert!(rlnorm(0.0, f64::INFINITY, &mut rng).is_nan());
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0), (1.0, 0.0)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rlnorm(a, b) };
+                assert_eq!(rlnorm(a, b, &mut rng), expected, "rlnorm({}, {})", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rlogis() {
+        let mut rng = Lcg(42);
+        assert!(rlogis(f64::NAN, 1.0, &mut rng).is_nan());
+        assert!(rlogis(0.0, f64::INFINITY, &mut rng).is_nan());
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0), (1.0, 0.0)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rlogis(a, b) };
+                assert_eq!(rlogis(a, b, &mut rng), expected, "rlogis({}, {})", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rnbinom() {
+        let mut rng = Lcg(42);
+        assert!(rnbinom(0.0, 0.5, &mut rng).is_nan());
+        assert!(rnbinom(3.0, 0.0, &mut rng).is_nan());
+        assert!(rnbinom_mu(3.0, -1.0, &mut rng).is_nan());
+        assert_eq!(rnbinom(3.0, 1.0, &mut rng), 0.0);
+        assert_eq!(rnbinom_mu(3.0, 0.0, &mut rng), 0.0);
+        // The gamma-Poisson mixture, with the same uniforms as C.
+        for &(size, prob, mu) in &[(0.5, 0.3, 2.0), (3.0, 0.9, 50.0), (1e3, 0.01, 1e-3)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rnbinom(size, prob) };
+                assert_eq!(
+                    rnbinom(size, prob, &mut rng),
+                    expected,
+                    "rnbinom({}, {})",
+                    size,
+                    prob
+                );
+                let expected = unsafe { c::rnbinom_mu(size, mu) };
+                assert_eq!(
+                    rnbinom_mu(size, mu, &mut rng),
+                    expected,
+                    "rnbinom_mu({}, {})",
+                    size,
+                    mu
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rng_stream() {
+        fn assert_send<T: Send + 'static>(_: &T) {}
+
+        /// `s` advanced by 2^e steps of the recurrence
+        /// `x[n] = (c[0] * x[n-3] + c[1] * x[n-2] + c[2] * x[n-1]) mod m`,
+        /// where the matrix of one step is squared `e` times.
+        fn jump(s: &[i32], c: [u128; 3], m: u128, e: u32) -> Vec<i32> {
+            let mul = |a: &[[u128; 3]; 3], b: &[[u128; 3]; 3]| {
+                let mut c = [[0u128; 3]; 3];
+                for i in 0..3 {
+                    for j in 0..3 {
+                        c[i][j] = (0..3).map(|k| a[i][k] * b[k][j]).sum::<u128>() % m;
+                    }
+                }
+                c
+            };
+            let mut a = [[0, 1, 0], [0, 0, 1], c];
+            for _ in 0..e {
+                a = mul(&a, &a);
+            }
+            let s: Vec<u128> = s.iter().map(|&x| x as u32 as u128).collect();
+            (0..3)
+                .map(|i| ((0..3).map(|k| a[i][k] * s[k]).sum::<u128>() % m) as u32 as i32)
+                .collect()
+        }
+        let (m1, m2) = (4294967087u128, 4294944443u128);
+        let c1 = [m1 - 810728, 1403580, 0];
+        let c2 = [m2 - 1370589, 0, 527612];
+
+        let mut rng = RRng::new(123, RngKind::LecuyerCmrg);
+        rng.set_norm_kind(NormKind::BoxMuller);
+        rng.set_sample_kind(SampleKind::Rounding);
+        for _ in 0..11 {
+            rng.norm_rand();
+        }
+        let seed = rng.random_seed();
+        for &(e, ref next) in &[
+            (76, rng.next_rng_substream().unwrap()),
+            (127, rng.next_rng_stream().unwrap()),
+        ] {
+            let mut expected = vec![seed[0]];
+            expected.extend(jump(&seed[1..4], c1, m1, e));
+            expected.extend(jump(&seed[4..7], c2, m2, e));
+            // The pending Box-Muller normal is dropped.
+            assert_eq!(next.random_seed(), expected, "2^{}", e);
+            assert_eq!(next.kind(), RngKind::LecuyerCmrg);
+            assert_eq!(next.norm_kind(), NormKind::BoxMuller);
+            assert_eq!(next.sample_kind(), SampleKind::Rounding);
+            assert_send(next);
+        }
+        // The first step of the recurrence is the generator itself.
+        let mut expected = vec![seed[0]];
+        expected.extend(jump(&seed[1..4], c1, m1, 0));
+        expected.extend(jump(&seed[4..7], c2, m2, 0));
+        rng.unif_rand();
+        assert_eq!(&rng.random_seed()[..7], &expected[..]);
+
+        // The streams are reproducible, also when used from other threads.
+        let streams = |n: usize| {
+            let mut stream = RRng::new(1, RngKind::LecuyerCmrg);
+            (0..n)
+                .map(|_| {
+                    stream = stream.next_rng_stream().unwrap();
+                    stream.clone()
+                })
+                .collect::<Vec<RRng>>()
+        };
+        let handles: Vec<_> = streams(4)
+            .into_iter()
+            .map(|mut rng| {
+                std::thread::spawn(move || {
+                    (0..100)
+                        .map(|_| rchisq(3.0, &mut rng))
+                        .collect::<Vec<f64>>()
+                })
+            })
+            .collect();
+        let threaded: Vec<Vec<f64>> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        for (mut rng, x) in streams(4).into_iter().zip(&threaded) {
+            let expected: Vec<f64> = (0..100).map(|_| rchisq(3.0, &mut rng)).collect();
+            assert_eq!(&expected, x);
+        }
+        assert_ne!(threaded[0], threaded[1]);
+
+        let rng = RRng::new(1, RngKind::MersenneTwister);
+        assert!(rng.next_rng_stream().is_none());
+        assert!(rng.next_rng_substream().is_none());
+    }
+
+    #[test]
+    fn test_rnorm() {
+        let mut rng = Lcg(42);
+        assert!(rnorm(f64::NAN, 1.0, &mut rng).is_nan());
+        assert!(rnorm(0.0, f64::INFINITY, &mut rng).is_nan());
+        assert!(rnorm(0.0, -1.0, &mut rng).is_nan());
+        assert_eq!(rnorm(f64::INFINITY, 1.0, &mut rng), f64::INFINITY);
+        for &(mu, sigma) in &[(0.0, 1.0), (-3.0, 0.5), (10.0, 20.0), (1.0, 0.0)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rnorm(mu, sigma) };
+                assert_eq!(
+                    rnorm(mu, sigma, &mut rng),
+                    expected,
+                    "rnorm({}, {})",
+                    mu,
+                    sigma
+                );
+            }
+        }
+        // set.seed(1); rnorm(5)
+        let mut rng = RRng::new(1, RngKind::MersenneTwister);
+        for &e in &[-0.6264538, 0.1836433, -0.8356286, 1.5952808, 0.3295078] {
+            let x = rnorm(0.0, 1.0, &mut rng);
+            assert!(abs_diff_eq!(x, e, epsilon = 5e-8), "{} != {}", x, e);
+        }
+    }
+
+    #[test]
+    fn test_norm_kinds() {
+        assert_eq!(NormKind::default(), NormKind::Inversion);
+        // The numeric codes of N01type in R_ext/Random.h.
+        for &(kind, code) in &[
+            (NormKind::BuggyKindermanRamage, 0),
+            (NormKind::AhrensDieter, 1),
+            (NormKind::BoxMuller, 2),
+            (NormKind::Inversion, 4),
+            (NormKind::KindermanRamage, 5),
+        ] {
+            let mut rng = RRng::new(1, RngKind::MersenneTwister);
+            assert_eq!(rng.norm_kind(), NormKind::Inversion);
+            rng.set_norm_kind(kind);
+            assert_eq!(rng.norm_kind(), kind);
+            set_c_rng(RRng::new(1, RngKind::MersenneTwister));
+            unsafe { c::N01_kind_kinds = code };
+            // An even number of draws, which leaves no Box-Muller normal
+            // behind for the next kind.
+            for _ in 0..10000 {
+                let expected = unsafe { c::norm_rand_kinds() };
+                assert_eq!(rng.norm_rand(), expected, "{}", kind.name());
+            }
+            // set.seed() zaps the Box-Muller history, and the uniform
+            // stream is shared with the normal kinds.
+            rng.norm_rand();
+            rng.set_seed(1);
+            let mut other = RRng::new(1, RngKind::MersenneTwister);
+            other.set_norm_kind(kind);
+            assert_eq!(rng, other, "{}", kind.name());
+            assert_eq!(rng.unif_rand(), other.unif_rand());
+        }
+    }
+
+    #[test]
+    fn test_rpois() {
+        let mut rng = Lcg(42);
+        assert!(rpois(-1.0, &mut rng).is_nan());
+        assert!(rpois(f64::INFINITY, &mut rng).is_nan());
+        assert_eq!(rpois(0.0, &mut rng), 0.0);
+        // Table lookup for mu < 10 and the normal approximation otherwise,
+        // with the same uniforms as C.
+        for &mu in &[1e-300, 1e-10, 0.5, 3.0, 9.99, 10.0, 47.5, 1e5, 1e10, 1e15] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rpois(mu) };
+                assert_eq!(rpois(mu, &mut rng), expected, "rpois({})", mu);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rrng() {
+        assert_eq!(RngKind::default(), RngKind::MersenneTwister);
+        // set.seed(seed); runif(5)
+        for &(seed, expected) in &[
+            (1, [0.2655087, 0.3721239, 0.5728534, 0.9082078, 0.2016819]),
+            (42, [0.9148060, 0.9370754, 0.2861395, 0.8304476, 0.6417455]),
+            (123, [0.2875775, 0.7883051, 0.4089769, 0.8830174, 0.9404673]),
+        ] {
+            let mut rng = RRng::new(seed, RngKind::MersenneTwister);
+            for &e in &expected {
+                let u = runif(0.0, 1.0, &mut rng);
+                assert!(abs_diff_eq!(u, e, epsilon = 5e-8), "{} != {}", u, e);
+            }
+        }
+        // Knuth's check values: ran_start(310952), and a[0] after 2010 calls
+        // of ran_array(a, 1009). The seed is scrambled to 310952 by set.seed.
+        for &(kind, expected) in &[
+            (RngKind::KnuthTaocp2002, 995235265.0),
+            (RngKind::KnuthTaocp, 461390032.0),
+        ] {
+            let mut rng = RRng::new(-980811078, kind);
+            for _ in 0..2008 * 100 {
+                rng.unif_rand();
+            }
+            assert_eq!(rng.unif_rand(), expected * 9.31322574615479e-10);
+        }
+        for &kind in &[
+            RngKind::WichmannHill,
+            RngKind::MarsagliaMulticarry,
+            RngKind::SuperDuper,
+            RngKind::MersenneTwister,
+            RngKind::KnuthTaocp,
+            RngKind::KnuthTaocp2002,
+            RngKind::LecuyerCmrg,
+        ] {
+            let mut rng = RRng::new(2024, kind);
+            assert_eq!(rng.kind(), kind);
+            let first: Vec<f64> = (0..1000).map(|_| rng.unif_rand()).collect();
+            assert!(first.iter().all(|&u| 0.0 < u && u < 1.0), "{}", kind.name());
+            let mean = first.iter().sum::<f64>() / 1000.0;
+            assert!((mean - 0.5).abs() < 0.05, "{}: {}", kind.name(), mean);
+            // set.seed() restarts the stream.
+            rng.set_seed(2024);
+            let again: Vec<f64> = (0..1000).map(|_| rng.unif_rand()).collect();
+            assert_eq!(first, again, "{}", kind.name());
+            // RNGkind() seeds the new kind from the old one, and set.seed()
+            // afterwards gives the same stream as seeding that kind directly.
+            let mut switched = RRng::new(7, RngKind::MersenneTwister);
+            switched.set_kind(kind);
+            assert_eq!(switched.kind(), kind);
+            switched.set_seed(2024);
+            let switched: Vec<f64> = (0..1000).map(|_| switched.unif_rand()).collect();
+            assert_eq!(switched, first, "{}", kind.name());
+        }
+        // RNGkind("Wichmann-Hill"); set.seed(1); .Random.seed; runif(5)
+        // The seeds are the scrambled seeds that set.seed(1) gives every kind,
+        // reduced modulo 30269, 30307 and 30323. The uniforms agree with
+        // Python 2's random.WichmannHill, an independent implementation of
+        // AS 183.
+        let mut rng = RRng::new(1, RngKind::WichmannHill);
+        assert_eq!(rng.random_seed(), vec![10400, 23415, 4903, 25333]);
+        for &e in &[0.1297134, 0.9822407, 0.8267184, 0.2423550, 0.8568853] {
+            let u = rng.unif_rand();
+            assert!(abs_diff_eq!(u, e, epsilon = 5e-8), "{} != {}", u, e);
+        }
+        // RNGkind("L'Ecuyer-CMRG"); set.seed(123); .Random.seed
+        let rng = RRng::new(123, RngKind::LecuyerCmrg);
+        assert_eq!(
+            rng.random_seed(),
+            vec![10407, 1806547166, -983674937, 643431772, 1162448557, -959247990, -133913213]
+        );
+        // MRG32k3a with all seeds 12345, as in L'Ecuyer's reference output.
+        let mut rng =
+            RRng::from_random_seed(&[10407, 12345, 12345, 12345, 12345, 12345, 12345]).unwrap();
+        for &e in &[0.127011, 0.318528, 0.309186, 0.825847, 0.221630] {
+            let u = rng.unif_rand();
+            assert!(abs_diff_eq!(u, e, epsilon = 5e-7), "{} != {}", u, e);
+        }
+    }
+
+    #[test]
+    fn test_rsignrank() {
+        let mut rng = Lcg(42);
+        assert!(rsignrank(-1.0, &mut rng).is_nan());
+        assert!(rsignrank(f64::NAN, &mut rng).is_nan());
+        assert_eq!(rsignrank(0.0, &mut rng), 0.0);
+        for &n in &[1.0, 2.0, 3.0, 10.0, 50.0, 1e3] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rsignrank(n) };
+                assert_eq!(rsignrank(n, &mut rng), expected, "rsignrank({})", n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_runif() {
+        let mut rng = Lcg(42);
+        assert!(runif(1.0, 0.0, &mut rng).is_nan());
+        assert!(runif(0.0, f64::INFINITY, &mut rng).is_nan());
+        assert_eq!(runif(2.0, 2.0, &mut rng), 2.0);
+        for &(a, b) in &[(0.0, 1.0), (-3.0, 0.5), (1e5, 1e10)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::runif(a, b) };
+                assert_eq!(runif(a, b, &mut rng), expected, "runif({}, {})", a, b);
+            }
+        }
+        // Draws of exactly 0 or 1 from a user-supplied source are skipped.
+        struct Fixed(Vec<f64>);
+        impl Rng for Fixed {
+            fn unif_rand(&mut self) -> f64 {
+                self.0.remove(0)
+            }
+        }
+        let mut rng = Fixed(vec![0.0, 1.0, 0.25]);
+        assert_eq!(runif(0.0, 2.0, &mut rng), 0.5);
+    }
+
+    #[test]
+    fn test_rweibull() {
+        let mut rng = Lcg(42);
+        assert!(rweibull(0.0, 1.0, &mut rng).is_nan());
+        assert!(rweibull(1.0, f64::INFINITY, &mut rng).is_nan());
+        assert_eq!(rweibull(2.0, 0.0, &mut rng), 0.0);
+        for &shape in &[0.3, 1.0, 2.5, 10.0] {
+            for &scale in &[1e-3, 1.0, 7.0] {
+                set_c_seed(7);
+                let mut rng = Lcg(7);
+                for _ in 0..1000 {
+                    let expected = unsafe { c::rweibull(shape, scale) };
+                    assert_eq!(
+                        rweibull(shape, scale, &mut rng),
+                        expected,
+                        "rweibull({}, {})",
+                        shape,
+                        scale
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rwilcox() {
+        let mut rng = Lcg(42);
+        assert!(rwilcox(-1.0, 3.0, &mut rng).is_nan());
+        assert!(rwilcox(f64::NAN, 3.0, &mut rng).is_nan());
+        assert_eq!(rwilcox(0.0, 3.0, &mut rng), 0.0);
+        take_warnings();
+        assert!(rwilcox(3.0, f64::INFINITY, &mut rng).is_nan());
+        assert!(rwilcox(1e3, WILCOX_DEFAULT_LIMIT as f64, &mut rng).is_nan());
+        assert_eq!(
+            take_warnings(),
+            vec![
+                MlWarning {
+                    kind: MlWarningKind::Range,
+                    function: "rwilcox"
+                };
+                2
+            ]
+        );
+        for &(m, n) in &[(1.0, 1.0), (2.0, 3.0), (7.0, 5.0), (30.0, 40.0), (1e3, 2e3)] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::rwilcox(m, n) };
+                assert_eq!(rwilcox(m, n, &mut rng), expected, "rwilcox({}, {})", m, n);
+            }
+        }
     }
 
     #[test]
@@ -445,4 +4021,31 @@ mod test_math {
         assert_eq!(tanpi(0.25), unsafe { c::tanpi(0.25) });
         assert_eq!(tanpi(0.234), unsafe { c::tanpi(0.234) });
     }
+
+    #[test]
+    fn test_unif_index() {
+        let mut rng = Lcg(42);
+        assert_eq!(rng.unif_index(0.0), 0.0);
+        assert_eq!(rng.unif_index(0.5), 0.0);
+        assert_eq!(rng.unif_index(1.0), 0.0);
+        for &dn in &[
+            2.0,
+            3.0,
+            10.0,
+            1000.0,
+            65537.0,
+            1e9,
+            2f64.powi(40),
+            2f64.powi(48) + 1.0,
+            1e15,
+            2f64.powi(53),
+        ] {
+            set_c_seed(7);
+            let mut rng = Lcg(7);
+            for _ in 0..1000 {
+                let expected = unsafe { c::R_unif_index(dn) };
+                assert_eq!(rng.unif_index(dn), expected, "unif_index({})", dn);
+            }
+        }
+    }
 }
@@ -187,6 +187,10 @@ mod test_math {
         helper(0.5, 10000.0, 0.2, true, false);
         helper(0.5, 10000.0, 0.2, false, false);
         helper(0.0, 0.0, 0.2, false, false);
+        // Large a and b near the mean use the asymptotic expansion `basym`.
+        helper(0.5, 1000.0, 1000.0, true, false);
+        helper(0.49, 1000.0, 1000.0, false, true);
+        helper(0.9, 3000.0, 300.0, true, false);
 
         // Based on a test in `d-p-q-r-tst-2.R` at line 850 from the R source code.
         assert_eq!(pbeta(0.0, 0.0, 3.0, true, false), 1.0);